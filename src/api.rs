@@ -56,6 +56,38 @@ pub fn select_auth_token(
     env_token.map(Ok).unwrap_or_else(|| token_store.get_token())
 }
 
+/// Send a JSON request outside the generated client.
+///
+/// Used for request bodies the generated types cannot express, such as an
+/// explicit `null` to clear a field. Errors are formatted like
+/// [`format_api_error`].
+pub async fn send_json(
+    client: &Client,
+    method: reqwest::Method,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let url = format!("{}{path}", client.baseurl());
+    let response = client
+        .client()
+        .request(method, url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    let value: serde_json::Value = response.json().await.unwrap_or(serde_json::Value::Null);
+    if !status.is_success() {
+        let message = value
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("request failed");
+        return Err(format!("{status}: {message}"));
+    }
+    Ok(value)
+}
+
 /// Format a Progenitor API error, extracting the server's `message` field
 /// from error responses instead of showing raw debug output.
 ///
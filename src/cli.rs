@@ -56,14 +56,20 @@ pub enum Command {
     Group(commands::group::GroupArgs),
     /// Work with iterations
     Iteration(commands::iteration::IterationArgs),
+    /// Work with objective key results
+    KeyResult(commands::key_result::KeyResultArgs),
     /// Work with labels
     Label(commands::label::LabelArgs),
     /// Work with workspace members
     Member(commands::member::MemberArgs),
+    /// Work with milestones
+    Milestone(commands::milestone::MilestoneArgs),
     /// Work with objectives
     Objective(commands::objective::ObjectiveArgs),
     /// Work with projects
     Project(commands::project::ProjectArgs),
+    /// Generate reports and analytics
+    Report(commands::report::ReportArgs),
     /// Search across Shortcut entities
     Search(commands::search::SearchArgs),
     /// Work with stories
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+/// Archive or unarchive an epic through the `archived` update field.
+pub async fn run(
+    id: i64,
+    archived: bool,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if out.is_dry_run() {
+        let body = serde_json::json!({ "archived": archived });
+        return out.dry_run_request("PUT", &format!("/api/v3/epics/{id}"), Some(&body));
+    }
+
+    let epic = client
+        .update_epic()
+        .epic_public_id(id)
+        .body_map(|b| b.archived(Some(archived)))
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to update epic: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::json!({"id": epic.id, "name": epic.name, "archived": epic.archived})
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{id}");
+        return Ok(());
+    }
+    let verb = if archived { "Archived" } else { "Unarchived" };
+    out_println!(out, "{verb} epic {} - {}", epic.id, epic.name);
+    Ok(())
+}
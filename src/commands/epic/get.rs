@@ -2,7 +2,7 @@ use std::error::Error;
 use std::path::Path;
 
 use crate::api;
-use crate::output::OutputConfig;
+use crate::output::{OutputConfig, style_health};
 
 use super::helpers::{resolve_epic_state_name, resolve_member_name};
 use crate::out_println;
@@ -26,6 +26,7 @@ pub async fn run(
             "name": epic.name,
             "epic_state_id": epic.epic_state_id,
             "description": epic.description,
+            "health": epic.health.as_ref().map(super::health::health_json),
         });
         out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
         return Ok(());
@@ -39,6 +40,13 @@ pub async fn run(
 
     out_println!(out, "{} - {}", epic.id, epic.name);
     out_println!(out, "  State:       {state_name}");
+    if let Some(health) = &epic.health {
+        let status = style_health(&health.status.to_string());
+        match health.text.as_deref().filter(|t| !t.is_empty()) {
+            Some(text) => out_println!(out, "  Health:      {status} - {text}"),
+            None => out_println!(out, "  Health:      {status}"),
+        }
+    }
     if let Some(dl) = &epic.deadline {
         out_println!(out, "  Deadline:    {}", dl.format("%Y-%m-%d"));
     }
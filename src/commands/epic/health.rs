@@ -0,0 +1,290 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::{Args, Subcommand};
+
+use crate::api;
+use crate::out_println;
+use crate::output::{OutputConfig, Table, style_health};
+
+use super::helpers::resolve_member_name;
+
+#[derive(Args)]
+pub struct HealthArgs {
+    #[command(subcommand)]
+    pub action: HealthAction,
+}
+
+#[derive(Subcommand)]
+pub enum HealthAction {
+    /// Show the current health of an epic
+    Get {
+        /// The ID of the epic
+        #[arg(long)]
+        id: i64,
+    },
+    /// Set the health of an epic
+    Set {
+        /// The ID of the epic
+        #[arg(long)]
+        id: i64,
+        /// on-track, at-risk, off-track or no-health
+        #[arg(long, value_parser = parse_status)]
+        status: api::types::CreateEpicHealthStatus,
+        /// A note explaining the status
+        #[arg(long, visible_alias = "text")]
+        note: Option<String>,
+    },
+    /// Show the health timeline of an epic
+    History {
+        /// The ID of the epic
+        #[arg(long)]
+        id: i64,
+    },
+}
+
+/// Accept both the API spelling ("On Track") and a flag-friendly one
+/// ("on-track").
+fn parse_status(s: &str) -> Result<api::types::CreateEpicHealthStatus, String> {
+    let key: String = s
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match key.as_str() {
+        "ontrack" => Ok(api::types::CreateEpicHealthStatus::OnTrack),
+        "atrisk" => Ok(api::types::CreateEpicHealthStatus::AtRisk),
+        "offtrack" => Ok(api::types::CreateEpicHealthStatus::OffTrack),
+        "nohealth" | "none" => Ok(api::types::CreateEpicHealthStatus::NoHealth),
+        _ => Err(format!(
+            "invalid health status '{s}' (expected on-track, at-risk, off-track or no-health)"
+        )),
+    }
+}
+
+pub async fn run(
+    args: &HealthArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    match &args.action {
+        HealthAction::Get { id } => run_get(*id, client, cache_dir, out).await,
+        HealthAction::Set { id, status, note } => {
+            run_set(*id, status, note.as_deref(), client, out).await
+        }
+        HealthAction::History { id } => run_history(*id, client, cache_dir, out).await,
+    }
+}
+
+/// Health lookups `fetch_healths` keeps in flight at once.
+const HEALTH_CONCURRENCY: usize = 8;
+
+/// Fetch the current health of each epic, a few at a time, in `ids` order.
+pub(super) async fn fetch_healths(
+    ids: &[i64],
+    client: &api::Client,
+) -> Result<Vec<Result<api::types::Health, String>>, Box<dyn Error>> {
+    let mut healths: Vec<Option<Result<api::types::Health, String>>> =
+        ids.iter().map(|_| None).collect();
+    let mut pending = ids.iter().copied().enumerate();
+    let mut tasks = tokio::task::JoinSet::new();
+    loop {
+        while tasks.len() < HEALTH_CONCURRENCY {
+            let Some((index, id)) = pending.next() else {
+                break;
+            };
+            let client = client.clone();
+            tasks.spawn(async move {
+                let health = client
+                    .get_epic_health()
+                    .epic_public_id(id)
+                    .send()
+                    .await
+                    .map(|h| h.into_inner())
+                    .map_err(|e| api::format_api_error(&e));
+                (index, health)
+            });
+        }
+        match tasks.join_next().await {
+            Some(joined) => {
+                let (index, health) = joined?;
+                healths[index] = Some(health);
+            }
+            None => break,
+        }
+    }
+    Ok(healths.into_iter().flatten().collect())
+}
+
+pub(super) fn health_json(health: &api::types::Health) -> serde_json::Value {
+    serde_json::json!({
+        "status": health.status.to_string(),
+        "text": health.text,
+        "author_id": health.author_id,
+        "updated_at": health.updated_at,
+    })
+}
+
+async fn run_get(
+    id: i64,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let health = client
+        .get_epic_health()
+        .epic_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get epic health: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&health_json(&health))?
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{}", health.status);
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "Epic {id}: {}",
+        style_health(&health.status.to_string())
+    );
+    if let Some(text) = health.text.as_deref().filter(|t| !t.is_empty()) {
+        out_println!(out, "  Note:    {text}");
+    }
+    if let Some(author) = &health.author_id {
+        out_println!(out, "  By:      {}", resolve_member_name(author, cache_dir));
+    }
+    if let Some(at) = health.updated_at.or(health.created_at) {
+        out_println!(out, "  Updated: {}", at.format("%Y-%m-%d %H:%M"));
+    }
+    Ok(())
+}
+
+async fn run_set(
+    id: i64,
+    status: &api::types::CreateEpicHealthStatus,
+    note: Option<&str>,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if out.is_dry_run() {
+        let mut body = serde_json::json!({ "status": status.to_string() });
+        if let Some(text) = note {
+            body["text"] = serde_json::Value::from(text);
+        }
+        return out.dry_run_request("POST", &format!("/api/v3/epics/{id}/health"), Some(&body));
+    }
+
+    let status = *status;
+    let text = note.map(String::from);
+    let health = client
+        .create_epic_health()
+        .epic_public_id(id)
+        .body_map(|b| b.status(status).text(text))
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to set epic health: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&health_json(&health))?
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{id}");
+        return Ok(());
+    }
+    out_println!(
+        out,
+        "Set epic {id} health to {}",
+        style_health(&health.status.to_string())
+    );
+    Ok(())
+}
+
+async fn run_history(
+    id: i64,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let mut entries = client
+        .list_epic_healths()
+        .epic_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list epic health history: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner();
+    entries.sort_by_key(|h| h.created_at);
+
+    if out.is_machine_readable() {
+        let json: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|h| {
+                let mut entry = health_json(h);
+                entry["created_at"] = serde_json::json!(h.created_at);
+                entry
+            })
+            .collect();
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    if out.is_quiet() {
+        for h in &entries {
+            out_println!(out, "{}", h.status);
+        }
+        return Ok(());
+    }
+    if entries.is_empty() {
+        out_println!(out, "No health updates for epic {id}");
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["Date", "Status", "By", "Note"]);
+    for h in &entries {
+        let date = h
+            .created_at
+            .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let author = h
+            .author_id
+            .as_ref()
+            .map(|a| resolve_member_name(a, cache_dir))
+            .unwrap_or_default();
+        table.add_row(vec![
+            date,
+            h.status.to_string(),
+            author,
+            h.text.clone().unwrap_or_default(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+    Ok(())
+}
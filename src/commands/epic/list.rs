@@ -2,10 +2,13 @@ use std::error::Error;
 
 use crate::api;
 use crate::out_println;
-use crate::output::{OutputConfig, Table};
+use crate::output::{OutputConfig, Table, style_health};
+
+use super::health::fetch_healths;
 
 pub async fn run(
     desc: bool,
+    archived: bool,
     client: &api::Client,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
@@ -13,31 +16,59 @@ pub async fn run(
     if desc {
         req = req.includes_description(true);
     }
-    let epics = req
+    let epics: Vec<_> = req
         .send()
         .await
-        .map_err(|e| format!("Failed to list epics: {}", crate::api::format_api_error(&e)))?;
+        .map_err(|e| format!("Failed to list epics: {}", crate::api::format_api_error(&e)))?
+        .into_inner()
+        .into_iter()
+        .filter(|e| archived || !e.archived)
+        .collect();
+
+    if out.is_quiet() {
+        for epic in epics.iter() {
+            out_println!(out, "{}", epic.id);
+        }
+        return Ok(());
+    }
+
+    // The list endpoint does not include health, so look it up per epic.
+    let ids: Vec<i64> = epics.iter().map(|e| e.id).collect();
+    let healths = fetch_healths(&ids, client).await?;
 
     if out.is_machine_readable() {
         let json: Vec<serde_json::Value> = epics
             .iter()
-            .map(|e| serde_json::json!({"id": e.id, "name": e.name}))
+            .zip(&healths)
+            .map(|(e, h)| {
+                let mut json = serde_json::json!({
+                    "id": e.id,
+                    "name": e.name,
+                    "health": h.as_ref().ok().map(|h| h.status.to_string()),
+                });
+                if let Err(e) = h {
+                    json["health_error"] = serde_json::Value::String(e.clone());
+                }
+                json
+            })
             .collect();
         out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
         return Ok(());
     }
-    if out.is_quiet() {
-        for epic in epics.iter() {
-            out_println!(out, "{}", epic.id);
-        }
-        return Ok(());
-    }
 
-    let mut table = Table::new(vec!["ID", "Name"]);
-    for epic in epics.iter() {
-        table.add_row(vec![epic.id.to_string(), epic.name.clone()]);
+    let mut table = Table::new(vec!["ID", "Name", "Health"]);
+    for (epic, health) in epics.iter().zip(&healths) {
+        let health = match health {
+            Ok(h) => style_health(&h.status.to_string()),
+            Err(_) => "?".to_string(),
+        };
+        table.add_row(vec![epic.id.to_string(), epic.name.clone(), health]);
     }
     out.write_str(format_args!("{}", table.render()))?;
+    let failed = healths.iter().filter(|h| h.is_err()).count();
+    if failed > 0 {
+        out_println!(out, "? = health could not be read ({failed} epic(s))");
+    }
 
     if desc {
         for epic in epics.iter() {
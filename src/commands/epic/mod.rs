@@ -1,15 +1,22 @@
+mod archive;
 mod create;
 mod delete;
 mod docs;
 mod get;
+mod health;
 pub(crate) mod helpers;
 mod list;
+mod progress;
+mod stories;
 mod update;
 pub mod wizard;
 
 pub mod comment;
 
 pub use create::CreateArgs;
+pub use health::{HealthAction, HealthArgs};
+pub use progress::ProgressArgs;
+pub use stories::StoriesArgs;
 pub use update::UpdateArgs;
 
 use std::error::Error;
@@ -33,6 +40,9 @@ pub enum EpicAction {
         /// Include epic descriptions in output
         #[arg(long, visible_alias = "descriptions")]
         desc: bool,
+        /// Include archived epics
+        #[arg(long)]
+        archived: bool,
     },
     /// Create a new epic
     Create(Box<create::CreateArgs>),
@@ -55,6 +65,24 @@ pub enum EpicAction {
         #[arg(long)]
         confirm: bool,
     },
+    /// Archive an epic (hidden from list and search by default)
+    Archive {
+        /// The ID of the epic to archive
+        #[arg(long)]
+        id: i64,
+    },
+    /// Unarchive an epic
+    Unarchive {
+        /// The ID of the epic to unarchive
+        #[arg(long)]
+        id: i64,
+    },
+    /// Get, set or show the history of an epic's health
+    Health(health::HealthArgs),
+    /// Show story and point progress, owner workload and a burnup chart
+    Progress(Box<progress::ProgressArgs>),
+    /// List an epic's stories grouped by workflow state, with estimate totals
+    Stories(Box<stories::StoriesArgs>),
     /// List documents linked to an epic
     Docs {
         /// The ID of the epic
@@ -70,7 +98,7 @@ pub async fn run(
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
     match &args.action {
-        EpicAction::List { desc } => list::run(*desc, client, out).await,
+        EpicAction::List { desc, archived } => list::run(*desc, *archived, client, out).await,
         EpicAction::Create(create_args) => {
             if create_args.interactive {
                 if !atty::is(atty::Stream::Stdin) {
@@ -99,6 +127,15 @@ pub async fn run(
         EpicAction::Update(update_args) => update::run(update_args, client, &cache_dir, out).await,
         EpicAction::Comment(args) => comment::run(args, client, &cache_dir, out).await,
         EpicAction::Delete { id, confirm } => delete::run(*id, *confirm, client, out).await,
+        EpicAction::Archive { id } => archive::run(*id, true, client, out).await,
+        EpicAction::Unarchive { id } => archive::run(*id, false, client, out).await,
+        EpicAction::Health(health_args) => health::run(health_args, client, &cache_dir, out).await,
+        EpicAction::Progress(progress_args) => {
+            progress::run(progress_args, client, &cache_dir, out).await
+        }
+        EpicAction::Stories(stories_args) => {
+            stories::run(stories_args, client, &cache_dir, out).await
+        }
         EpicAction::Docs { id } => docs::run(*id, client, out).await,
     }
 }
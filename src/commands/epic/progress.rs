@@ -0,0 +1,464 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{NaiveDate, Utc};
+use clap::Args;
+
+use crate::api;
+use crate::commands::story::helpers::{STATE_TYPE_ORDER, build_workflow_state_type_map};
+use crate::commands::story::history;
+use crate::out_println;
+use crate::output::{ChartSeries, OutputConfig, Table, ascii_chart, csv_row, percent};
+
+use super::helpers::resolve_member_name;
+
+#[derive(Args)]
+pub struct ProgressArgs {
+    /// The ID of the epic
+    #[arg(long)]
+    pub id: i64,
+
+    /// Print the burnup series as CSV
+    #[arg(long, conflicts_with = "no_burnup")]
+    pub csv: bool,
+
+    /// Skip the burnup series (avoids fetching story histories)
+    #[arg(long)]
+    pub no_burnup: bool,
+
+    /// Ignore cached story histories and fetch them again
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+const CHART_HEIGHT: usize = 10;
+
+#[derive(Default)]
+struct Tally {
+    stories: i64,
+    points: i64,
+}
+
+#[derive(Default)]
+struct Workload {
+    stories: i64,
+    points: i64,
+    remaining_stories: i64,
+    remaining_points: i64,
+}
+
+struct BurnupPoint {
+    date: NaiveDate,
+    scope_stories: i64,
+    done_stories: i64,
+    scope_points: i64,
+    done_points: i64,
+}
+
+struct Progress<'a> {
+    /// From the epic's stats, which count archived stories too.
+    by_type: BTreeMap<usize, (String, Tally)>,
+    total: Tally,
+    done: Tally,
+    /// From the listed stories, which leave archived ones out.
+    non_archived: usize,
+    owners: Vec<(String, Workload)>,
+    unestimated: Vec<&'a api::types::StorySlim>,
+}
+
+pub async fn run(
+    args: &ProgressArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let epic = client
+        .get_epic()
+        .epic_public_id(args.id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get epic: {}", crate::api::format_api_error(&e)))?;
+
+    let stories: Vec<api::types::StorySlim> = client
+        .list_epic_stories()
+        .epic_public_id(args.id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list epic stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|s| !s.archived)
+        .collect();
+
+    let state_types = build_workflow_state_type_map(client).await?;
+    let progress = summarize(&epic.stats, &stories, &state_types, cache_dir);
+
+    let burnup = if args.no_burnup {
+        Vec::new()
+    } else {
+        build_burnup(args, &stories, &state_types, client, cache_dir).await?
+    };
+
+    if args.csv {
+        return print_csv(&burnup, out);
+    }
+
+    if out.is_machine_readable() {
+        let json = build_json(epic.id, &epic.name, &progress, &burnup);
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(
+            out,
+            "{}",
+            percent(progress.done.points as f64, progress.total.points as f64)
+        );
+        return Ok(());
+    }
+
+    print_summary(epic.id, &epic.name, &progress, &burnup, out)
+}
+
+/// Counts and points come from the epic's stats, which include archived
+/// stories; owner workload and unestimated stories from the non-archived
+/// stories listed.
+fn summarize<'a>(
+    stats: &api::types::EpicStats,
+    stories: &'a [api::types::StorySlim],
+    state_types: &HashMap<i64, String>,
+    cache_dir: &Path,
+) -> Progress<'a> {
+    let mut by_type: BTreeMap<usize, (String, Tally)> = BTreeMap::new();
+    for (order, state_type) in STATE_TYPE_ORDER.iter().enumerate() {
+        let (stories, points) = match *state_type {
+            "backlog" => (stats.num_stories_backlog, stats.num_points_backlog),
+            "unstarted" => (stats.num_stories_unstarted, stats.num_points_unstarted),
+            "started" => (stats.num_stories_started, stats.num_points_started),
+            _ => (stats.num_stories_done, stats.num_points_done),
+        };
+        if stories > 0 || points > 0 {
+            by_type.insert(order, (state_type.to_string(), Tally { stories, points }));
+        }
+    }
+    let total = Tally {
+        stories: stats.num_stories_total,
+        points: stats.num_points,
+    };
+    let done = Tally {
+        stories: stats.num_stories_done,
+        points: stats.num_points_done,
+    };
+    let mut owners: HashMap<String, Workload> = HashMap::new();
+    let mut unestimated = Vec::new();
+
+    for story in stories {
+        let points = story.estimate.unwrap_or(0);
+        let is_done = state_types
+            .get(&story.workflow_state_id)
+            .is_some_and(|t| t == "done");
+
+        let names: Vec<String> = if story.owner_ids.is_empty() {
+            vec!["(unowned)".to_string()]
+        } else {
+            story
+                .owner_ids
+                .iter()
+                .map(|id| resolve_member_name(id, cache_dir))
+                .collect()
+        };
+        for name in names {
+            let w = owners.entry(name).or_default();
+            w.stories += 1;
+            w.points += points;
+            if !is_done {
+                w.remaining_stories += 1;
+                w.remaining_points += points;
+            }
+        }
+
+        if story.estimate.is_none() {
+            unestimated.push(story);
+        }
+    }
+
+    let mut owners: Vec<(String, Workload)> = owners.into_iter().collect();
+    owners.sort_by(|a, b| {
+        b.1.remaining_points
+            .cmp(&a.1.remaining_points)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    Progress {
+        by_type,
+        total,
+        done,
+        non_archived: stories.len(),
+        owners,
+        unestimated,
+    }
+}
+
+/// Reconstruct daily scope and completion from each story's history. A story
+/// counts towards scope from the day it joined the epic (or was created) and
+/// towards done on days that end with it in a done state. Current estimates
+/// are used throughout.
+async fn build_burnup(
+    args: &ProgressArgs,
+    stories: &[api::types::StorySlim],
+    state_types: &HashMap<i64, String>,
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<BurnupPoint>, Box<dyn Error>> {
+    let timelines = history::story_timelines(
+        stories,
+        |entries| history::epic_added_at(entries, args.id),
+        state_types,
+        client,
+        cache_dir,
+        args.refresh,
+    )
+    .await?;
+
+    let Some(start) = timelines.iter().map(|t| t.added).min() else {
+        return Ok(Vec::new());
+    };
+    let all_done = stories.iter().all(|s| {
+        state_types
+            .get(&s.workflow_state_id)
+            .is_some_and(|t| t == "done")
+    });
+    let last_event = timelines
+        .iter()
+        .map(|t| t.last_change())
+        .max()
+        .unwrap_or(start);
+    let end = if all_done {
+        last_event
+    } else {
+        Utc::now().date_naive().max(last_event)
+    };
+
+    let mut series = Vec::new();
+    for date in start.iter_days().take_while(|d| *d <= end) {
+        let mut point = BurnupPoint {
+            date,
+            scope_stories: 0,
+            done_stories: 0,
+            scope_points: 0,
+            done_points: 0,
+        };
+        for t in timelines.iter().filter(|t| t.added <= date) {
+            point.scope_stories += 1;
+            point.scope_points += t.points;
+            if t.done_on(date) {
+                point.done_stories += 1;
+                point.done_points += t.points;
+            }
+        }
+        series.push(point);
+    }
+    Ok(series)
+}
+
+fn build_json(
+    id: i64,
+    name: &str,
+    progress: &Progress,
+    burnup: &[BurnupPoint],
+) -> serde_json::Value {
+    serde_json::json!({
+        "epic": { "id": id, "name": name },
+        "stories": {
+            "total": progress.total.stories,
+            "done": progress.done.stories,
+            "percent": percent(progress.done.stories as f64, progress.total.stories as f64),
+            "non_archived": progress.non_archived,
+        },
+        "points": {
+            "total": progress.total.points,
+            "done": progress.done.points,
+            "percent": percent(progress.done.points as f64, progress.total.points as f64),
+        },
+        "by_state_type": progress
+            .by_type
+            .values()
+            .map(|(t, tally)| serde_json::json!({
+                "type": t,
+                "stories": tally.stories,
+                "points": tally.points,
+            }))
+            .collect::<Vec<_>>(),
+        "owners": progress
+            .owners
+            .iter()
+            .map(|(owner, w)| serde_json::json!({
+                "owner": owner,
+                "stories": w.stories,
+                "points": w.points,
+                "remaining_stories": w.remaining_stories,
+                "remaining_points": w.remaining_points,
+            }))
+            .collect::<Vec<_>>(),
+        "unestimated": progress
+            .unestimated
+            .iter()
+            .map(|s| serde_json::json!({ "id": s.id, "name": s.name }))
+            .collect::<Vec<_>>(),
+        "burnup": burnup
+            .iter()
+            .map(|p| serde_json::json!({
+                "date": p.date.to_string(),
+                "scope_stories": p.scope_stories,
+                "done_stories": p.done_stories,
+                "scope_points": p.scope_points,
+                "done_points": p.done_points,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn print_csv(burnup: &[BurnupPoint], out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    out_println!(
+        out,
+        "{}",
+        csv_row(&[
+            "date".to_string(),
+            "scope_stories".to_string(),
+            "done_stories".to_string(),
+            "scope_points".to_string(),
+            "done_points".to_string(),
+        ])
+    );
+    for p in burnup {
+        out_println!(
+            out,
+            "{}",
+            csv_row(&[
+                p.date.to_string(),
+                p.scope_stories.to_string(),
+                p.done_stories.to_string(),
+                p.scope_points.to_string(),
+                p.done_points.to_string(),
+            ])
+        );
+    }
+    Ok(())
+}
+
+fn print_summary(
+    id: i64,
+    name: &str,
+    progress: &Progress,
+    burnup: &[BurnupPoint],
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    out_println!(out, "{id} - {name}");
+    out_println!(
+        out,
+        "  Stories: {}/{} done ({}%)",
+        progress.done.stories,
+        progress.total.stories,
+        percent(progress.done.stories as f64, progress.total.stories as f64)
+    );
+    out_println!(
+        out,
+        "  Points:  {}/{} done ({}%)",
+        progress.done.points,
+        progress.total.points,
+        percent(progress.done.points as f64, progress.total.points as f64)
+    );
+
+    if progress.total.stories == 0 {
+        return Ok(());
+    }
+    out_println!(
+        out,
+        "  Totals and state types are from the epic's stats and include archived stories;"
+    );
+    out_println!(
+        out,
+        "  owners and unestimated stories cover the {} non-archived ones.",
+        progress.non_archived
+    );
+
+    out_println!(out, "");
+    let mut table = Table::new(vec!["State type", "Stories", "Points"]);
+    for (state_type, tally) in progress.by_type.values() {
+        table.add_row(vec![
+            state_type.clone(),
+            tally.stories.to_string(),
+            tally.points.to_string(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    out_println!(out, "");
+    out_println!(out, "Owners:");
+    let mut table = Table::new(vec!["Owner", "Stories", "Points", "Remaining"]);
+    for (owner, w) in &progress.owners {
+        table.add_row(vec![
+            owner.clone(),
+            w.stories.to_string(),
+            w.points.to_string(),
+            format!("{} ({} pts)", w.remaining_stories, w.remaining_points),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    if !progress.unestimated.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Unestimated stories ({}):", progress.unestimated.len());
+        for story in &progress.unestimated {
+            out_println!(out, "  {} - {}", story.id, story.name);
+        }
+    }
+
+    if let (Some(first), Some(last)) = (burnup.first(), burnup.last()) {
+        let by_points = progress.total.points > 0;
+        let (scope, done): (Vec<f64>, Vec<f64>) = burnup
+            .iter()
+            .map(|p| {
+                if by_points {
+                    (p.scope_points as f64, p.done_points as f64)
+                } else {
+                    (p.scope_stories as f64, p.done_stories as f64)
+                }
+            })
+            .unzip();
+        let first_label = first.date.to_string();
+        let last_label = last.date.to_string();
+
+        out_println!(out, "");
+        out_println!(
+            out,
+            "Burnup ({}):",
+            if by_points { "points" } else { "stories" }
+        );
+        let chart = ascii_chart(
+            &[
+                ChartSeries {
+                    name: "Scope",
+                    marker: '-',
+                    values: &scope,
+                },
+                ChartSeries {
+                    name: "Done",
+                    marker: '*',
+                    values: &done,
+                },
+            ],
+            CHART_HEIGHT,
+            (&first_label, &last_label),
+        );
+        out.write_str(format_args!("{chart}"))?;
+    }
+    Ok(())
+}
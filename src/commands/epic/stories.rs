@@ -0,0 +1,172 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::commands::member;
+use crate::commands::story::helpers::{
+    STORY_TYPES, build_workflow_state_id_map, build_workflow_state_type_map, group_by_state,
+    resolve_workflow_state_id,
+};
+use crate::out_println;
+use crate::output::{OutputConfig, Table};
+
+use super::helpers::resolve_member_name;
+
+#[derive(Args)]
+pub struct StoriesArgs {
+    /// The ID of the epic
+    #[arg(long)]
+    pub id: i64,
+
+    /// Filter by owner (@mention_name or UUID)
+    #[arg(long)]
+    pub owner: Option<String>,
+
+    /// Filter by workflow state name or ID
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Filter by story type (feature, bug, chore)
+    #[arg(long, name = "type")]
+    pub story_type: Option<String>,
+
+    /// Filter by label name
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Include archived stories
+    #[arg(long)]
+    pub archived: bool,
+}
+
+pub async fn run(
+    args: &StoriesArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let owner_id = match &args.owner {
+        Some(val) => Some(member::resolve_member_id(val, client, cache_dir).await?),
+        None => None,
+    };
+    let state_id = match &args.state {
+        Some(val) => Some(resolve_workflow_state_id(val, client, cache_dir).await?),
+        None => None,
+    };
+    let story_type = match args.story_type.as_deref().map(str::to_lowercase) {
+        Some(t) if !STORY_TYPES.contains(&t.as_str()) => {
+            return Err(format!("Invalid story type: {t} (expected feature, bug or chore)").into());
+        }
+        other => other,
+    };
+    let label = args.label.as_deref().map(str::to_lowercase);
+
+    let stories: Vec<api::types::StorySlim> = client
+        .list_epic_stories()
+        .epic_public_id(args.id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list epic stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|s| args.archived || !s.archived)
+        .filter(|s| owner_id.is_none_or(|id| s.owner_ids.contains(&id)))
+        .filter(|s| state_id.is_none_or(|id| s.workflow_state_id == id))
+        .filter(|s| story_type.as_ref().is_none_or(|t| s.story_type == *t))
+        .filter(|s| {
+            label
+                .as_ref()
+                .is_none_or(|l| s.labels.iter().any(|sl| sl.name.to_lowercase() == *l))
+        })
+        .collect();
+
+    if out.is_quiet() {
+        for story in &stories {
+            out_println!(out, "{}", story.id);
+        }
+        return Ok(());
+    }
+
+    let state_names = build_workflow_state_id_map(client, cache_dir).await?;
+    let state_types = build_workflow_state_type_map(client).await?;
+    let groups = group_by_state(&stories, &state_names, &state_types);
+    let total_points: i64 = groups.iter().map(|g| g.points).sum();
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "epic_id": args.id,
+            "total": { "stories": stories.len(), "points": total_points },
+            "states": groups.iter().map(|g| serde_json::json!({
+                "workflow_state_id": g.state_id,
+                "state": g.name,
+                "points": g.points,
+                "stories": g.stories.iter().map(|s| serde_json::json!({
+                    "id": s.id,
+                    "name": s.name,
+                    "story_type": s.story_type,
+                    "estimate": s.estimate,
+                    "owner_ids": s.owner_ids,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if stories.is_empty() {
+        out_println!(out, "No stories found");
+        return Ok(());
+    }
+
+    for group in &groups {
+        out_println!(
+            out,
+            "{} ({}, {})",
+            group.name,
+            count(group.stories.len() as i64, "story", "stories"),
+            count(group.points, "point", "points"),
+        );
+        let mut table = Table::new(vec!["ID", "Type", "Est", "Owners", "Name"]);
+        for story in &group.stories {
+            let owners: Vec<String> = story
+                .owner_ids
+                .iter()
+                .map(|id| resolve_member_name(id, cache_dir))
+                .collect();
+            table.add_row(vec![
+                story.id.to_string(),
+                story.story_type.clone(),
+                story
+                    .estimate
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                owners.join(", "),
+                story.name.clone(),
+            ]);
+        }
+        out.write_str(format_args!("{}", table.render()))?;
+        out_println!(out, "");
+    }
+    out_println!(
+        out,
+        "Total: {}, {}",
+        count(stories.len() as i64, "story", "stories"),
+        count(total_points, "point", "points"),
+    );
+    Ok(())
+}
+
+fn count(n: i64, singular: &str, plural: &str) -> String {
+    if n == 1 {
+        format!("{n} {singular}")
+    } else {
+        format!("{n} {plural}")
+    }
+}
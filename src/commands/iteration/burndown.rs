@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{NaiveDate, Utc};
+use clap::Args;
+
+use crate::api;
+use crate::commands::story::helpers::build_workflow_state_type_map;
+use crate::commands::story::history;
+use crate::out_println;
+use crate::output::{ChartSeries, OutputConfig, ascii_chart, csv_row, round_tenth};
+
+use super::helpers::{IterationRef, resolve_iteration_id};
+
+#[derive(Args)]
+pub struct BurndownArgs {
+    /// The ID of the iteration (or current, next, previous)
+    #[arg(long)]
+    pub id: IterationRef,
+
+    /// Print the daily series as CSV
+    #[arg(long)]
+    pub csv: bool,
+
+    /// Ignore cached story histories and fetch them again
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+const CHART_HEIGHT: usize = 10;
+
+struct BurndownPoint {
+    date: NaiveDate,
+    /// `None` for days that have not happened yet.
+    remaining: Option<i64>,
+    ideal: f64,
+}
+
+pub async fn run(
+    args: &BurndownArgs,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let id = resolve_iteration_id(args.id, group, client, cache_dir).await?;
+    let iteration = client
+        .get_iteration()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get iteration: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    let stories: Vec<api::types::StorySlim> = client
+        .list_iteration_stories()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|s| !s.archived)
+        .collect();
+
+    let state_types = build_workflow_state_type_map(client).await?;
+    let start = iteration.start_date.date_naive();
+    let end = iteration.end_date.date_naive();
+    let series = build_burndown(
+        args,
+        id,
+        &stories,
+        &state_types,
+        (start, end),
+        client,
+        cache_dir,
+    )
+    .await?;
+    let scope: i64 = stories.iter().map(|s| s.estimate.unwrap_or(0)).sum();
+    let latest = series
+        .iter()
+        .rev()
+        .find_map(|p| p.remaining.map(|r| (p.date, r)));
+
+    if args.csv {
+        return print_csv(&series, out);
+    }
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "iteration": {
+                "id": iteration.id,
+                "name": iteration.name,
+                "start_date": start.to_string(),
+                "end_date": end.to_string(),
+            },
+            "scope_points": scope,
+            "remaining_points": latest.map(|(_, r)| r),
+            "burndown": series.iter().map(|p| serde_json::json!({
+                "date": p.date.to_string(),
+                "remaining_points": p.remaining,
+                "ideal_points": p.ideal,
+            })).collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", latest.map(|(_, r)| r).unwrap_or(scope));
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "{} - {} ({} to {})",
+        iteration.id,
+        iteration.name,
+        start,
+        end
+    );
+    out_println!(out, "  Scope:     {scope} points");
+    match latest {
+        Some((date, remaining)) => {
+            out_println!(out, "  Remaining: {remaining} points (as of {date})")
+        }
+        None => out_println!(out, "  Remaining: {scope} points (not started)"),
+    }
+
+    if let (Some(first), Some(last)) = (series.first(), series.last()) {
+        let remaining: Vec<f64> = series
+            .iter()
+            .map_while(|p| p.remaining.map(|r| r as f64))
+            .collect();
+        let ideal: Vec<f64> = series.iter().map(|p| p.ideal).collect();
+        let first_label = first.date.to_string();
+        let last_label = last.date.to_string();
+
+        out_println!(out, "");
+        out_println!(out, "Burndown (points):");
+        let chart = ascii_chart(
+            &[
+                ChartSeries {
+                    name: "Ideal",
+                    marker: '.',
+                    values: &ideal,
+                },
+                ChartSeries {
+                    name: "Remaining",
+                    marker: '*',
+                    values: &remaining,
+                },
+            ],
+            CHART_HEIGHT,
+            (&first_label, &last_label),
+        );
+        out.write_str(format_args!("{chart}"))?;
+    }
+    Ok(())
+}
+
+/// Reconstruct the points left at the end of each day of the iteration from
+/// each story's history. A story counts from the day it joined the iteration
+/// (or the first day, if it was there from the start) and stops counting on
+/// days that end with it in a done state. Current estimates are used
+/// throughout. The ideal line runs from the first day's remaining points down
+/// to zero on the last day.
+async fn build_burndown(
+    args: &BurndownArgs,
+    id: i64,
+    stories: &[api::types::StorySlim],
+    state_types: &HashMap<i64, String>,
+    (start, end): (NaiveDate, NaiveDate),
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<BurndownPoint>, Box<dyn Error>> {
+    let timelines = history::story_timelines(
+        stories,
+        |entries| history::iteration_added_at(entries, id),
+        state_types,
+        client,
+        cache_dir,
+        args.refresh,
+    )
+    .await?;
+
+    let remaining_on = |date: NaiveDate| -> i64 {
+        timelines
+            .iter()
+            .filter(|t| t.added <= date && !t.done_on(date))
+            .map(|t| t.points)
+            .sum()
+    };
+
+    let today = Utc::now().date_naive();
+    let days: Vec<NaiveDate> = start.iter_days().take_while(|d| *d <= end).collect();
+    let initial = remaining_on(start) as f64;
+    let steps = days.len().saturating_sub(1).max(1) as f64;
+
+    Ok(days
+        .iter()
+        .enumerate()
+        .map(|(i, date)| BurndownPoint {
+            date: *date,
+            remaining: (*date <= today).then(|| remaining_on(*date)),
+            ideal: round_tenth(initial * (1.0 - i as f64 / steps)),
+        })
+        .collect())
+}
+
+fn print_csv(series: &[BurndownPoint], out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    out_println!(
+        out,
+        "{}",
+        csv_row(&[
+            "date".to_string(),
+            "remaining_points".to_string(),
+            "ideal_points".to_string(),
+        ])
+    );
+    for p in series {
+        out_println!(
+            out,
+            "{}",
+            csv_row(&[
+                p.date.to_string(),
+                p.remaining.map(|r| r.to_string()).unwrap_or_default(),
+                p.ideal.to_string(),
+            ])
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,333 @@
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use clap::Args;
+use colored::Colorize;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::helpers::list_group_iterations;
+
+#[derive(Args)]
+pub struct CalendarArgs {
+    /// Show the whole quarter instead of a single month
+    #[arg(long)]
+    pub quarter: bool,
+
+    /// A day in the month or quarter to show (default: today)
+    #[arg(long, value_name = "YYYY-MM-DD|YYYY-MM")]
+    pub date: Option<String>,
+}
+
+const MAX_LABEL_WIDTH: usize = 28;
+
+struct Overlap<'a> {
+    first: &'a api::types::IterationSlim,
+    second: &'a api::types::IterationSlim,
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+struct Gap<'a> {
+    after: &'a api::types::IterationSlim,
+    before: &'a api::types::IterationSlim,
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+pub async fn run(
+    args: &CalendarArgs,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let day = match &args.date {
+        Some(val) => parse_day(val)?,
+        None => Utc::now().date_naive(),
+    };
+    let (from, to, title) = if args.quarter {
+        quarter_range(day)
+    } else {
+        month_range(day)
+    };
+
+    let mut all = list_group_iterations(group, client, cache_dir).await?;
+    all.sort_by_key(|i| (i.start_date, i.id));
+    let iterations: Vec<&api::types::IterationSlim> = all
+        .iter()
+        .filter(|i| start_of(i) <= to && end_of(i) >= from)
+        .collect();
+
+    let overlaps = find_overlaps(&iterations);
+    // Gaps are found across all iterations so that ones running into the
+    // period from either side are reported too.
+    let gaps: Vec<Gap> = find_gaps(&all)
+        .into_iter()
+        .filter(|g| g.start <= to && g.end >= from)
+        .collect();
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "iterations": iterations.iter().map(|i| serde_json::json!({
+                "id": i.id,
+                "name": i.name,
+                "status": i.status,
+                "start_date": start_of(i).to_string(),
+                "end_date": end_of(i).to_string(),
+            })).collect::<Vec<_>>(),
+            "overlaps": overlaps.iter().map(|o| serde_json::json!({
+                "iterations": [o.first.id, o.second.id],
+                "start_date": o.start.to_string(),
+                "end_date": o.end.to_string(),
+                "days": days_between(o.start, o.end),
+            })).collect::<Vec<_>>(),
+            "gaps": gaps.iter().map(|g| serde_json::json!({
+                "after": g.after.id,
+                "before": g.before.id,
+                "start_date": g.start.to_string(),
+                "end_date": g.end.to_string(),
+                "days": days_between(g.start, g.end),
+            })).collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        for iteration in &iterations {
+            out_println!(out, "{}", iteration.id);
+        }
+        return Ok(());
+    }
+
+    out_println!(out, "{title} ({from} to {to})");
+    if iterations.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "No iterations in this period");
+        return Ok(());
+    }
+
+    let days: Vec<NaiveDate> = from.iter_days().take_while(|d| *d <= to).collect();
+    let labels: Vec<String> = iterations.iter().map(|i| label(i)).collect();
+    let width = labels
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Coverage".len());
+
+    out_println!(out, "");
+    out_println!(
+        out,
+        "{:width$}  {}",
+        "",
+        ruler(&days, args.quarter).trim_end()
+    );
+    for (iteration, label) in iterations.iter().zip(&labels) {
+        let bar: String = days
+            .iter()
+            .map(|d| {
+                if *d >= start_of(iteration) && *d <= end_of(iteration) {
+                    '█'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        out_println!(out, "{label:>width$}  {}", bar.trim_end());
+    }
+    let coverage: String = days
+        .iter()
+        .map(|d| {
+            let covering = iterations
+                .iter()
+                .filter(|i| *d >= start_of(i) && *d <= end_of(i))
+                .count();
+            match covering {
+                0 if is_weekend(*d) => " ".to_string(),
+                0 => "·".yellow().to_string(),
+                1 => "─".to_string(),
+                _ => "!".red().bold().to_string(),
+            }
+        })
+        .collect();
+    out_println!(out, "{:>width$}  {}", "Coverage", coverage.trim_end());
+
+    if !overlaps.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Overlaps:");
+        for o in &overlaps {
+            out_println!(
+                out,
+                "  {} and {}: {} to {} ({})",
+                o.first.name,
+                o.second.name,
+                o.start,
+                o.end,
+                plural_days(days_between(o.start, o.end))
+            );
+        }
+    }
+    if !gaps.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Gaps:");
+        for g in &gaps {
+            out_println!(
+                out,
+                "  {} to {} ({}) between {} and {}",
+                g.start,
+                g.end,
+                plural_days(days_between(g.start, g.end)),
+                g.after.name,
+                g.before.name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn start_of(iteration: &api::types::IterationSlim) -> NaiveDate {
+    iteration.start_date.date_naive()
+}
+
+fn end_of(iteration: &api::types::IterationSlim) -> NaiveDate {
+    iteration.end_date.date_naive()
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, Box<dyn Error>> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d"))
+        .map_err(|_| format!("Invalid --date '{value}' (expected YYYY-MM-DD or YYYY-MM)").into())
+}
+
+fn first_of_month(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).expect("valid month")
+}
+
+/// The last day of the month `months` after the month of `first`.
+fn end_of_month_after(first: NaiveDate, months: u32) -> NaiveDate {
+    let index = first.month0() + months + 1;
+    let next = first_of_month(first.year() + (index / 12) as i32, index % 12 + 1);
+    next - Duration::days(1)
+}
+
+fn month_range(day: NaiveDate) -> (NaiveDate, NaiveDate, String) {
+    let first = first_of_month(day.year(), day.month());
+    let title = first.format("%B %Y").to_string();
+    (first, end_of_month_after(first, 0), title)
+}
+
+fn quarter_range(day: NaiveDate) -> (NaiveDate, NaiveDate, String) {
+    let quarter = day.month0() / 3;
+    let first = first_of_month(day.year(), quarter * 3 + 1);
+    let title = format!("Q{} {}", quarter + 1, day.year());
+    (first, end_of_month_after(first, 2), title)
+}
+
+fn label(iteration: &api::types::IterationSlim) -> String {
+    let label = format!("{} {}", iteration.id, iteration.name);
+    if label.chars().count() <= MAX_LABEL_WIDTH {
+        label
+    } else {
+        let truncated: String = label.chars().take(MAX_LABEL_WIDTH - 1).collect();
+        format!("{truncated}…")
+    }
+}
+
+/// Column markers: the day of the month on each Monday, or the month name on
+/// the first of each month for a quarter. Markers that would collide with
+/// the previous one are skipped.
+fn ruler(days: &[NaiveDate], quarter: bool) -> String {
+    let mut line: Vec<char> = vec![' '; days.len()];
+    let mut free_from = 0;
+    for (col, day) in days.iter().enumerate() {
+        let mark = if quarter {
+            (day.day() == 1).then(|| day.format("%b").to_string())
+        } else {
+            (day.weekday() == Weekday::Mon).then(|| day.day().to_string())
+        };
+        let Some(mark) = mark else {
+            continue;
+        };
+        if col < free_from || col + mark.len() > days.len() {
+            continue;
+        }
+        for (i, c) in mark.chars().enumerate() {
+            line[col + i] = c;
+        }
+        free_from = col + mark.len() + 1;
+    }
+    line.into_iter().collect()
+}
+
+fn is_weekend(day: NaiveDate) -> bool {
+    matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn days_between(start: NaiveDate, end: NaiveDate) -> i64 {
+    (end - start).num_days() + 1
+}
+
+fn plural_days(days: i64) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{days} days")
+    }
+}
+
+/// Every pair of iterations whose dates intersect.
+fn find_overlaps<'a>(iterations: &[&'a api::types::IterationSlim]) -> Vec<Overlap<'a>> {
+    let mut overlaps = Vec::new();
+    for (i, first) in iterations.iter().copied().enumerate() {
+        for second in iterations[i + 1..].iter().copied() {
+            let start = start_of(first).max(start_of(second));
+            let end = end_of(first).min(end_of(second));
+            if start <= end {
+                overlaps.push(Overlap {
+                    first,
+                    second,
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// Stretches between consecutive iterations (sorted by start date) that no
+/// iteration covers. Gaps made up only of weekend days are not reported.
+fn find_gaps(iterations: &[api::types::IterationSlim]) -> Vec<Gap<'_>> {
+    let mut gaps = Vec::new();
+    let Some(mut latest) = iterations.first() else {
+        return gaps;
+    };
+    for next in &iterations[1..] {
+        let start = end_of(latest) + Duration::days(1);
+        let end = start_of(next) - Duration::days(1);
+        if start <= end
+            && start
+                .iter_days()
+                .take_while(|d| *d <= end)
+                .any(|d| !is_weekend(d))
+        {
+            gaps.push(Gap {
+                after: latest,
+                before: next,
+                start,
+                end,
+            });
+        }
+        if end_of(next) > end_of(latest) {
+            latest = next;
+        }
+    }
+    gaps
+}
@@ -1,9 +1,126 @@
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{NaiveDate, Utc};
 
 use crate::api;
+use crate::commands::group::helpers::resolve_group_id;
 use crate::commands::member;
 
+/// An iteration given on the command line: a numeric ID, or `current`,
+/// `next` or `previous` relative to today.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterationRef {
+    Id(i64),
+    Current,
+    Next,
+    Previous,
+}
+
+impl FromStr for IterationRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "current" => Ok(Self::Current),
+            "next" => Ok(Self::Next),
+            "previous" | "prev" => Ok(Self::Previous),
+            other => other.parse().map(Self::Id).map_err(|_| {
+                format!("invalid iteration '{s}' (expected an ID, current, next or previous)")
+            }),
+        }
+    }
+}
+
+impl From<i64> for IterationRef {
+    fn from(id: i64) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl fmt::Display for IterationRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{id}"),
+            Self::Current => f.write_str("current"),
+            Self::Next => f.write_str("next"),
+            Self::Previous => f.write_str("previous"),
+        }
+    }
+}
+
+/// List iterations, keeping only those of `group` (@mention_name or UUID)
+/// when one is given.
+pub async fn list_group_iterations(
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<api::types::IterationSlim>, Box<dyn Error>> {
+    let group_id = match group {
+        Some(val) => Some(resolve_group_id(val, client, cache_dir).await?),
+        None => None,
+    };
+    let iterations = client.list_iterations().send().await.map_err(|e| {
+        format!(
+            "Failed to list iterations: {}",
+            crate::api::format_api_error(&e)
+        )
+    })?;
+    Ok(iterations
+        .into_inner()
+        .into_iter()
+        .filter(|i| group_id.is_none_or(|g| i.group_ids.contains(&g)))
+        .collect())
+}
+
+/// Resolve an [`IterationRef`] to an iteration ID. Relative references only
+/// consider iterations of `group` when one is given.
+pub async fn resolve_iteration_id(
+    iteration: IterationRef,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<i64, Box<dyn Error>> {
+    if let IterationRef::Id(id) = iteration {
+        return Ok(id);
+    }
+    let iterations = list_group_iterations(group, client, cache_dir).await?;
+    pick_relative(&iterations, iteration, Utc::now().date_naive())
+        .map(|i| i.id)
+        .ok_or_else(|| {
+            let scope = group.map(|g| format!(" for group {g}")).unwrap_or_default();
+            format!("No {iteration} iteration found{scope}").into()
+        })
+}
+
+/// The iteration running on `today` (the most recently started one if
+/// several overlap), the first one starting after it, or the last one that
+/// ended before it.
+fn pick_relative(
+    iterations: &[api::types::IterationSlim],
+    which: IterationRef,
+    today: NaiveDate,
+) -> Option<&api::types::IterationSlim> {
+    let start = |i: &api::types::IterationSlim| i.start_date.date_naive();
+    let end = |i: &api::types::IterationSlim| i.end_date.date_naive();
+    let mut candidates = iterations.iter();
+    match which {
+        IterationRef::Id(id) => candidates.find(|i| i.id == id),
+        IterationRef::Current => candidates
+            .filter(|i| start(i) <= today && end(i) >= today)
+            .max_by_key(|i| (i.start_date, i.id)),
+        IterationRef::Next => candidates
+            .filter(|i| start(i) > today)
+            .min_by_key(|i| (i.start_date, i.id)),
+        IterationRef::Previous => candidates
+            .filter(|i| end(i) < today)
+            .max_by_key(|i| (i.end_date, i.id)),
+    }
+}
+
 /// Fetch current and future iterations as `IdChoice` items for the story wizard.
 pub async fn fetch_iteration_choices(
     client: &api::Client,
@@ -22,7 +139,7 @@ pub async fn fetch_iteration_choices(
             id: i.id,
         })
         .collect();
-    choices.sort_by(|a, b| a.display.to_lowercase().cmp(&b.display.to_lowercase()));
+    choices.sort_by_key(|a| a.display.to_lowercase());
     Ok(choices)
 }
 
@@ -38,3 +155,51 @@ pub async fn resolve_followers(
     }
     Ok(ids)
 }
+
+/// Stories of an iteration that are not archived and not in a done state.
+pub(super) async fn unfinished_stories(
+    id: i64,
+    state_types: &HashMap<i64, String>,
+    client: &api::Client,
+) -> Result<Vec<api::types::StorySlim>, Box<dyn Error>> {
+    Ok(client
+        .list_iteration_stories()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|s| !s.archived)
+        .filter(|s| state_types.get(&s.workflow_state_id).map(String::as_str) != Some("done"))
+        .collect())
+}
+
+/// Move stories into iteration `id` with a single bulk update.
+pub(super) async fn move_stories(
+    story_ids: &[i64],
+    id: i64,
+    client: &api::Client,
+) -> Result<(), Box<dyn Error>> {
+    if story_ids.is_empty() {
+        return Ok(());
+    }
+    let ids = story_ids.to_vec();
+    client
+        .update_multiple_stories()
+        .body_map(|b| b.story_ids(ids).iteration_id(Some(id)))
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to move stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+    Ok(())
+}
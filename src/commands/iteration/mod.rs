@@ -1,13 +1,24 @@
+mod burndown;
+mod calendar;
 mod create;
 mod delete;
 mod get;
 pub(crate) mod helpers;
 mod list;
+mod plan;
+mod rollover;
 mod stories;
 mod update;
+mod velocity;
 pub mod wizard;
 
+pub use burndown::BurndownArgs;
+pub use calendar::CalendarArgs;
 pub use create::CreateArgs;
+pub use helpers::IterationRef;
+pub use plan::PlanArgs;
+pub use rollover::RolloverArgs;
+pub use velocity::VelocityArgs;
 
 use std::error::Error;
 use std::path::PathBuf;
@@ -21,6 +32,11 @@ use crate::output::OutputConfig;
 pub struct IterationArgs {
     #[command(subcommand)]
     pub action: IterationAction,
+
+    /// Team (group) whose iterations `current`, `next` and `previous` refer
+    /// to, by @mention_name or UUID
+    #[arg(long, global = true)]
+    pub group: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -35,30 +51,40 @@ pub enum IterationAction {
     Create(Box<create::CreateArgs>),
     /// Get an iteration by ID
     Get {
-        /// The ID of the iteration
+        /// The ID of the iteration (or current, next, previous)
         #[arg(long)]
-        id: i64,
+        id: IterationRef,
     },
     /// Update an iteration
     Update(Box<update::UpdateArgs>),
     /// Delete an iteration
     Delete {
-        /// The ID of the iteration to delete
+        /// The ID of the iteration to delete (or current, next, previous)
         #[arg(long)]
-        id: i64,
+        id: IterationRef,
         /// Confirm the irreversible deletion
         #[arg(long)]
         confirm: bool,
     },
     /// List stories in an iteration
     Stories {
-        /// The ID of the iteration
+        /// The ID of the iteration (or current, next, previous)
         #[arg(long)]
-        id: i64,
+        id: IterationRef,
         /// Include story descriptions in output
         #[arg(long, visible_alias = "descriptions")]
         desc: bool,
     },
+    /// Show the iteration plan by state and owner against member capacity
+    Plan(Box<plan::PlanArgs>),
+    /// Show remaining points per day against an ideal burndown line
+    Burndown(Box<burndown::BurndownArgs>),
+    /// Show completed points per finished iteration with a rolling average
+    Velocity(Box<velocity::VelocityArgs>),
+    /// Create the next iteration and move unfinished stories into it
+    Rollover(Box<rollover::RolloverArgs>),
+    /// Show iterations on a month or quarter timeline with overlaps and gaps
+    Calendar(calendar::CalendarArgs),
 }
 
 pub async fn run(
@@ -67,6 +93,7 @@ pub async fn run(
     cache_dir: PathBuf,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
+    let group = args.group.as_deref();
     match &args.action {
         IterationAction::List { state } => list::run(state.as_deref(), client, out).await,
         IterationAction::Create(create_args) => {
@@ -85,11 +112,35 @@ pub async fn run(
             }
             create::run(create_args, client, &cache_dir, out).await
         }
-        IterationAction::Get { id } => get::run(*id, client, out).await,
+        IterationAction::Get { id } => {
+            let id = helpers::resolve_iteration_id(*id, group, client, &cache_dir).await?;
+            get::run(id, client, out).await
+        }
         IterationAction::Update(update_args) => {
-            update::run(update_args, client, &cache_dir, out).await
+            update::run(update_args, group, client, &cache_dir, out).await
+        }
+        IterationAction::Delete { id, confirm } => {
+            let id = helpers::resolve_iteration_id(*id, group, client, &cache_dir).await?;
+            delete::run(id, *confirm, client, out).await
+        }
+        IterationAction::Stories { id, desc } => {
+            let id = helpers::resolve_iteration_id(*id, group, client, &cache_dir).await?;
+            stories::run(id, *desc, client, out).await
+        }
+        IterationAction::Plan(plan_args) => {
+            plan::run(plan_args, group, client, &cache_dir, out).await
+        }
+        IterationAction::Burndown(burndown_args) => {
+            burndown::run(burndown_args, group, client, &cache_dir, out).await
+        }
+        IterationAction::Velocity(velocity_args) => {
+            velocity::run(velocity_args, group, client, &cache_dir, out).await
+        }
+        IterationAction::Rollover(rollover_args) => {
+            rollover::run(rollover_args, group, client, &cache_dir, out).await
+        }
+        IterationAction::Calendar(calendar_args) => {
+            calendar::run(calendar_args, group, client, &cache_dir, out).await
         }
-        IterationAction::Delete { id, confirm } => delete::run(*id, *confirm, client, out).await,
-        IterationAction::Stories { id, desc } => stories::run(*id, *desc, client, out).await,
     }
 }
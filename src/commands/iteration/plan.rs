@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use clap::Args;
+use serde::Deserialize;
+
+use crate::api;
+use crate::commands::epic::helpers::resolve_member_name;
+use crate::commands::member;
+use crate::commands::story::helpers::{
+    build_workflow_state_id_map, build_workflow_state_type_map, group_by_state,
+};
+use crate::out_println;
+use crate::output::{OutputConfig, Table, format_points, percent};
+
+use super::helpers::{IterationRef, move_stories, resolve_iteration_id, unfinished_stories};
+
+#[derive(Args)]
+pub struct PlanArgs {
+    /// The ID of the iteration (or current, next, previous)
+    #[arg(long)]
+    pub id: IterationRef,
+
+    /// Move unfinished stories from this iteration (ID or current, previous)
+    /// into the planned one first
+    #[arg(long, value_name = "PREV")]
+    pub carry_over_from: Option<IterationRef>,
+
+    /// Capacity file (default: capacity.yml in this project's
+    /// ~/.shortcut/projects/<hash>/ directory)
+    #[arg(long)]
+    pub capacity: Option<PathBuf>,
+}
+
+/// Per-member capacity, read from `capacity.yml`:
+///
+/// ```yaml
+/// default_points_per_day: 1
+/// members:
+///   "@alice":
+///     points_per_day: 1.5
+///     days_off: [2024-03-04, 2024-03-05]
+/// ```
+#[derive(Deserialize, Default)]
+struct CapacityConfig {
+    default_points_per_day: Option<f64>,
+    #[serde(default)]
+    members: HashMap<String, MemberCapacity>,
+}
+
+#[derive(Deserialize, Default)]
+struct MemberCapacity {
+    points_per_day: Option<f64>,
+    #[serde(default)]
+    days_off: Vec<NaiveDate>,
+}
+
+#[derive(Default)]
+struct OwnerLoad {
+    stories: i64,
+    points: i64,
+    available_days: Option<i64>,
+    capacity: Option<f64>,
+}
+
+pub async fn run(
+    args: &PlanArgs,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let id = resolve_iteration_id(args.id, group, client, cache_dir).await?;
+    let iteration = client
+        .get_iteration()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get iteration: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    let state_types = build_workflow_state_type_map(client).await?;
+
+    let carried_over = match args.carry_over_from {
+        Some(prev) => {
+            let prev = resolve_iteration_id(prev, group, client, cache_dir).await?;
+            carry_over(prev, id, &state_types, client, out).await?
+        }
+        None => Vec::new(),
+    };
+    let config = load_capacity(args.capacity.as_deref(), cache_dir)?;
+
+    let stories: Vec<api::types::StorySlim> = client
+        .list_iteration_stories()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|s| !s.archived)
+        .collect();
+
+    let start = iteration.start_date.date_naive();
+    let end = iteration.end_date.date_naive();
+    let owners = owner_loads(&stories, &config, start, end, client, cache_dir).await?;
+    let unestimated: Vec<&api::types::StorySlim> =
+        stories.iter().filter(|s| s.estimate.is_none()).collect();
+    let unowned: Vec<&api::types::StorySlim> =
+        stories.iter().filter(|s| s.owner_ids.is_empty()).collect();
+
+    if out.is_quiet() {
+        for story in &stories {
+            out_println!(out, "{}", story.id);
+        }
+        return Ok(());
+    }
+
+    let state_names = build_workflow_state_id_map(client, cache_dir).await?;
+    let groups = group_by_state(&stories, &state_names, &state_types);
+    let total_points: i64 = groups.iter().map(|g| g.points).sum();
+
+    if out.is_machine_readable() {
+        let brief = |s: &&api::types::StorySlim| serde_json::json!({"id": s.id, "name": s.name});
+        let json = serde_json::json!({
+            "iteration": {
+                "id": iteration.id,
+                "name": iteration.name,
+                "start_date": start.to_string(),
+                "end_date": end.to_string(),
+            },
+            "total": { "stories": stories.len(), "points": total_points },
+            "states": groups.iter().map(|g| serde_json::json!({
+                "workflow_state_id": g.state_id,
+                "state": g.name,
+                "stories": g.stories.len(),
+                "points": g.points,
+            })).collect::<Vec<_>>(),
+            "owners": owners.iter().map(|(id, name, load)| serde_json::json!({
+                "owner": name,
+                "owner_id": id,
+                "stories": load.stories,
+                "points": load.points,
+                "available_days": load.available_days,
+                "capacity": load.capacity,
+            })).collect::<Vec<_>>(),
+            "unestimated": unestimated.iter().map(brief).collect::<Vec<_>>(),
+            "unowned": unowned.iter().map(brief).collect::<Vec<_>>(),
+            "carried_over": carried_over,
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "{} - {} ({} to {})",
+        iteration.id,
+        iteration.name,
+        start,
+        end
+    );
+    out_println!(
+        out,
+        "  Committed: {} stories, {total_points} points",
+        stories.len()
+    );
+    if !carried_over.is_empty() {
+        out_println!(out, "  Carried over: {} stories", carried_over.len());
+    }
+
+    if stories.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "No stories in this iteration");
+        return Ok(());
+    }
+
+    out_println!(out, "");
+    out_println!(out, "By state:");
+    let mut table = Table::new(vec!["Points", "Stories", "State"]);
+    for group in &groups {
+        table.add_row(vec![
+            group.points.to_string(),
+            group.stories.len().to_string(),
+            group.name.clone(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    out_println!(out, "");
+    out_println!(out, "By owner:");
+    let mut table = Table::new(vec!["Points", "Capacity", "Stories", "Owner", "Load"]);
+    for (_, name, load) in &owners {
+        let (capacity, status) = match load.capacity {
+            Some(cap) => {
+                let status = if load.points as f64 > cap {
+                    format!("over by {}", format_points(load.points as f64 - cap))
+                } else {
+                    format!("{}% of capacity", percent(load.points as f64, cap))
+                };
+                (format_points(cap), status)
+            }
+            None => ("-".to_string(), String::new()),
+        };
+        table.add_row(vec![
+            load.points.to_string(),
+            capacity,
+            load.stories.to_string(),
+            name.clone(),
+            status,
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    print_flagged("Unestimated", &unestimated, out)?;
+    print_flagged("Unowned", &unowned, out)?;
+    Ok(())
+}
+
+/// Move every story of `prev` that is not in a done state into `id` with a
+/// single bulk update, returning the moved story IDs.
+async fn carry_over(
+    prev: i64,
+    id: i64,
+    state_types: &HashMap<i64, String>,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<Vec<i64>, Box<dyn Error>> {
+    let story_ids: Vec<i64> = unfinished_stories(prev, state_types, client)
+        .await?
+        .iter()
+        .map(|s| s.id)
+        .collect();
+
+    if out.is_dry_run() {
+        let body = serde_json::json!({ "story_ids": story_ids, "iteration_id": id });
+        out.dry_run_request("PUT", "/api/v3/stories/bulk", Some(&body))?;
+        return Ok(story_ids);
+    }
+    move_stories(&story_ids, id, client).await?;
+    Ok(story_ids)
+}
+
+fn default_capacity_path(cache_dir: &Path) -> Option<PathBuf> {
+    cache_dir.parent().map(|dir| dir.join("capacity.yml"))
+}
+
+/// Load the capacity file. An explicit `--capacity` path must exist; the
+/// default one is optional.
+fn load_capacity(path: Option<&Path>, cache_dir: &Path) -> Result<CapacityConfig, Box<dyn Error>> {
+    let (path, required) = match path {
+        Some(p) => (p.to_path_buf(), true),
+        None => match default_capacity_path(cache_dir) {
+            Some(p) => (p, false),
+            None => return Ok(CapacityConfig::default()),
+        },
+    };
+    let data = match std::fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(_) if !required => return Ok(CapacityConfig::default()),
+        Err(e) => return Err(format!("Failed to read {}: {e}", path.display()).into()),
+    };
+    serde_yaml::from_str(&data)
+        .map_err(|e| format!("Invalid capacity file {}: {e}", path.display()).into())
+}
+
+/// Tally points per owner and work out each owner's capacity from the working
+/// days in the iteration minus their days off. Owners are tallied by member
+/// ID and sorted by name, with configured members who own nothing listed as
+/// well.
+async fn owner_loads(
+    stories: &[api::types::StorySlim],
+    config: &CapacityConfig,
+    start: NaiveDate,
+    end: NaiveDate,
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<(uuid::Uuid, String, OwnerLoad)>, Box<dyn Error>> {
+    let mut configured: HashMap<uuid::Uuid, &MemberCapacity> = HashMap::new();
+    for (key, capacity) in &config.members {
+        let id = member::resolve_member_id(key, client, cache_dir).await?;
+        configured.insert(id, capacity);
+    }
+
+    let mut loads: HashMap<uuid::Uuid, OwnerLoad> = HashMap::new();
+    for id in configured.keys() {
+        loads.entry(*id).or_default();
+    }
+    for story in stories {
+        for owner in &story.owner_ids {
+            let load = loads.entry(*owner).or_default();
+            load.stories += 1;
+            load.points += story.estimate.unwrap_or(0);
+        }
+    }
+
+    let default = MemberCapacity::default();
+    let mut owners = Vec::new();
+    for (id, mut load) in loads {
+        let member = configured.get(&id).copied();
+        let per_day = member
+            .and_then(|m| m.points_per_day)
+            .or(config.default_points_per_day);
+        if let Some(per_day) = per_day {
+            let days = available_days(start, end, &member.unwrap_or(&default).days_off);
+            load.available_days = Some(days);
+            load.capacity = Some(per_day * days as f64);
+        }
+        owners.push((id, resolve_member_name(&id, cache_dir), load));
+    }
+    owners.sort_by(|(a_id, a_name, _), (b_id, b_name, _)| (a_name, a_id).cmp(&(b_name, b_id)));
+    Ok(owners)
+}
+
+/// Weekdays from `start` to `end` inclusive that are not in `days_off`.
+fn available_days(start: NaiveDate, end: NaiveDate, days_off: &[NaiveDate]) -> i64 {
+    start
+        .iter_days()
+        .take_while(|d| *d <= end)
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter(|d| !days_off.contains(d))
+        .count() as i64
+}
+
+fn print_flagged(
+    label: &str,
+    stories: &[&api::types::StorySlim],
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if stories.is_empty() {
+        return Ok(());
+    }
+    out_println!(out, "");
+    out_println!(out, "{label} stories ({}):", stories.len());
+    for story in stories {
+        out_println!(out, "  {} - {}", story.id, story.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use clap::Args;
+
+use crate::api;
+use crate::commands::story::helpers::build_workflow_state_type_map;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::helpers::{IterationRef, move_stories, resolve_iteration_id, unfinished_stories};
+
+#[derive(Args)]
+pub struct RolloverArgs {
+    /// The ID of the iteration that is ending (or current, previous)
+    #[arg(long)]
+    pub id: IterationRef,
+
+    /// Name for the next iteration (default: the current name with its
+    /// number incremented, e.g. "Sprint 24" -> "Sprint 25")
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+pub async fn run(
+    args: &RolloverArgs,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let id = resolve_iteration_id(args.id, group, client, cache_dir).await?;
+    let current = client
+        .get_iteration()
+        .iteration_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get iteration: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner();
+
+    let name_str = match &args.name {
+        Some(name) => name.clone(),
+        None => next_name(&current.name).ok_or_else(|| {
+            format!(
+                "Cannot derive the next name from \"{}\" (no number to increment); pass --name",
+                current.name
+            )
+        })?,
+    };
+    let (start, end) = next_dates(
+        current.start_date.date_naive(),
+        current.end_date.date_naive(),
+    );
+    let label_names: Vec<&str> = current.labels.iter().map(|l| l.name.as_str()).collect();
+
+    let state_types = build_workflow_state_type_map(client).await?;
+    let stories = unfinished_stories(id, &state_types, client).await?;
+    let story_ids: Vec<i64> = stories.iter().map(|s| s.id).collect();
+
+    if out.is_dry_run() {
+        let mut body = serde_json::json!({
+            "name": name_str,
+            "start_date": start.to_string(),
+            "end_date": end.to_string(),
+        });
+        if !current.labels.is_empty() {
+            body["labels"] = serde_json::json!(
+                current
+                    .labels
+                    .iter()
+                    .map(|l| serde_json::json!({ "name": l.name, "color": l.color }))
+                    .collect::<Vec<_>>()
+            );
+        }
+        if !current.group_ids.is_empty() {
+            body["group_ids"] = serde_json::json!(current.group_ids);
+        }
+        out.dry_run_request("POST", "/api/v3/iterations", Some(&body))?;
+        if !story_ids.is_empty() {
+            let body = serde_json::json!({
+                "story_ids": story_ids,
+                "iteration_id": "<new iteration id>",
+            });
+            out.dry_run_request("PUT", "/api/v3/stories/bulk", Some(&body))?;
+        }
+        return Ok(());
+    }
+
+    let name = name_str
+        .parse::<api::types::CreateIterationName>()
+        .map_err(|e| format!("Invalid name: {e}"))?;
+    let start_date = start
+        .to_string()
+        .parse::<api::types::CreateIterationStartDate>()
+        .map_err(|e| format!("Invalid start_date: {e}"))?;
+    let end_date = end
+        .to_string()
+        .parse::<api::types::CreateIterationEndDate>()
+        .map_err(|e| format!("Invalid end_date: {e}"))?;
+    let labels: Vec<api::types::CreateLabelParams> = current
+        .labels
+        .iter()
+        .map(|l| -> Result<_, String> {
+            Ok(api::types::CreateLabelParams {
+                name: l
+                    .name
+                    .parse()
+                    .map_err(|e| format!("Invalid label name: {e}"))?,
+                color: l.color.clone(),
+                description: None,
+                external_id: None,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    let group_ids = current.group_ids.clone();
+
+    let next = client
+        .create_iteration()
+        .body_map(|mut b| {
+            b = b.name(name).start_date(start_date).end_date(end_date);
+            if !labels.is_empty() {
+                b = b.labels(labels);
+            }
+            if !group_ids.is_empty() {
+                b = b.group_ids(group_ids);
+            }
+            b
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to create iteration: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    move_stories(&story_ids, next.id, client).await?;
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "previous": { "id": current.id, "name": current.name },
+            "iteration": {
+                "id": next.id,
+                "name": next.name,
+                "start_date": start.to_string(),
+                "end_date": end.to_string(),
+                "group_ids": next.group_ids,
+                "labels": label_names,
+            },
+            "moved": stories
+                .iter()
+                .map(|s| serde_json::json!({ "id": s.id, "name": s.name, "estimate": s.estimate }))
+                .collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{}", next.id);
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "Created iteration {} - {} ({} \u{2192} {})",
+        next.id,
+        next.name,
+        start,
+        end
+    );
+    if !next.group_ids.is_empty() {
+        out_println!(out, "  Groups: {}", next.group_ids.len());
+    }
+    if !label_names.is_empty() {
+        out_println!(out, "  Labels: {}", label_names.join(", "));
+    }
+    let points: i64 = stories.iter().filter_map(|s| s.estimate).sum();
+    out_println!(
+        out,
+        "Moved {} unfinished stories ({points} points) from {} - {}",
+        stories.len(),
+        current.id,
+        current.name
+    );
+    for story in &stories {
+        out_println!(out, "  {} - {}", story.id, story.name);
+    }
+    Ok(())
+}
+
+/// Increment the last number in `name`, keeping any zero padding
+/// ("Sprint 09" -> "Sprint 10"). Returns `None` if the name has no number.
+fn next_name(name: &str) -> Option<String> {
+    let end = name.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = name[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let digits = &name[start..end];
+    let next = digits.parse::<u64>().ok()? + 1;
+    Some(format!(
+        "{}{next:0width$}{}",
+        &name[..start],
+        &name[end..],
+        width = digits.len()
+    ))
+}
+
+/// The next iteration has the same length and starts on the same weekday as
+/// the current one, on the first such day after the current one ends.
+fn next_dates(start: NaiveDate, end: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let length = end - start;
+    let mut next_start = end + Duration::days(1);
+    while next_start.weekday() != start.weekday() {
+        next_start += Duration::days(1);
+    }
+    (next_start, next_start + length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_name_increments_last_number() {
+        assert_eq!(next_name("Sprint 24").as_deref(), Some("Sprint 25"));
+        assert_eq!(next_name("Sprint 09").as_deref(), Some("Sprint 10"));
+        assert_eq!(
+            next_name("2024 Q1 Sprint 3 (web)").as_deref(),
+            Some("2024 Q1 Sprint 4 (web)")
+        );
+        assert_eq!(next_name("Backlog grooming"), None);
+    }
+
+    #[test]
+    fn next_dates_keep_length_and_weekday() {
+        let d = |s: &str| s.parse::<NaiveDate>().unwrap();
+        // Monday to Friday two weeks later: next one starts the Monday after.
+        assert_eq!(
+            next_dates(d("2024-03-04"), d("2024-03-15")),
+            (d("2024-03-18"), d("2024-03-29"))
+        );
+        // Back-to-back weeks.
+        assert_eq!(
+            next_dates(d("2024-03-04"), d("2024-03-10")),
+            (d("2024-03-11"), d("2024-03-17"))
+        );
+    }
+}
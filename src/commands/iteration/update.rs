@@ -1,4 +1,4 @@
-use super::helpers::resolve_followers;
+use super::helpers::{IterationRef, resolve_followers, resolve_iteration_id};
 use crate::api;
 use crate::out_println;
 use crate::output::OutputConfig;
@@ -9,8 +9,9 @@ use std::path::Path;
 #[derive(Args)]
 #[command(arg_required_else_help = true)]
 pub struct UpdateArgs {
+    /// The ID of the iteration (or current, next, previous)
     #[arg(long)]
-    pub id: i64,
+    pub id: IterationRef,
     #[arg(long)]
     pub name: Option<String>,
     #[arg(long)]
@@ -29,10 +30,12 @@ pub struct UpdateArgs {
 
 pub async fn run(
     args: &UpdateArgs,
+    group: Option<&str>,
     client: &api::Client,
     cache_dir: &Path,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
+    let id = resolve_iteration_id(args.id, group, client, cache_dir).await?;
     let name = args
         .name
         .as_ref()
@@ -103,16 +106,12 @@ pub async fn run(
             body.insert("group_ids".into(), serde_json::json!(args.group_ids));
         }
         let body = serde_json::Value::Object(body);
-        return out.dry_run_request(
-            "PUT",
-            &format!("/api/v3/iterations/{}", args.id),
-            Some(&body),
-        );
+        return out.dry_run_request("PUT", &format!("/api/v3/iterations/{id}"), Some(&body));
     }
 
     let iteration = client
         .update_iteration()
-        .iteration_public_id(args.id)
+        .iteration_public_id(id)
         .body_map(|mut b| {
             if let Some(name) = name {
                 b = b.name(Some(name));
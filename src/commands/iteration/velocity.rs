@@ -0,0 +1,156 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::out_println;
+use crate::output::{OutputConfig, Table, csv_row, format_points, round_tenth};
+
+use super::helpers::list_group_iterations;
+
+#[derive(Args)]
+pub struct VelocityArgs {
+    /// Number of most recent finished iterations to include
+    #[arg(long, default_value_t = 6)]
+    pub last: usize,
+
+    /// Print the series as CSV
+    #[arg(long)]
+    pub csv: bool,
+}
+
+/// Number of iterations averaged for the rolling average column.
+const ROLLING_WINDOW: usize = 3;
+
+struct VelocityRow<'a> {
+    iteration: &'a api::types::IterationSlim,
+    completed: i64,
+    planned: i64,
+    rolling_average: f64,
+}
+
+pub async fn run(
+    args: &VelocityArgs,
+    group: Option<&str>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let iterations = list_group_iterations(group, client, cache_dir).await?;
+
+    let mut finished: Vec<&api::types::IterationSlim> =
+        iterations.iter().filter(|i| i.status == "done").collect();
+    finished.sort_by_key(|i| i.end_date);
+    let finished = &finished[finished.len().saturating_sub(args.last)..];
+
+    let rows: Vec<VelocityRow> = finished
+        .iter()
+        .enumerate()
+        .map(|(idx, iteration)| {
+            let window = &finished[(idx + 1).saturating_sub(ROLLING_WINDOW)..=idx];
+            let sum: i64 = window.iter().map(|i| i.stats.num_points_done).sum();
+            VelocityRow {
+                iteration,
+                completed: iteration.stats.num_points_done,
+                planned: iteration.stats.num_points,
+                rolling_average: round_tenth(sum as f64 / window.len() as f64),
+            }
+        })
+        .collect();
+    let average = if rows.is_empty() {
+        0.0
+    } else {
+        round_tenth(rows.iter().map(|r| r.completed).sum::<i64>() as f64 / rows.len() as f64)
+    };
+
+    if args.csv {
+        return print_csv(&rows, out);
+    }
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "average": average,
+            "iterations": rows.iter().map(|r| serde_json::json!({
+                "id": r.iteration.id,
+                "name": r.iteration.name,
+                "start_date": r.iteration.start_date.date_naive().to_string(),
+                "end_date": r.iteration.end_date.date_naive().to_string(),
+                "completed_points": r.completed,
+                "planned_points": r.planned,
+                "rolling_average": r.rolling_average,
+            })).collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", format_points(average));
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        out_println!(out, "No finished iterations found");
+        return Ok(());
+    }
+
+    let avg_header = format!("Avg ({ROLLING_WINDOW})");
+    let mut table = Table::new(vec![
+        "ID",
+        "Completed",
+        "Planned",
+        &avg_header,
+        "Ended",
+        "Name",
+    ]);
+    for r in &rows {
+        table.add_row(vec![
+            r.iteration.id.to_string(),
+            r.completed.to_string(),
+            r.planned.to_string(),
+            format_points(r.rolling_average),
+            r.iteration.end_date.date_naive().to_string(),
+            r.iteration.name.clone(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+    out_println!(out, "");
+    out_println!(
+        out,
+        "Average velocity: {} points over {} iterations",
+        format_points(average),
+        rows.len()
+    );
+    Ok(())
+}
+
+fn print_csv(rows: &[VelocityRow], out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    out_println!(
+        out,
+        "{}",
+        csv_row(&[
+            "id".to_string(),
+            "name".to_string(),
+            "end_date".to_string(),
+            "completed_points".to_string(),
+            "planned_points".to_string(),
+            "rolling_average".to_string(),
+        ])
+    );
+    for r in rows {
+        out_println!(
+            out,
+            "{}",
+            csv_row(&[
+                r.iteration.id.to_string(),
+                r.iteration.name.clone(),
+                r.iteration.end_date.date_naive().to_string(),
+                r.completed.to_string(),
+                r.planned.to_string(),
+                r.rolling_average.to_string(),
+            ])
+        );
+    }
+    Ok(())
+}
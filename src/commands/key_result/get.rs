@@ -0,0 +1,57 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::helpers::{
+    fetch_key_result, format_value, key_result_json, percent_complete, progress_cell,
+};
+
+pub async fn run(
+    id: uuid::Uuid,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let kr = fetch_key_result(id, client).await?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&key_result_json(&kr))?
+        );
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", percent_complete(&kr));
+        return Ok(());
+    }
+
+    out_println!(out, "{} - {}", kr.id, kr.name);
+    out_println!(out, "  Objective:   {}", kr.objective_id);
+    out_println!(out, "  Type:        {}", kr.type_);
+    out_println!(
+        out,
+        "  Initial:     {}",
+        format_value(&kr.initial_observed_value, kr.type_)
+    );
+    out_println!(
+        out,
+        "  Current:     {}",
+        format_value(&kr.current_observed_value, kr.type_)
+    );
+    out_println!(
+        out,
+        "  Target:      {}",
+        format_value(&kr.current_target_value, kr.type_)
+    );
+    out_println!(
+        out,
+        "  Progress:    {} {}%",
+        progress_cell(&kr),
+        percent_complete(&kr)
+    );
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::error::Error;
+
+use crate::api;
+use crate::output::progress_bar;
+
+const BAR_WIDTH: usize = 20;
+
+pub async fn fetch_key_result(
+    id: uuid::Uuid,
+    client: &api::Client,
+) -> Result<api::types::KeyResult, Box<dyn Error>> {
+    Ok(client
+        .get_key_result()
+        .key_result_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get key result: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner())
+}
+
+/// Parse a `--current`/`--target` value: `true`/`false` for boolean key
+/// results, otherwise a number with at most two decimal places.
+pub fn parse_value(value: &str) -> Result<api::types::KeyResultValue, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" => Ok(api::types::KeyResultValue {
+            boolean_value: Some(true),
+            numeric_value: None,
+        }),
+        "false" | "no" => Ok(api::types::KeyResultValue {
+            boolean_value: Some(false),
+            numeric_value: None,
+        }),
+        _ if is_decimal(value) => Ok(api::types::KeyResultValue {
+            boolean_value: None,
+            numeric_value: Some(value.to_string()),
+        }),
+        _ => Err(format!(
+            "Invalid value: {value} (expected a number with at most two decimal places, or true/false)"
+        )),
+    }
+}
+
+/// Whether `value` is written as the API accepts numbers: `-?\d+(\.\d{1,2})?`.
+fn is_decimal(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let (whole, fraction) = match digits.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (digits, None),
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    all_digits(whole) && fraction.is_none_or(|f| f.len() <= 2 && all_digits(f))
+}
+
+fn numeric(value: &api::types::KeyResultValue) -> Option<f64> {
+    value.numeric_value.as_deref().and_then(|v| v.parse().ok())
+}
+
+/// Completion of a key result from 0 to 100. Numeric and percent key results
+/// measure how far the current value has moved from the initial value towards
+/// the target; boolean ones are either done or not.
+pub fn percent_complete(kr: &api::types::KeyResult) -> i64 {
+    if kr.type_ == api::types::KeyResultType::Boolean {
+        let target = kr.current_target_value.boolean_value.unwrap_or(true);
+        return if kr.current_observed_value.boolean_value == Some(target) {
+            100
+        } else {
+            0
+        };
+    }
+
+    let initial = numeric(&kr.initial_observed_value).unwrap_or(0.0);
+    let (Some(current), Some(target)) = (
+        numeric(&kr.current_observed_value),
+        numeric(&kr.current_target_value),
+    ) else {
+        return kr.progress.clamp(0, 100);
+    };
+    if target == initial {
+        return if current >= target { 100 } else { 0 };
+    }
+    (((current - initial) / (target - initial)) * 100.0)
+        .round()
+        .clamp(0.0, 100.0) as i64
+}
+
+pub fn format_value(
+    value: &api::types::KeyResultValue,
+    type_: api::types::KeyResultType,
+) -> String {
+    if let Some(b) = value.boolean_value {
+        return if b { "yes" } else { "no" }.to_string();
+    }
+    match (&value.numeric_value, type_) {
+        (Some(n), api::types::KeyResultType::Percent) => format!("{n}%"),
+        (Some(n), _) => n.clone(),
+        (None, _) => "-".to_string(),
+    }
+}
+
+pub fn progress_cell(kr: &api::types::KeyResult) -> String {
+    progress_bar(percent_complete(kr), BAR_WIDTH)
+}
+
+fn value_json(value: &api::types::KeyResultValue) -> serde_json::Value {
+    match (value.boolean_value, &value.numeric_value) {
+        (Some(b), _) => serde_json::json!(b),
+        (None, Some(n)) => n
+            .parse::<f64>()
+            .map(|f| serde_json::json!(f))
+            .unwrap_or_else(|_| serde_json::json!(n)),
+        (None, None) => serde_json::Value::Null,
+    }
+}
+
+pub fn key_result_json(kr: &api::types::KeyResult) -> serde_json::Value {
+    serde_json::json!({
+        "id": kr.id,
+        "objective_id": kr.objective_id,
+        "name": kr.name,
+        "type": kr.type_.to_string(),
+        "initial": value_json(&kr.initial_observed_value),
+        "current": value_json(&kr.current_observed_value),
+        "target": value_json(&kr.current_target_value),
+        "percent": percent_complete(kr),
+    })
+}
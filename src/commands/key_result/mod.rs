@@ -0,0 +1,41 @@
+mod get;
+pub(crate) mod helpers;
+mod update;
+
+pub use update::UpdateArgs;
+
+use std::error::Error;
+
+use clap::{Args, Subcommand};
+
+use crate::api;
+use crate::output::OutputConfig;
+
+#[derive(Args)]
+pub struct KeyResultArgs {
+    #[command(subcommand)]
+    pub action: KeyResultAction,
+}
+
+#[derive(Subcommand)]
+pub enum KeyResultAction {
+    /// Get a key result by ID
+    Get {
+        /// The ID of the key result
+        #[arg(long)]
+        id: uuid::Uuid,
+    },
+    /// Record progress on a key result
+    Update(Box<update::UpdateArgs>),
+}
+
+pub async fn run(
+    args: &KeyResultArgs,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    match &args.action {
+        KeyResultAction::Get { id } => get::run(*id, client, out).await,
+        KeyResultAction::Update(update_args) => update::run(update_args, client, out).await,
+    }
+}
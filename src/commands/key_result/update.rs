@@ -0,0 +1,115 @@
+use std::error::Error;
+
+use clap::Args;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::helpers::{key_result_json, parse_value, percent_complete, progress_cell};
+
+#[derive(Args)]
+#[command(arg_required_else_help = true)]
+pub struct UpdateArgs {
+    /// The ID of the key result
+    #[arg(long)]
+    pub id: uuid::Uuid,
+
+    /// The current observed value (a number, or true/false for boolean key results)
+    #[arg(long)]
+    pub current: Option<String>,
+
+    /// The target value
+    #[arg(long)]
+    pub target: Option<String>,
+
+    /// The key result name
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+pub async fn run(
+    args: &UpdateArgs,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let current = args.current.as_deref().map(parse_value).transpose()?;
+    let target = args.target.as_deref().map(parse_value).transpose()?;
+    let name = args
+        .name
+        .as_ref()
+        .map(|n| n.parse::<api::types::UpdateKeyResultName>())
+        .transpose()
+        .map_err(|e| format!("Invalid name: {e}"))?;
+
+    if current.is_none() && target.is_none() && name.is_none() {
+        return Err("Nothing to update. Pass --current, --target or --name.".into());
+    }
+
+    if out.is_dry_run() {
+        let mut body = serde_json::Map::new();
+        if let Some(name) = &args.name {
+            body.insert("name".into(), serde_json::json!(name));
+        }
+        if let Some(current) = &current {
+            body.insert("observed_value".into(), serde_json::to_value(current)?);
+        }
+        if let Some(target) = &target {
+            body.insert("target_value".into(), serde_json::to_value(target)?);
+        }
+        let body = serde_json::Value::Object(body);
+        return out.dry_run_request(
+            "PUT",
+            &format!("/api/v3/key-results/{}", args.id),
+            Some(&body),
+        );
+    }
+
+    let kr = client
+        .update_key_result()
+        .key_result_public_id(args.id)
+        .body_map(|mut b| {
+            if let Some(name) = name {
+                b = b.name(Some(name));
+            }
+            if let Some(current) = current {
+                b = b.observed_value(Some(current));
+            }
+            if let Some(target) = target {
+                b = b.target_value(Some(target));
+            }
+            b
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to update key result: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&key_result_json(&kr))?
+        );
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", percent_complete(&kr));
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "Updated key result {} - {}: {} {}%",
+        kr.id,
+        kr.name,
+        progress_cell(&kr),
+        percent_complete(&kr)
+    );
+    Ok(())
+}
@@ -270,7 +270,7 @@ pub fn member_choices_from(members: &[api::types::Member]) -> Vec<MemberChoice>
             }
         })
         .collect();
-    choices.sort_by(|a, b| a.display.to_lowercase().cmp(&b.display.to_lowercase()));
+    choices.sort_by_key(|a| a.display.to_lowercase());
     choices
 }
 
@@ -0,0 +1,113 @@
+use std::error::Error;
+
+use clap::Args;
+
+use crate::api;
+use crate::output::OutputConfig;
+
+use super::helpers::{categories_json, resolve_categories, resolve_state};
+use crate::out_println;
+
+#[derive(Args)]
+#[command(arg_required_else_help = true)]
+pub struct CreateArgs {
+    /// The milestone name
+    #[arg(long)]
+    pub name: String,
+
+    /// The milestone description
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// The state ("to do", "in progress", "done")
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Category names or IDs (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub categories: Vec<String>,
+}
+
+pub async fn run(
+    args: &CreateArgs,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let name = args
+        .name
+        .parse::<api::types::CreateMilestoneName>()
+        .map_err(|e| format!("Invalid name: {e}"))?;
+
+    let description = args
+        .description
+        .as_ref()
+        .map(|d| d.parse::<api::types::CreateMilestoneDescription>())
+        .transpose()
+        .map_err(|e| format!("Invalid description: {e}"))?;
+
+    let state_name = args.state.as_deref().map(resolve_state).transpose()?;
+    let state = state_name
+        .map(|s| s.parse::<api::types::CreateMilestoneState>())
+        .transpose()
+        .map_err(|e| format!("Invalid state: {e}"))?;
+
+    if out.is_dry_run() {
+        let mut body = serde_json::json!({ "name": args.name });
+        if let Some(desc) = &args.description {
+            body["description"] = serde_json::json!(desc);
+        }
+        if let Some(state) = state_name {
+            body["state"] = serde_json::json!(state);
+        }
+        if !args.categories.is_empty() {
+            body["categories"] = categories_json(&args.categories);
+        }
+        return out.dry_run_request("POST", "/api/v3/milestones", Some(&body));
+    }
+
+    let categories = resolve_categories(&args.categories, client).await?;
+
+    let milestone = client
+        .create_milestone()
+        .body_map(|mut b| {
+            b = b.name(name);
+            if let Some(desc) = description {
+                b = b.description(Some(desc));
+            }
+            if let Some(state) = state {
+                b = b.state(Some(state));
+            }
+            if !categories.is_empty() {
+                b = b.categories(categories);
+            }
+            b
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to create milestone: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        let json = serde_json::to_string_pretty(&*milestone)?;
+        out.write_str(format_args!("{json}"))?;
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", milestone.id);
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "Created milestone {} - {} ({})",
+        milestone.id,
+        milestone.name,
+        milestone.state
+    );
+    Ok(())
+}
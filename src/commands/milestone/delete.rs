@@ -0,0 +1,57 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+pub async fn run(
+    id: i64,
+    confirm: bool,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if out.is_dry_run() {
+        return out.dry_run_request::<serde_json::Value>(
+            "DELETE",
+            &format!("/api/v3/milestones/{id}"),
+            None,
+        );
+    }
+
+    if !confirm {
+        return Err("Deleting a milestone is irreversible. Pass --confirm to proceed.".into());
+    }
+
+    let milestone = client
+        .get_milestone()
+        .milestone_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get milestone: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    let name = milestone.name.clone();
+
+    client
+        .delete_milestone()
+        .milestone_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to delete milestone: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_quiet() {
+        return Ok(());
+    }
+
+    out_println!(out, "Deleted milestone {id} - {name}");
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+pub async fn run(id: i64, client: &api::Client, out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    let milestone = client
+        .get_milestone()
+        .milestone_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get milestone: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        let json = serde_json::to_string_pretty(&*milestone)?;
+        out.write_str(format_args!("{json}"))?;
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", milestone.id);
+        return Ok(());
+    }
+
+    out_println!(out, "{} - {}", milestone.id, milestone.name);
+    out_println!(out, "  State:       {}", milestone.state);
+    out_println!(out, "  Archived:    {}", milestone.archived);
+    if let Some(started) = milestone.started_at_override.or(milestone.started_at) {
+        out_println!(out, "  Started:     {}", started.format("%Y-%m-%d"));
+    }
+    if let Some(completed) = milestone.completed_at_override.or(milestone.completed_at) {
+        out_println!(out, "  Completed:   {}", completed.format("%Y-%m-%d"));
+    }
+
+    if !milestone.categories.is_empty() {
+        let names: Vec<String> = milestone
+            .categories
+            .iter()
+            .map(|c| format!("{} - {}", c.id, c.name))
+            .collect();
+        out_println!(out, "  Categories:  {}", names.join(", "));
+    }
+
+    let stats = &milestone.stats;
+    out_println!(out, "  Documents:   {}", stats.num_related_documents);
+    if let Some(cycle) = stats.average_cycle_time {
+        let cycle_days = cycle as f64 / 86400.0;
+        out_println!(out, "  Avg Cycle:   {cycle_days:.1} days");
+    }
+    if let Some(lead) = stats.average_lead_time {
+        let lead_days = lead as f64 / 86400.0;
+        out_println!(out, "  Avg Lead:    {lead_days:.1} days");
+    }
+
+    if !milestone.description.is_empty() {
+        out_println!(out, "  Description: {}", milestone.description);
+    }
+
+    // Show associated epics
+    let epics = client
+        .list_milestone_epics()
+        .milestone_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list milestone epics: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if !epics.is_empty() {
+        out_println!(out, "  Epics:");
+        for epic in epics.iter() {
+            out_println!(out, "    {} - {}", epic.id, epic.name);
+        }
+    }
+
+    Ok(())
+}
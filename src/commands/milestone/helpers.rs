@@ -0,0 +1,65 @@
+use std::error::Error;
+
+use crate::api;
+use crate::commands::objective::helpers::build_categories;
+
+/// Normalize a milestone state to the API spelling ("to do", "in progress",
+/// "done"), accepting variants like "todo", "in-progress" or "In Progress".
+pub fn resolve_state(state: &str) -> Result<&'static str, String> {
+    let key: String = state
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match key.as_str() {
+        "todo" | "unstarted" => Ok("to do"),
+        "inprogress" | "started" => Ok("in progress"),
+        "done" | "completed" => Ok("done"),
+        _ => Err(format!(
+            "Invalid state: {state} (expected \"to do\", \"in progress\" or \"done\")"
+        )),
+    }
+}
+
+/// Resolve `--categories` values to category params. Numeric values are
+/// looked up as category IDs; anything else is used as a category name, which
+/// the API creates if it does not exist yet.
+pub async fn resolve_categories(
+    values: &[String],
+    client: &api::Client,
+) -> Result<Vec<api::types::CreateCategoryParams>, Box<dyn Error>> {
+    let mut names = Vec::with_capacity(values.len());
+    for value in values {
+        match value.parse::<i64>() {
+            Ok(id) => {
+                let category = client
+                    .get_category()
+                    .category_public_id(id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        format!(
+                            "Failed to get category {id}: {}",
+                            crate::api::format_api_error(&e)
+                        )
+                    })?;
+                names.push(category.name.clone());
+            }
+            Err(_) => names.push(value.clone()),
+        }
+    }
+    Ok(build_categories(&names)?)
+}
+
+/// The dry-run representation of `--categories`.
+pub fn categories_json(values: &[String]) -> serde_json::Value {
+    serde_json::json!(
+        values
+            .iter()
+            .map(|v| match v.parse::<i64>() {
+                Ok(id) => serde_json::json!({ "id": id }),
+                Err(_) => serde_json::json!({ "name": v }),
+            })
+            .collect::<Vec<_>>()
+    )
+}
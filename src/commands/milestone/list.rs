@@ -0,0 +1,56 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::{OutputConfig, Table};
+
+pub async fn run(
+    include_archived: bool,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let milestones: Vec<_> = client
+        .list_milestones()
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list milestones: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner()
+        .into_iter()
+        .filter(|m| include_archived || !m.archived)
+        .collect();
+
+    if out.is_machine_readable() {
+        let json = serde_json::to_string_pretty(&milestones)?;
+        out.write_str(format_args!("{json}"))?;
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        for milestone in &milestones {
+            out_println!(out, "{}", milestone.id);
+        }
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["ID", "State", "Categories", "Name"]);
+    for milestone in &milestones {
+        let categories: Vec<&str> = milestone
+            .categories
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        table.add_row(vec![
+            milestone.id.to_string(),
+            milestone.state.clone(),
+            categories.join(", "),
+            milestone.name.clone(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+mod create;
+mod delete;
+mod epics;
+mod get;
+pub(crate) mod helpers;
+mod list;
+mod update;
+
+pub use create::CreateArgs;
+pub use update::UpdateArgs;
+
+use std::error::Error;
+
+use clap::{Args, Subcommand};
+
+use crate::api;
+use crate::output::OutputConfig;
+
+#[derive(Args)]
+pub struct MilestoneArgs {
+    #[command(subcommand)]
+    pub action: MilestoneAction,
+}
+
+#[derive(Subcommand)]
+pub enum MilestoneAction {
+    /// List all milestones
+    List {
+        /// Include archived milestones
+        #[arg(long)]
+        archived: bool,
+    },
+    /// Create a new milestone
+    Create(Box<create::CreateArgs>),
+    /// Get a milestone by ID
+    Get {
+        /// The ID of the milestone
+        #[arg(long)]
+        id: i64,
+    },
+    /// Update a milestone
+    Update(Box<update::UpdateArgs>),
+    /// Delete a milestone
+    Delete {
+        /// The ID of the milestone to delete
+        #[arg(long)]
+        id: i64,
+        /// Confirm the irreversible deletion
+        #[arg(long)]
+        confirm: bool,
+    },
+    /// List epics associated with a milestone
+    Epics {
+        /// The ID of the milestone
+        #[arg(long)]
+        id: i64,
+        /// Include epic descriptions in output
+        #[arg(long, visible_alias = "descriptions")]
+        desc: bool,
+    },
+}
+
+pub async fn run(
+    args: &MilestoneArgs,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    match &args.action {
+        MilestoneAction::List { archived } => list::run(*archived, client, out).await,
+        MilestoneAction::Create(create_args) => create::run(create_args, client, out).await,
+        MilestoneAction::Get { id } => get::run(*id, client, out).await,
+        MilestoneAction::Update(update_args) => update::run(update_args, client, out).await,
+        MilestoneAction::Delete { id, confirm } => delete::run(*id, *confirm, client, out).await,
+        MilestoneAction::Epics { id, desc } => epics::run(*id, *desc, client, out).await,
+    }
+}
@@ -0,0 +1,139 @@
+use std::error::Error;
+
+use clap::Args;
+
+use crate::api;
+use crate::output::OutputConfig;
+
+use super::helpers::{categories_json, resolve_categories, resolve_state};
+use crate::out_println;
+
+#[derive(Args)]
+#[command(arg_required_else_help = true)]
+pub struct UpdateArgs {
+    /// The ID of the milestone to update
+    #[arg(long)]
+    pub id: i64,
+
+    /// The milestone name
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// The milestone description
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// The state ("to do", "in progress", "done")
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Whether the milestone is archived
+    #[arg(long)]
+    pub archived: Option<bool>,
+
+    /// Category names or IDs (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub categories: Vec<String>,
+}
+
+pub async fn run(
+    args: &UpdateArgs,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let name = args
+        .name
+        .as_ref()
+        .map(|n| n.parse::<api::types::UpdateMilestoneName>())
+        .transpose()
+        .map_err(|e| format!("Invalid name: {e}"))?;
+
+    let description = args
+        .description
+        .as_ref()
+        .map(|d| d.parse::<api::types::UpdateMilestoneDescription>())
+        .transpose()
+        .map_err(|e| format!("Invalid description: {e}"))?;
+
+    let state_name = args.state.as_deref().map(resolve_state).transpose()?;
+    let state = state_name
+        .map(|s| s.parse::<api::types::UpdateMilestoneState>())
+        .transpose()
+        .map_err(|e| format!("Invalid state: {e}"))?;
+
+    if out.is_dry_run() {
+        let mut body = serde_json::Map::new();
+        if let Some(name) = &args.name {
+            body.insert("name".into(), serde_json::json!(name));
+        }
+        if let Some(desc) = &args.description {
+            body.insert("description".into(), serde_json::json!(desc));
+        }
+        if let Some(state) = state_name {
+            body.insert("state".into(), serde_json::json!(state));
+        }
+        if let Some(archived) = args.archived {
+            body.insert("archived".into(), serde_json::json!(archived));
+        }
+        if !args.categories.is_empty() {
+            body.insert("categories".into(), categories_json(&args.categories));
+        }
+        let body = serde_json::Value::Object(body);
+        return out.dry_run_request(
+            "PUT",
+            &format!("/api/v3/milestones/{}", args.id),
+            Some(&body),
+        );
+    }
+
+    let categories = resolve_categories(&args.categories, client).await?;
+
+    let milestone = client
+        .update_milestone()
+        .milestone_public_id(args.id)
+        .body_map(|mut b| {
+            if let Some(name) = name {
+                b = b.name(Some(name));
+            }
+            if let Some(desc) = description {
+                b = b.description(Some(desc));
+            }
+            if let Some(state) = state {
+                b = b.state(Some(state));
+            }
+            if let Some(archived) = args.archived {
+                b = b.archived(Some(archived));
+            }
+            if !categories.is_empty() {
+                b = b.categories(categories);
+            }
+            b
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to update milestone: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        let json = serde_json::to_string_pretty(&*milestone)?;
+        out.write_str(format_args!("{json}"))?;
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        out_println!(out, "{}", milestone.id);
+        return Ok(());
+    }
+
+    out_println!(
+        out,
+        "Updated milestone {} - {}",
+        milestone.id,
+        milestone.name
+    );
+    Ok(())
+}
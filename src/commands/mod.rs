@@ -6,11 +6,14 @@ pub mod epic;
 pub mod group;
 pub mod init;
 pub mod iteration;
+pub mod key_result;
 pub mod label;
 pub mod login;
 pub mod member;
+pub mod milestone;
 pub mod objective;
 pub mod project;
+pub mod report;
 pub mod search;
 pub mod story;
 pub mod template;
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use crate::api;
+use crate::commands::key_result::helpers::percent_complete;
 use crate::out_println;
 use crate::output::OutputConfig;
 
@@ -58,6 +59,18 @@ pub async fn run(id: i64, client: &api::Client, out: &OutputConfig) -> Result<()
         out_println!(out, "  Description: {}", objective.description);
     }
 
+    // Key results are extra detail; don't fail the whole command over them
+    match super::key_results::fetch_key_results(&objective, client).await {
+        Ok(key_results) if !key_results.is_empty() => {
+            out_println!(out, "  Key Results:");
+            for kr in &key_results {
+                out_println!(out, "    {:>3}% {}", percent_complete(kr), kr.name);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => out_println!(out, "  Key Results: unavailable ({e})"),
+    }
+
     // Show associated epics
     let epics = client
         .list_objective_epics()
@@ -0,0 +1,68 @@
+use std::error::Error;
+
+use crate::api;
+use crate::commands::key_result::helpers::{
+    fetch_key_result, format_value, key_result_json, percent_complete, progress_cell,
+};
+use crate::out_println;
+use crate::output::{OutputConfig, Table};
+
+/// Fetch every key result of an objective, in the objective's order.
+pub(super) async fn fetch_key_results(
+    objective: &api::types::Objective,
+    client: &api::Client,
+) -> Result<Vec<api::types::KeyResult>, Box<dyn Error>> {
+    let mut key_results = Vec::with_capacity(objective.key_result_ids.len());
+    for id in &objective.key_result_ids {
+        key_results.push(fetch_key_result(*id, client).await?);
+    }
+    Ok(key_results)
+}
+
+pub async fn run(id: i64, client: &api::Client, out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    let objective = client
+        .get_objective()
+        .objective_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get objective: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+    let key_results = fetch_key_results(&objective, client).await?;
+
+    if out.is_machine_readable() {
+        let json: Vec<serde_json::Value> = key_results.iter().map(key_result_json).collect();
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        for kr in &key_results {
+            out_println!(out, "{}", kr.id);
+        }
+        return Ok(());
+    }
+
+    if key_results.is_empty() {
+        out_println!(out, "No key results for this objective");
+        return Ok(());
+    }
+
+    out_println!(out, "{} - {}", objective.id, objective.name);
+    let mut table = Table::new(vec!["%", "Progress", "Current", "Target", "Name", "ID"]);
+    for kr in &key_results {
+        table.add_row(vec![
+            format!("{}%", percent_complete(kr)),
+            progress_cell(kr),
+            format_value(&kr.current_observed_value, kr.type_),
+            format_value(&kr.current_target_value, kr.type_),
+            kr.name.clone(),
+            kr.id.to_string(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+    Ok(())
+}
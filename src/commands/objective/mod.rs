@@ -3,6 +3,7 @@ mod delete;
 mod epics;
 mod get;
 pub(crate) mod helpers;
+mod key_results;
 mod list;
 mod update;
 
@@ -49,6 +50,12 @@ pub enum ObjectiveAction {
         #[arg(long)]
         confirm: bool,
     },
+    /// List an objective's key results with progress
+    KeyResults {
+        /// The ID of the objective
+        #[arg(long)]
+        id: i64,
+    },
     /// List epics associated with an objective
     Epics {
         /// The ID of the objective
@@ -71,6 +78,7 @@ pub async fn run(
         ObjectiveAction::Get { id } => get::run(*id, client, out).await,
         ObjectiveAction::Update(update_args) => update::run(update_args, client, out).await,
         ObjectiveAction::Delete { id, confirm } => delete::run(*id, *confirm, client, out).await,
+        ObjectiveAction::KeyResults { id } => key_results::run(*id, client, out).await,
         ObjectiveAction::Epics { id, desc } => epics::run(*id, *desc, client, out).await,
     }
 }
@@ -0,0 +1,534 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::Args;
+
+use crate::api;
+use crate::output::{OutputConfig, Table, csv_row};
+
+use super::super::group;
+use super::super::iteration::helpers::{IterationRef, resolve_iteration_id};
+use super::super::story::helpers::build_workflow_state_id_map;
+use super::super::story::history;
+use crate::out_println;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Args)]
+pub struct CycleTimeArgs {
+    /// Only include stories in this epic
+    #[arg(long)]
+    pub epic_id: Option<i64>,
+
+    /// Only include stories in this iteration (ID or current, next, previous;
+    /// relative to --group-id when given)
+    #[arg(long)]
+    pub iteration_id: Option<IterationRef>,
+
+    /// Only include stories with this label name
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Only include stories owned by this team (@mention_name or UUID)
+    #[arg(long)]
+    pub group_id: Option<String>,
+
+    /// Only include stories of this type (feature, bug, chore)
+    #[arg(long, name = "type")]
+    pub story_type: Option<String>,
+
+    /// Only include stories completed on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only include stories completed on or before this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Output one CSV row per story instead of a summary
+    #[arg(long)]
+    pub csv: bool,
+
+    /// Ignore locally cached story histories and fetch them again
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+struct StoryTimes {
+    id: i64,
+    name: String,
+    story_type: String,
+    created_at: DateTime<Utc>,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: DateTime<Utc>,
+    lead_days: f64,
+    cycle_days: Option<f64>,
+    /// Days spent in each workflow state, keyed by state ID.
+    state_days: HashMap<i64, f64>,
+}
+
+pub async fn run(
+    args: &CycleTimeArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let resolved_group_id = match &args.group_id {
+        Some(val) => Some(group::helpers::resolve_group_id(val, client, cache_dir).await?),
+        None => None,
+    };
+
+    let resolved_story_type = args
+        .story_type
+        .as_ref()
+        .map(|t| t.parse::<api::types::SearchStoriesStoryType>())
+        .transpose()
+        .map_err(|e| format!("Invalid story type: {e}"))?;
+
+    let resolved_label_name = args
+        .label
+        .as_ref()
+        .map(|l| l.parse::<api::types::SearchStoriesLabelName>())
+        .transpose()
+        .map_err(|e| format!("Invalid label name: {e}"))?;
+
+    let since = args
+        .since
+        .as_deref()
+        .map(|d| parse_date(d, "--since"))
+        .transpose()?
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    let until = args
+        .until
+        .as_deref()
+        .map(|d| parse_date(d, "--until"))
+        .transpose()?
+        .map(|d| d.and_hms_opt(23, 59, 59).unwrap().and_utc());
+
+    let epic_id = args.epic_id;
+    let iteration_id = match args.iteration_id {
+        Some(val) => {
+            Some(resolve_iteration_id(val, args.group_id.as_deref(), client, cache_dir).await?)
+        }
+        None => None,
+    };
+
+    let stories = client
+        .query_stories()
+        .body_map(|mut b| {
+            b = b.workflow_state_types(vec![api::types::SearchStoriesWorkflowStateTypesItem::Done]);
+            if let Some(epic_id) = epic_id {
+                b = b.epic_id(Some(epic_id));
+            }
+            if let Some(iteration_id) = iteration_id {
+                b = b.iteration_id(Some(iteration_id));
+            }
+            if let Some(label) = resolved_label_name {
+                b = b.label_name(Some(label));
+            }
+            if let Some(group_id) = resolved_group_id {
+                b = b.group_id(Some(group_id));
+            }
+            if let Some(st) = resolved_story_type {
+                b = b.story_type(Some(st));
+            }
+            if let Some(since) = since {
+                b = b.completed_at_start(Some(since));
+            }
+            if let Some(until) = until {
+                b = b.completed_at_end(Some(until));
+            }
+            b
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to search stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    let mut state_names: HashMap<i64, String> = HashMap::new();
+    let mut times: Vec<StoryTimes> = Vec::new();
+    for story in stories.iter() {
+        let Some(completed_at) = story.completed_at else {
+            continue;
+        };
+        let entries = history::fetch_history_cached(
+            client,
+            story.id,
+            story.updated_at,
+            cache_dir,
+            args.refresh,
+        )
+        .await?;
+        state_names.extend(history::reference_state_names(&entries));
+
+        let transitions = history::state_transitions(&entries);
+        times.push(StoryTimes {
+            id: story.id,
+            name: story.name.clone(),
+            story_type: story.story_type.clone(),
+            created_at: story.created_at,
+            started_at: story.started_at,
+            completed_at,
+            lead_days: days_between(story.created_at, completed_at),
+            cycle_days: story.started_at.map(|s| days_between(s, completed_at)),
+            state_days: time_in_state(&transitions, story.created_at, completed_at),
+        });
+    }
+    times.sort_by_key(|t| t.id);
+
+    if out.is_quiet() {
+        for t in &times {
+            out_println!(out, "{}", t.id);
+        }
+        return Ok(());
+    }
+
+    if times.is_empty() {
+        if out.is_machine_readable() {
+            out_println!(
+                out,
+                "{}",
+                serde_json::to_string_pretty(&build_json(&[], &state_names))?
+            );
+        } else if !args.csv {
+            out_println!(out, "No completed stories found");
+        }
+        return Ok(());
+    }
+
+    // Fill in names for states that never appeared in history references
+    let seen_states: Vec<i64> = times
+        .iter()
+        .flat_map(|t| t.state_days.keys().copied())
+        .collect();
+    if seen_states.iter().any(|id| !state_names.contains_key(id)) {
+        for (id, name) in build_workflow_state_id_map(client, cache_dir).await? {
+            state_names.entry(id).or_insert(name);
+        }
+    }
+
+    if out.is_machine_readable() {
+        let json = build_json(&times, &state_names);
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if args.csv {
+        return print_csv(&times, &state_names, out);
+    }
+
+    print_summary(&times, &state_names, out)
+}
+
+fn parse_date(value: &str, flag: &str) -> Result<NaiveDate, Box<dyn Error>> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid {flag} date '{value}': expected YYYY-MM-DD").into())
+}
+
+fn days_between(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    (end - start).num_seconds().max(0) as f64 / SECONDS_PER_DAY
+}
+
+/// Accumulate the days a story spent in each workflow state before completion.
+fn time_in_state(
+    transitions: &[history::StateTransition],
+    created_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+) -> HashMap<i64, f64> {
+    let mut days: HashMap<i64, f64> = HashMap::new();
+    let mut add = |state: i64, start: DateTime<Utc>, end: DateTime<Utc>| {
+        let end = end.min(completed_at);
+        if end > start {
+            *days.entry(state).or_default() += days_between(start, end);
+        }
+    };
+
+    let mut current: Option<(i64, DateTime<Utc>)> = None;
+    for t in transitions {
+        match current {
+            Some((state, since)) => add(state, since, t.changed_at),
+            // No creation event seen: assume the story sat in `from` since it was created
+            None => {
+                if let Some(from) = t.from {
+                    add(from, created_at, t.changed_at);
+                }
+            }
+        }
+        current = t.to.map(|state| (state, t.changed_at));
+    }
+    if let Some((state, since)) = current {
+        add(state, since, completed_at);
+    }
+
+    days
+}
+
+/// Nearest-rank percentile of an ascending-sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+struct Stats {
+    count: usize,
+    mean: f64,
+    p50: f64,
+    p85: f64,
+    p95: f64,
+}
+
+fn stats(values: impl IntoIterator<Item = f64>) -> Stats {
+    let mut sorted: Vec<f64> = values.into_iter().collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let count = sorted.len();
+    let mean = if count == 0 {
+        0.0
+    } else {
+        sorted.iter().sum::<f64>() / count as f64
+    };
+    Stats {
+        count,
+        mean,
+        p50: percentile(&sorted, 50.0),
+        p85: percentile(&sorted, 85.0),
+        p95: percentile(&sorted, 95.0),
+    }
+}
+
+impl Stats {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "count": self.count,
+            "mean_days": round2(self.mean),
+            "p50_days": round2(self.p50),
+            "p85_days": round2(self.p85),
+            "p95_days": round2(self.p95),
+        })
+    }
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+fn fmt_days(v: f64) -> String {
+    format!("{v:.1}d")
+}
+
+fn state_label(id: i64, state_names: &HashMap<i64, String>) -> String {
+    state_names
+        .get(&id)
+        .cloned()
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Per-state day lists, keyed by display name so output is stable.
+fn collect_state_days(
+    times: &[StoryTimes],
+    state_names: &HashMap<i64, String>,
+) -> BTreeMap<String, Vec<f64>> {
+    let mut by_state: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for t in times {
+        for (&id, &d) in &t.state_days {
+            by_state
+                .entry(state_label(id, state_names))
+                .or_default()
+                .push(d);
+        }
+    }
+    by_state
+}
+
+fn collect_by_type(times: &[StoryTimes]) -> BTreeMap<&str, Vec<&StoryTimes>> {
+    let mut by_type: BTreeMap<&str, Vec<&StoryTimes>> = BTreeMap::new();
+    for t in times {
+        by_type.entry(t.story_type.as_str()).or_default().push(t);
+    }
+    by_type
+}
+
+fn build_json(times: &[StoryTimes], state_names: &HashMap<i64, String>) -> serde_json::Value {
+    let stories: Vec<serde_json::Value> = times
+        .iter()
+        .map(|t| {
+            let in_state: BTreeMap<String, f64> = t
+                .state_days
+                .iter()
+                .map(|(&id, &d)| (state_label(id, state_names), round2(d)))
+                .collect();
+            serde_json::json!({
+                "id": t.id,
+                "name": t.name,
+                "story_type": t.story_type,
+                "created_at": t.created_at,
+                "started_at": t.started_at,
+                "completed_at": t.completed_at,
+                "lead_time_days": round2(t.lead_days),
+                "cycle_time_days": t.cycle_days.map(round2),
+                "time_in_state_days": in_state,
+            })
+        })
+        .collect();
+
+    let by_type: serde_json::Map<String, serde_json::Value> = collect_by_type(times)
+        .into_iter()
+        .map(|(story_type, items)| {
+            (
+                story_type.to_string(),
+                serde_json::json!({
+                    "lead_time": stats(items.iter().map(|t| t.lead_days)).to_json(),
+                    "cycle_time": stats(items.iter().filter_map(|t| t.cycle_days)).to_json(),
+                }),
+            )
+        })
+        .collect();
+
+    let time_in_state: serde_json::Map<String, serde_json::Value> =
+        collect_state_days(times, state_names)
+            .into_iter()
+            .map(|(name, days)| (name, stats(days).to_json()))
+            .collect();
+
+    serde_json::json!({
+        "stories": stories,
+        "summary": {
+            "lead_time": stats(times.iter().map(|t| t.lead_days)).to_json(),
+            "cycle_time": stats(times.iter().filter_map(|t| t.cycle_days)).to_json(),
+            "by_type": by_type,
+            "time_in_state": time_in_state,
+        },
+    })
+}
+
+fn print_csv(
+    times: &[StoryTimes],
+    state_names: &HashMap<i64, String>,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let states: Vec<String> = collect_state_days(times, state_names).into_keys().collect();
+
+    let mut header: Vec<String> = [
+        "id",
+        "name",
+        "story_type",
+        "created_at",
+        "started_at",
+        "completed_at",
+        "lead_time_days",
+        "cycle_time_days",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    header.extend(states.iter().map(|s| format!("{s} (days)")));
+    out_println!(out, "{}", csv_row(&header));
+
+    for t in times {
+        let in_state: HashMap<String, f64> = t
+            .state_days
+            .iter()
+            .map(|(&id, &d)| (state_label(id, state_names), d))
+            .collect();
+        let mut row = vec![
+            t.id.to_string(),
+            t.name.clone(),
+            t.story_type.clone(),
+            t.created_at.to_rfc3339(),
+            t.started_at.map(|s| s.to_rfc3339()).unwrap_or_default(),
+            t.completed_at.to_rfc3339(),
+            format!("{:.2}", t.lead_days),
+            t.cycle_days.map(|d| format!("{d:.2}")).unwrap_or_default(),
+        ];
+        row.extend(states.iter().map(|s| {
+            in_state
+                .get(s)
+                .map(|d| format!("{d:.2}"))
+                .unwrap_or_default()
+        }));
+        out_println!(out, "{}", csv_row(&row));
+    }
+
+    Ok(())
+}
+
+fn print_summary(
+    times: &[StoryTimes],
+    state_names: &HashMap<i64, String>,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    out_println!(out, "Completed stories: {}", times.len());
+    out_println!(out, "");
+
+    let mut table = Table::new(vec!["Metric", "Stories", "Mean", "P50", "P85", "P95"]);
+    for (label, s) in [
+        ("Lead time", stats(times.iter().map(|t| t.lead_days))),
+        (
+            "Cycle time",
+            stats(times.iter().filter_map(|t| t.cycle_days)),
+        ),
+    ] {
+        table.add_row(vec![
+            label.to_string(),
+            s.count.to_string(),
+            fmt_days(s.mean),
+            fmt_days(s.p50),
+            fmt_days(s.p85),
+            fmt_days(s.p95),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    out_println!(out, "");
+    out_println!(out, "By type:");
+    let mut table = Table::new(vec![
+        "Type",
+        "Stories",
+        "Lead P50",
+        "Lead P85",
+        "Cycle P50",
+        "Cycle P85",
+    ]);
+    for (story_type, items) in collect_by_type(times) {
+        let lead = stats(items.iter().map(|t| t.lead_days));
+        let cycle = stats(items.iter().filter_map(|t| t.cycle_days));
+        table.add_row(vec![
+            story_type.to_string(),
+            items.len().to_string(),
+            fmt_days(lead.p50),
+            fmt_days(lead.p85),
+            fmt_days(cycle.p50),
+            fmt_days(cycle.p85),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+
+    let by_state = collect_state_days(times, state_names);
+    if !by_state.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Time in state:");
+        let mut table = Table::new(vec!["State", "Stories", "Mean", "P50", "P85", "P95"]);
+        for (name, days) in by_state {
+            let s = stats(days);
+            table.add_row(vec![
+                name,
+                s.count.to_string(),
+                fmt_days(s.mean),
+                fmt_days(s.p50),
+                fmt_days(s.p85),
+                fmt_days(s.p95),
+            ]);
+        }
+        out.write_str(format_args!("{}", table.render()))?;
+    }
+
+    Ok(())
+}
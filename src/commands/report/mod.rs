@@ -0,0 +1,34 @@
+mod cycle_time;
+
+pub use cycle_time::CycleTimeArgs;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::api;
+use crate::output::OutputConfig;
+
+#[derive(Args)]
+pub struct ReportArgs {
+    #[command(subcommand)]
+    pub action: ReportAction,
+}
+
+#[derive(Subcommand)]
+pub enum ReportAction {
+    /// Lead time, cycle time and time-in-state for completed stories
+    CycleTime(Box<cycle_time::CycleTimeArgs>),
+}
+
+pub async fn run(
+    args: &ReportArgs,
+    client: &api::Client,
+    cache_dir: PathBuf,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    match &args.action {
+        ReportAction::CycleTime(args) => cycle_time::run(args, client, &cache_dir, out).await,
+    }
+}
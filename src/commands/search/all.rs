@@ -4,6 +4,7 @@ use crate::api;
 use crate::output::OutputConfig;
 
 use super::SearchQueryArgs;
+use super::helpers::effective_query;
 use crate::out_println;
 
 pub async fn run(
@@ -11,8 +12,7 @@ pub async fn run(
     client: &api::Client,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
-    let query = args
-        .query
+    let query = effective_query(args)
         .parse::<api::types::SearchQuery>()
         .map_err(|e| format!("Invalid query: {e}"))?;
 
@@ -4,7 +4,7 @@ use crate::api;
 use crate::output::OutputConfig;
 
 use super::SearchQueryArgs;
-use super::helpers::print_pagination;
+use super::helpers::{effective_query, print_pagination};
 use crate::out_println;
 
 pub async fn run(
@@ -12,8 +12,7 @@ pub async fn run(
     client: &api::Client,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
-    let query = args
-        .query
+    let query = effective_query(args)
         .parse::<api::types::SearchEpicsQuery>()
         .map_err(|e| format!("Invalid query: {e}"))?;
 
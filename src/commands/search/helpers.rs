@@ -1,5 +1,17 @@
 use crate::output::OutputConfig;
 
+use super::SearchQueryArgs;
+
+/// The query to send, excluding archived items unless `--archived` was passed
+/// or the query already filters on `is:archived` itself.
+pub fn effective_query(args: &SearchQueryArgs) -> String {
+    if args.archived || args.query.contains("is:archived") {
+        args.query.clone()
+    } else {
+        format!("{} !is:archived", args.query)
+    }
+}
+
 pub fn print_pagination(count: usize, total: i64, next: Option<&str>, out: &OutputConfig) {
     if count == 0 {
         return;
@@ -54,6 +54,10 @@ pub struct SearchQueryArgs {
     /// Include descriptions in output
     #[arg(long, visible_alias = "descriptions")]
     pub desc: bool,
+
+    /// Include archived stories and epics in results
+    #[arg(long)]
+    pub archived: bool,
 }
 
 pub async fn run(
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+/// Archive or unarchive a story through the `archived` update field.
+pub async fn run(
+    id: i64,
+    archived: bool,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if out.is_dry_run() {
+        let body = serde_json::json!({ "archived": archived });
+        return out.dry_run_request("PUT", &format!("/api/v3/stories/{id}"), Some(&body));
+    }
+
+    let story = client
+        .update_story()
+        .story_public_id(id)
+        .body_map(|b| b.archived(Some(archived)))
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to update story: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::json!({"id": story.id, "name": story.name, "archived": story.archived})
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{id}");
+        return Ok(());
+    }
+    let verb = if archived { "Archived" } else { "Unarchived" };
+    out_println!(out, "{verb} story {} - {}", story.id, story.name);
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::commands::iteration::IterationRef;
+use crate::output::OutputConfig;
+
+use super::create::{self, CreateArgs, CreateExtras};
+
+#[derive(Args)]
+pub struct CloneArgs {
+    /// The ID of the story to clone
+    #[arg(long)]
+    pub id: i64,
+
+    /// Name for the new story (defaults to the source story's name)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Put the clone in this epic instead of the source story's epic
+    #[arg(long)]
+    pub epic_id: Option<i64>,
+
+    /// Put the clone in this iteration (ID or current, next, previous)
+    /// instead of the source story's iteration
+    #[arg(long = "into-iteration")]
+    pub iteration_id: Option<IterationRef>,
+
+    /// Put the clone in this project instead of the source story's project
+    #[arg(long)]
+    pub project_id: Option<i64>,
+
+    /// The team (group) by @mention_name or UUID, instead of the source story's team
+    #[arg(long)]
+    pub group_id: Option<String>,
+
+    /// Owner(s) by @mention_name or UUID (comma-separated), instead of the source story's owners
+    #[arg(long, value_delimiter = ',')]
+    pub owner: Vec<String>,
+
+    /// Also copy the source story's comments
+    #[arg(long)]
+    pub with_comments: bool,
+
+    /// Also copy the source story's links to other stories
+    #[arg(long)]
+    pub with_links: bool,
+
+    /// Number of copies to create
+    #[arg(long, default_value = "1")]
+    pub count: u32,
+}
+
+pub async fn run(
+    args: &CloneArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if args.count == 0 {
+        return Err("--count must be at least 1".into());
+    }
+
+    let source = client
+        .get_story()
+        .story_public_id(args.id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get story: {}", crate::api::format_api_error(&e)))?
+        .into_inner();
+
+    let mut create_args = copy_create_args(&source);
+    if let Some(name) = &args.name {
+        create_args.name = Some(name.clone());
+    }
+    if args.epic_id.is_some() {
+        create_args.epic_id = args.epic_id;
+    }
+    if args.iteration_id.is_some() {
+        create_args.iteration_id = args.iteration_id;
+    }
+    if args.group_id.is_some() {
+        create_args.group_id = args.group_id.clone();
+    }
+    if !args.owner.is_empty() {
+        create_args.owner = args.owner.clone();
+    }
+
+    let mut extras = copy_extras(&source, args.with_comments, args.with_links)?;
+    if args.project_id.is_some() {
+        extras.project_id = args.project_id;
+    }
+
+    for _ in 0..args.count {
+        if let Some(story) =
+            create::create_story(&create_args, &extras, client, cache_dir, out).await?
+        {
+            create::print_created(&story, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the `create` flags that reproduce `source`. Workflow state and parent
+/// are left unset so a copy starts in the default state as a standalone story.
+pub(super) fn copy_create_args(source: &api::types::Story) -> CreateArgs {
+    CreateArgs {
+        interactive: false,
+        name: Some(source.name.clone()),
+        description: Some(source.description.clone()).filter(|d| !d.is_empty()),
+        story_type: Some(source.story_type.clone()),
+        owner: source.owner_ids.iter().map(|id| id.to_string()).collect(),
+        state: None,
+        epic_id: source.epic_id,
+        estimate: source.estimate,
+        labels: source.labels.iter().map(|l| l.name.clone()).collect(),
+        group_id: source.group_id.map(|g| g.to_string()),
+        iteration_id: source.iteration_id.map(IterationRef::Id),
+        custom_fields: vec![],
+        parent_story_id: None,
+    }
+}
+
+/// Collect the parts of `source` that `create` has no flags for.
+pub(super) fn copy_extras(
+    source: &api::types::Story,
+    with_comments: bool,
+    with_links: bool,
+) -> Result<CreateExtras, Box<dyn Error>> {
+    let custom_fields = source
+        .custom_fields
+        .iter()
+        .map(|cf| api::types::CustomFieldValueParams {
+            field_id: cf.field_id,
+            value: Some(cf.value.clone()),
+            value_id: cf.value_id,
+        })
+        .collect();
+
+    let tasks = source
+        .tasks
+        .iter()
+        .map(|t| -> Result<_, String> {
+            Ok(api::types::CreateTaskParams {
+                complete: Some(t.complete),
+                created_at: None,
+                description: t
+                    .description
+                    .parse()
+                    .map_err(|e| format!("Invalid task description: {e}"))?,
+                external_id: None,
+                owner_ids: t.owner_ids.clone(),
+                updated_at: None,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let comments = if with_comments {
+        source
+            .comments
+            .iter()
+            .filter(|c| !c.deleted)
+            .filter_map(|c| c.text.as_deref().map(|text| (c, text)))
+            .map(|(c, text)| -> Result<_, String> {
+                Ok(api::types::CreateStoryCommentParams {
+                    author_id: c.author_id,
+                    created_at: None,
+                    external_id: None,
+                    parent_id: None,
+                    text: text
+                        .parse()
+                        .map_err(|e| format!("Invalid comment text: {e}"))?,
+                    updated_at: None,
+                })
+            })
+            .collect::<Result<_, _>>()?
+    } else {
+        vec![]
+    };
+
+    // The new story takes the place of the source on whichever side of the
+    // link it was on, so only the other end is sent.
+    let story_links = if with_links {
+        source
+            .story_links
+            .iter()
+            .map(|link| -> Result<_, String> {
+                let verb = link
+                    .verb
+                    .parse()
+                    .map_err(|e| format!("Invalid link verb: {e}"))?;
+                let (subject_id, object_id) = if link.subject_id == source.id {
+                    (None, Some(link.object_id))
+                } else {
+                    (Some(link.subject_id), None)
+                };
+                Ok(api::types::CreateStoryLinkParams {
+                    object_id,
+                    subject_id,
+                    verb,
+                })
+            })
+            .collect::<Result<_, _>>()?
+    } else {
+        vec![]
+    };
+
+    Ok(CreateExtras {
+        project_id: source.project_id,
+        custom_fields,
+        tasks,
+        comments,
+        story_links,
+    })
+}
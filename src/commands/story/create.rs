@@ -4,6 +4,7 @@ use std::path::Path;
 use clap::Args;
 
 use crate::api;
+use crate::commands::iteration::helpers::{IterationRef, resolve_iteration_id};
 use crate::output::OutputConfig;
 
 use super::helpers::{
@@ -54,9 +55,10 @@ pub struct CreateArgs {
     #[arg(long)]
     pub group_id: Option<String>,
 
-    /// The iteration ID to assign this story to
+    /// The iteration to assign this story to: an ID, or current, next or
+    /// previous (for the story's team when --group-id is given)
     #[arg(long)]
-    pub iteration_id: Option<i64>,
+    pub iteration_id: Option<IterationRef>,
 
     /// Set a custom field value (format: "FieldName=Value", repeatable)
     #[arg(long = "custom-field")]
@@ -67,12 +69,40 @@ pub struct CreateArgs {
     pub parent_story_id: Option<i64>,
 }
 
+/// Fields that have no CLI flag but can be sent alongside a create request,
+/// used by commands that build on `create` such as `story clone`.
+#[derive(Default)]
+pub(crate) struct CreateExtras {
+    pub project_id: Option<i64>,
+    pub custom_fields: Vec<api::types::CustomFieldValueParams>,
+    pub tasks: Vec<api::types::CreateTaskParams>,
+    pub comments: Vec<api::types::CreateStoryCommentParams>,
+    pub story_links: Vec<api::types::CreateStoryLinkParams>,
+}
+
 pub async fn run(
     args: &CreateArgs,
     client: &api::Client,
     cache_dir: &Path,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
+    if let Some(story) =
+        create_story(args, &CreateExtras::default(), client, cache_dir, out).await?
+    {
+        print_created(&story, out)?;
+    }
+    Ok(())
+}
+
+/// Create a story from `args` plus `extras`. Returns `None` in dry-run mode,
+/// after the request has been printed.
+pub(crate) async fn create_story(
+    args: &CreateArgs,
+    extras: &CreateExtras,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<Option<api::types::Story>, Box<dyn Error>> {
     let name_str = args.name.as_ref().ok_or("Name is required")?;
     let name = name_str
         .parse::<api::types::CreateStoryParamsName>()
@@ -106,6 +136,13 @@ pub async fn run(
         None => None,
     };
 
+    let resolved_iteration_id = match args.iteration_id {
+        Some(val) => {
+            Some(resolve_iteration_id(val, args.group_id.as_deref(), client, cache_dir).await?)
+        }
+        None => None,
+    };
+
     let labels: Vec<api::types::CreateLabelParams> = args
         .labels
         .iter()
@@ -119,8 +156,9 @@ pub async fn run(
         })
         .collect::<Result<_, _>>()?;
 
-    let custom_field_params =
+    let mut custom_field_params =
         resolve_custom_field_args(&args.custom_fields, client, cache_dir).await?;
+    custom_field_params.extend(extras.custom_fields.iter().cloned());
 
     if out.is_dry_run() {
         let mut body = serde_json::json!({ "name": name_str });
@@ -153,7 +191,7 @@ pub async fn run(
                     .collect::<Vec<_>>()
             );
         }
-        if let Some(iter_id) = args.iteration_id {
+        if let Some(iter_id) = resolved_iteration_id {
             body["iteration_id"] = serde_json::json!(iter_id);
         }
         if let Some(parent_id) = args.parent_story_id {
@@ -162,7 +200,20 @@ pub async fn run(
         if !custom_field_params.is_empty() {
             body["custom_fields"] = serde_json::json!(custom_field_params);
         }
-        return out.dry_run_request("POST", "/api/v3/stories", Some(&body));
+        if let Some(project_id) = extras.project_id {
+            body["project_id"] = serde_json::json!(project_id);
+        }
+        if !extras.tasks.is_empty() {
+            body["tasks"] = serde_json::json!(extras.tasks);
+        }
+        if !extras.comments.is_empty() {
+            body["comments"] = serde_json::json!(extras.comments);
+        }
+        if !extras.story_links.is_empty() {
+            body["story_links"] = serde_json::json!(extras.story_links);
+        }
+        out.dry_run_request("POST", "/api/v3/stories", Some(&body))?;
+        return Ok(None);
     }
 
     let story = client
@@ -193,7 +244,7 @@ pub async fn run(
             if !labels.is_empty() {
                 b = b.labels(labels);
             }
-            if let Some(iter_id) = args.iteration_id {
+            if let Some(iter_id) = resolved_iteration_id {
                 b = b.iteration_id(Some(iter_id));
             }
             if let Some(parent_id) = args.parent_story_id {
@@ -202,6 +253,18 @@ pub async fn run(
             if !custom_field_params.is_empty() {
                 b = b.custom_fields(custom_field_params);
             }
+            if let Some(project_id) = extras.project_id {
+                b = b.project_id(Some(project_id));
+            }
+            if !extras.tasks.is_empty() {
+                b = b.tasks(extras.tasks.clone());
+            }
+            if !extras.comments.is_empty() {
+                b = b.comments(extras.comments.clone());
+            }
+            if !extras.story_links.is_empty() {
+                b = b.story_links(extras.story_links.clone());
+            }
             b
         })
         .send()
@@ -211,8 +274,16 @@ pub async fn run(
                 "Failed to create story: {}",
                 crate::api::format_api_error(&e)
             )
-        })?;
+        })?
+        .into_inner();
+
+    Ok(Some(story))
+}
 
+pub(crate) fn print_created(
+    story: &api::types::Story,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
     if out.is_machine_readable() {
         out_println!(
             out,
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::path::Path;
 
 use crate::api;
 use crate::out_println;
@@ -7,7 +8,9 @@ use crate::output::OutputConfig;
 pub async fn run(
     id: i64,
     confirm: bool,
+    trash: bool,
     client: &api::Client,
+    cache_dir: &Path,
     out: &OutputConfig,
 ) -> Result<(), Box<dyn Error>> {
     if out.is_dry_run() {
@@ -31,6 +34,12 @@ pub async fn run(
 
     let name = story.name.clone();
 
+    let trash_path = if trash {
+        Some(super::trash::save(&story, cache_dir)?)
+    } else {
+        None
+    };
+
     client
         .delete_story()
         .story_public_id(id)
@@ -48,5 +57,12 @@ pub async fn run(
         return Ok(());
     }
     out_println!(out, "Deleted story {id} - {name}");
+    if let Some(path) = trash_path {
+        out_println!(
+            out,
+            "Saved snapshot to {} (restore with: shortcut story restore --from-trash {id})",
+            path.display()
+        );
+    }
     Ok(())
 }
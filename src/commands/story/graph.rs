@@ -0,0 +1,558 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::commands::iteration::helpers::{IterationRef, resolve_iteration_id};
+use crate::output::OutputConfig;
+
+use super::helpers::build_workflow_state_id_map;
+use super::link::invert_verb;
+use crate::out_println;
+
+#[derive(Args)]
+#[command(group(
+    clap::ArgGroup::new("scope")
+        .required(true)
+        .args(["id", "epic_id", "iteration_id"])
+))]
+pub struct GraphArgs {
+    /// Start from a single story
+    #[arg(long)]
+    pub id: Option<i64>,
+
+    /// Start from every story in an epic
+    #[arg(long, visible_alias = "epic")]
+    pub epic_id: Option<i64>,
+
+    /// Start from every story in an iteration (ID or current, next, previous)
+    #[arg(long, visible_alias = "iteration")]
+    pub iteration_id: Option<IterationRef>,
+
+    /// Team (group) whose iterations `current`, `next` and `previous` refer
+    /// to, by @mention_name or UUID
+    #[arg(long, requires = "iteration_id")]
+    pub group: Option<String>,
+
+    /// Maximum number of links to follow from the starting stories
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    /// Render as Graphviz DOT
+    #[arg(long, conflicts_with = "mermaid")]
+    pub dot: bool,
+
+    /// Render as a Mermaid flowchart
+    #[arg(long)]
+    pub mermaid: bool,
+}
+
+const PARENT_OF: &str = "parent of";
+
+struct Node {
+    id: i64,
+    name: String,
+    story_type: String,
+    workflow_state_id: i64,
+    completed: bool,
+}
+
+/// A directed edge `subject --verb--> object`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Edge {
+    subject_id: i64,
+    verb: String,
+    object_id: i64,
+}
+
+struct Graph {
+    nodes: BTreeMap<i64, Node>,
+    edges: BTreeSet<Edge>,
+    roots: Vec<i64>,
+}
+
+/// The relationships of a story as seen from the API, before traversal.
+struct Fetched {
+    node: Node,
+    edges: Vec<Edge>,
+}
+
+impl From<&api::types::Story> for Fetched {
+    fn from(s: &api::types::Story) -> Self {
+        Fetched {
+            node: Node {
+                id: s.id,
+                name: s.name.clone(),
+                story_type: s.story_type.clone(),
+                workflow_state_id: s.workflow_state_id,
+                completed: s.completed,
+            },
+            edges: story_edges(
+                s.id,
+                s.parent_story_id,
+                &s.sub_task_story_ids,
+                &s.story_links,
+            ),
+        }
+    }
+}
+
+impl From<&api::types::StorySlim> for Fetched {
+    fn from(s: &api::types::StorySlim) -> Self {
+        Fetched {
+            node: Node {
+                id: s.id,
+                name: s.name.clone(),
+                story_type: s.story_type.clone(),
+                workflow_state_id: s.workflow_state_id,
+                completed: s.completed,
+            },
+            edges: story_edges(
+                s.id,
+                s.parent_story_id,
+                &s.sub_task_story_ids,
+                &s.story_links,
+            ),
+        }
+    }
+}
+
+fn story_edges(
+    id: i64,
+    parent_story_id: Option<i64>,
+    sub_task_story_ids: &[i64],
+    links: &[api::types::TypedStoryLink],
+) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = links
+        .iter()
+        .map(|l| Edge {
+            subject_id: l.subject_id,
+            verb: l.verb.clone(),
+            object_id: l.object_id,
+        })
+        .collect();
+    if let Some(parent) = parent_story_id {
+        edges.push(Edge {
+            subject_id: parent,
+            verb: PARENT_OF.to_string(),
+            object_id: id,
+        });
+    }
+    for &child in sub_task_story_ids {
+        edges.push(Edge {
+            subject_id: id,
+            verb: PARENT_OF.to_string(),
+            object_id: child,
+        });
+    }
+    edges
+}
+
+pub async fn run(
+    args: &GraphArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let seeds: Vec<Fetched> = if let Some(id) = args.id {
+        vec![fetch_story(id, client).await?]
+    } else if let Some(epic_id) = args.epic_id {
+        let stories = client
+            .list_epic_stories()
+            .epic_public_id(epic_id)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to list epic stories: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+        stories.iter().map(Fetched::from).collect()
+    } else if let Some(iteration) = args.iteration_id {
+        let iteration_id =
+            resolve_iteration_id(iteration, args.group.as_deref(), client, cache_dir).await?;
+        let stories = client
+            .list_iteration_stories()
+            .iteration_public_id(iteration_id)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to list iteration stories: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+        stories.iter().map(Fetched::from).collect()
+    } else {
+        return Err("One of --id, --epic-id or --iteration-id is required".into());
+    };
+
+    if seeds.is_empty() {
+        out_println!(out, "No stories found");
+        return Ok(());
+    }
+
+    let graph = build_graph(seeds, args.depth, client).await?;
+    let cycles = find_cycles(&graph);
+    let blocked = find_blocked(&graph);
+
+    if out.is_machine_readable() {
+        let json = serde_json::json!({
+            "nodes": graph.nodes.values().map(|n| serde_json::json!({
+                "id": n.id,
+                "name": n.name,
+                "story_type": n.story_type,
+                "workflow_state_id": n.workflow_state_id,
+                "completed": n.completed,
+            })).collect::<Vec<_>>(),
+            "edges": graph.edges.iter().map(|e| serde_json::json!({
+                "subject_id": e.subject_id,
+                "verb": e.verb,
+                "object_id": e.object_id,
+            })).collect::<Vec<_>>(),
+            "cycles": cycles,
+            "blocked": blocked.iter().map(|(id, blockers)| serde_json::json!({
+                "id": id,
+                "blocked_by": blockers,
+            })).collect::<Vec<_>>(),
+        });
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        for id in graph.nodes.keys() {
+            out_println!(out, "{id}");
+        }
+        return Ok(());
+    }
+
+    let state_map = build_workflow_state_id_map(client, cache_dir).await?;
+    let state_name = |n: &Node| {
+        state_map
+            .get(&n.workflow_state_id)
+            .cloned()
+            .unwrap_or_else(|| n.workflow_state_id.to_string())
+    };
+
+    if args.dot {
+        out.write_str(format_args!(
+            "{}",
+            render_dot(&graph, &blocked, &state_name)
+        ))?;
+        return Ok(());
+    }
+
+    if args.mermaid {
+        out.write_str(format_args!(
+            "{}",
+            render_mermaid(&graph, &blocked, &state_name)
+        ))?;
+        return Ok(());
+    }
+
+    out.write_str(format_args!("{}", render_tree(&graph, &state_name)))?;
+
+    if !cycles.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Dependency cycles:");
+        for cycle in &cycles {
+            let path: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+            out_println!(out, "  {} -> {}", path.join(" -> "), cycle[0]);
+        }
+    }
+
+    if !blocked.is_empty() {
+        out_println!(out, "");
+        out_println!(out, "Blocked stories:");
+        for (id, blockers) in &blocked {
+            let name = graph.nodes.get(id).map(|n| n.name.as_str()).unwrap_or("?");
+            let by: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+            out_println!(out, "  {id} - {name} (blocked by {})", by.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_story(id: i64, client: &api::Client) -> Result<Fetched, Box<dyn Error>> {
+    let story = client
+        .get_story()
+        .story_public_id(id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get story: {}", crate::api::format_api_error(&e)))?;
+    Ok(Fetched::from(&*story))
+}
+
+/// Fetch a linked story, or `None` if it no longer exists; such stories stay
+/// in the graph as bare IDs.
+async fn fetch_linked_story(
+    id: i64,
+    client: &api::Client,
+) -> Result<Option<Fetched>, Box<dyn Error>> {
+    match client.get_story().story_public_id(id).send().await {
+        Ok(story) => Ok(Some(Fetched::from(&*story))),
+        Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => Ok(None),
+        Err(e) => Err(format!(
+            "Failed to get story {id}: {}",
+            crate::api::format_api_error(&e)
+        )
+        .into()),
+    }
+}
+
+/// Breadth-first walk over links and parent/sub-task relationships.
+async fn build_graph(
+    seeds: Vec<Fetched>,
+    max_depth: Option<usize>,
+    client: &api::Client,
+) -> Result<Graph, Box<dyn Error>> {
+    let mut nodes: BTreeMap<i64, Node> = BTreeMap::new();
+    let mut edges: BTreeSet<Edge> = BTreeSet::new();
+    let mut roots: Vec<i64> = Vec::new();
+    let mut queue: VecDeque<(Fetched, usize)> = VecDeque::new();
+    let mut queued: HashSet<i64> = HashSet::new();
+
+    for seed in seeds {
+        roots.push(seed.node.id);
+        queued.insert(seed.node.id);
+        queue.push_back((seed, 0));
+    }
+
+    while let Some((fetched, depth)) = queue.pop_front() {
+        let id = fetched.node.id;
+        nodes.insert(id, fetched.node);
+
+        let within_depth = max_depth.is_none_or(|max| depth < max);
+        for edge in fetched.edges {
+            let other = if edge.subject_id == id {
+                edge.object_id
+            } else {
+                edge.subject_id
+            };
+            if !within_depth && !queued.contains(&other) {
+                continue;
+            }
+            edges.insert(edge);
+            if queued.insert(other)
+                && let Some(next) = fetch_linked_story(other, client).await?
+            {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    Ok(Graph {
+        nodes,
+        edges,
+        roots,
+    })
+}
+
+/// Find cycles among `blocks` edges using depth-first search.
+fn find_cycles(graph: &Graph) -> Vec<Vec<i64>> {
+    let mut adjacency: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    for e in graph.edges.iter().filter(|e| e.verb == "blocks") {
+        adjacency.entry(e.subject_id).or_default().push(e.object_id);
+    }
+
+    fn visit(
+        id: i64,
+        adjacency: &BTreeMap<i64, Vec<i64>>,
+        path: &mut Vec<i64>,
+        done: &mut HashSet<i64>,
+        cycles: &mut Vec<Vec<i64>>,
+    ) {
+        path.push(id);
+        for &next in adjacency.get(&id).map(|v| v.as_slice()).unwrap_or(&[]) {
+            if let Some(pos) = path.iter().position(|&p| p == next) {
+                cycles.push(path[pos..].to_vec());
+            } else if !done.contains(&next) {
+                visit(next, adjacency, path, done, cycles);
+            }
+        }
+        path.pop();
+        done.insert(id);
+    }
+
+    let mut done: HashSet<i64> = HashSet::new();
+    let mut cycles: Vec<Vec<i64>> = Vec::new();
+    for &id in adjacency.keys() {
+        if !done.contains(&id) {
+            visit(id, &adjacency, &mut Vec::new(), &mut done, &mut cycles);
+        }
+    }
+    cycles
+}
+
+/// Stories that are not done and have at least one blocker that is not done.
+/// Stories that no longer exist neither block nor are blocked.
+fn find_blocked(graph: &Graph) -> BTreeMap<i64, Vec<i64>> {
+    let is_open = |id: &i64| graph.nodes.get(id).is_some_and(|n| !n.completed);
+    let mut blocked: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    for e in graph.edges.iter().filter(|e| e.verb == "blocks") {
+        if is_open(&e.object_id) && is_open(&e.subject_id) {
+            blocked.entry(e.object_id).or_default().push(e.subject_id);
+        }
+    }
+    blocked
+}
+
+fn node_label(n: &Node, state_name: &dyn Fn(&Node) -> String) -> String {
+    format!("{} {} [{}]", n.id, n.name, state_name(n))
+}
+
+/// Relationships of `id` from its own perspective, as (display verb, other ID).
+fn neighbours(graph: &Graph, id: i64) -> Vec<(&str, i64)> {
+    let mut result = Vec::new();
+    for e in &graph.edges {
+        if e.subject_id == id {
+            result.push((e.verb.as_str(), e.object_id));
+        } else if e.object_id == id {
+            let verb = if e.verb == PARENT_OF {
+                "sub-task of"
+            } else {
+                invert_verb(&e.verb)
+            };
+            result.push((verb, e.subject_id));
+        }
+    }
+    result
+}
+
+fn render_tree(graph: &Graph, state_name: &dyn Fn(&Node) -> String) -> String {
+    fn walk(
+        graph: &Graph,
+        id: i64,
+        from: Option<i64>,
+        prefix: &str,
+        visited: &mut HashSet<i64>,
+        state_name: &dyn Fn(&Node) -> String,
+        out: &mut String,
+    ) {
+        let children: Vec<(&str, i64)> = neighbours(graph, id)
+            .into_iter()
+            .filter(|(_, other)| Some(*other) != from)
+            .collect();
+        for (i, (verb, other)) in children.iter().enumerate() {
+            let last = i == children.len() - 1;
+            let branch = if last { "└── " } else { "├── " };
+            let label = graph
+                .nodes
+                .get(other)
+                .map(|n| node_label(n, state_name))
+                .unwrap_or_else(|| other.to_string());
+            if visited.contains(other) {
+                out.push_str(&format!("{prefix}{branch}{verb} {other} (see above)\n"));
+                continue;
+            }
+            out.push_str(&format!("{prefix}{branch}{verb} {label}\n"));
+            if !graph.nodes.contains_key(other) {
+                continue;
+            }
+            visited.insert(*other);
+            let next_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            walk(
+                graph,
+                *other,
+                Some(id),
+                &next_prefix,
+                visited,
+                state_name,
+                out,
+            );
+        }
+    }
+
+    let mut out = String::new();
+    let mut visited: HashSet<i64> = HashSet::new();
+    for &root in &graph.roots {
+        if !visited.insert(root) {
+            continue;
+        }
+        if let Some(n) = graph.nodes.get(&root) {
+            out.push_str(&node_label(n, state_name));
+            out.push('\n');
+        }
+        walk(graph, root, None, "", &mut visited, state_name, &mut out);
+    }
+    out
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(
+    graph: &Graph,
+    blocked: &BTreeMap<i64, Vec<i64>>,
+    state_name: &dyn Fn(&Node) -> String,
+) -> String {
+    let mut out = String::from("digraph stories {\n  rankdir=LR;\n  node [shape=box];\n");
+    for n in graph.nodes.values() {
+        let mut attrs = format!(
+            "label=\"{}: {}\\n({})\"",
+            n.id,
+            dot_escape(&n.name),
+            dot_escape(&state_name(n))
+        );
+        if blocked.contains_key(&n.id) {
+            attrs.push_str(", color=red");
+        } else if n.completed {
+            attrs.push_str(", style=dashed");
+        }
+        out.push_str(&format!("  \"{}\" [{attrs}];\n", n.id));
+    }
+    for e in &graph.edges {
+        let style = match e.verb.as_str() {
+            "blocks" => "",
+            "relates to" => ", dir=none, style=dashed",
+            PARENT_OF => ", style=dotted",
+            _ => ", style=dashed",
+        };
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"{style}];\n",
+            e.subject_id, e.object_id, e.verb
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(
+    graph: &Graph,
+    blocked: &BTreeMap<i64, Vec<i64>>,
+    state_name: &dyn Fn(&Node) -> String,
+) -> String {
+    let mut out = String::from("graph LR\n");
+    for n in graph.nodes.values() {
+        out.push_str(&format!(
+            "  s{}[\"{}: {} ({})\"]\n",
+            n.id,
+            n.id,
+            n.name.replace('"', "#quot;"),
+            state_name(n).replace('"', "#quot;")
+        ));
+    }
+    for e in &graph.edges {
+        let arrow = match e.verb.as_str() {
+            "blocks" => "-->",
+            "relates to" => "-.-",
+            _ => "-.->",
+        };
+        out.push_str(&format!(
+            "  s{} {arrow}|{}| s{}\n",
+            e.subject_id, e.verb, e.object_id
+        ));
+    }
+    if !blocked.is_empty() {
+        out.push_str("  classDef blocked stroke:#d33,stroke-width:2px\n");
+        let ids: Vec<String> = blocked.keys().map(|id| format!("s{id}")).collect();
+        out.push_str(&format!("  class {} blocked\n", ids.join(",")));
+    }
+    out
+}
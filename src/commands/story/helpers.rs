@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
@@ -177,6 +177,70 @@ pub async fn build_workflow_state_id_map(
     Ok(map)
 }
 
+/// Map each workflow state ID to its type (`backlog`, `unstarted`, `started`, `done`).
+pub async fn build_workflow_state_type_map(
+    client: &api::Client,
+) -> Result<HashMap<i64, String>, Box<dyn Error>> {
+    let workflows = client.list_workflows().send().await.map_err(|e| {
+        format!(
+            "Failed to list workflows: {}",
+            crate::api::format_api_error(&e)
+        )
+    })?;
+
+    Ok(workflows
+        .iter()
+        .flat_map(|wf| wf.states.iter())
+        .map(|state| (state.id, state.type_.clone()))
+        .collect())
+}
+
+/// Workflow state types in the order state groups are printed.
+pub(crate) const STATE_TYPE_ORDER: &[&str] = &["backlog", "unstarted", "started", "done"];
+
+/// Stories sharing a workflow state, with their summed estimates.
+pub struct StateGroup<'a> {
+    pub state_id: i64,
+    pub name: String,
+    pub points: i64,
+    pub stories: Vec<&'a api::types::StorySlim>,
+}
+
+/// Group stories by workflow state, ordering groups by state type and then
+/// by state ID, and stories within a group by position.
+pub fn group_by_state<'a>(
+    stories: &'a [api::types::StorySlim],
+    state_names: &HashMap<i64, String>,
+    state_types: &HashMap<i64, String>,
+) -> Vec<StateGroup<'a>> {
+    let mut groups: BTreeMap<(usize, i64), StateGroup<'a>> = BTreeMap::new();
+    for story in stories {
+        let state_id = story.workflow_state_id;
+        let type_rank = state_types
+            .get(&state_id)
+            .and_then(|t| STATE_TYPE_ORDER.iter().position(|s| s == t))
+            .unwrap_or(STATE_TYPE_ORDER.len());
+        let group = groups
+            .entry((type_rank, state_id))
+            .or_insert_with(|| StateGroup {
+                state_id,
+                name: state_names
+                    .get(&state_id)
+                    .cloned()
+                    .unwrap_or_else(|| state_id.to_string()),
+                points: 0,
+                stories: Vec::new(),
+            });
+        group.points += story.estimate.unwrap_or(0);
+        group.stories.push(story);
+    }
+    let mut groups: Vec<StateGroup<'a>> = groups.into_values().collect();
+    for group in &mut groups {
+        group.stories.sort_by_key(|s| s.position);
+    }
+    groups
+}
+
 // --- Resolve a single state ID to its display name ---
 
 pub async fn resolve_workflow_state_name(
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::error::Error;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, NaiveDate, Utc};
 use clap::Args;
+use serde::{Deserialize, Serialize};
 
 use crate::api;
 use crate::output::OutputConfig;
@@ -57,45 +59,59 @@ pub async fn run(
     let member_map = build_member_lookup(cache_dir, client).await?;
 
     // Build state and label lookups from references
-    let mut state_map: HashMap<i64, String> = HashMap::new();
-    let mut label_map: HashMap<i64, String> = HashMap::new();
+    let state_map = reference_state_names(entries.iter().copied());
+    let label_map = reference_label_names(entries.iter().copied());
+
     for entry in &entries {
-        for reference in &entry.references {
-            match reference {
-                api::types::HistoryReferencesItem::WorkflowState(ws) => {
-                    if let api::types::HistoryReferenceWorkflowStateId::Int64(id) = ws.id {
-                        state_map.insert(id, ws.name.clone());
-                    }
-                }
-                api::types::HistoryReferencesItem::Label(lb) => {
-                    if let api::types::HistoryReferenceLabelId::Int64(id) = lb.id {
-                        label_map.insert(id, lb.name.clone());
-                    }
-                }
-                _ => {}
-            }
-        }
+        print_entry(out, entry, &state_map, &label_map, &member_map)?;
     }
 
-    for entry in &entries {
-        let who = entry
-            .member_id
-            .as_ref()
-            .and_then(|id| member_map.get(&id.to_string()))
-            .map(|m| format!("@{m}"))
-            .unwrap_or_else(|| "(system)".to_string());
+    Ok(())
+}
 
-        for action in &entry.actions {
-            if let Some(line) = format_action(action, &state_map, &label_map, &member_map) {
-                out_println!(out, "[{}] {who}: {line}", entry.changed_at);
-            }
+/// Print one line per action in a history entry, prefixed with time and actor.
+pub(crate) fn print_entry(
+    out: &OutputConfig,
+    entry: &api::types::History,
+    state_map: &HashMap<i64, String>,
+    label_map: &HashMap<i64, String>,
+    member_map: &HashMap<String, String>,
+) -> Result<(), Box<dyn Error>> {
+    let who = actor(entry, member_map)
+        .map(|m| format!("@{m}"))
+        .unwrap_or_else(|| "(system)".to_string());
+
+    for action in &entry.actions {
+        if let Some(line) = format_action(action, state_map, label_map, member_map) {
+            out_println!(out, "[{}] {who}: {line}", entry.changed_at);
         }
     }
-
     Ok(())
 }
 
-async fn build_member_lookup(
+/// The mention name of the member behind a history entry, if any.
+pub(crate) fn actor<'a>(
+    entry: &api::types::History,
+    member_map: &'a HashMap<String, String>,
+) -> Option<&'a String> {
+    entry
+        .member_id
+        .as_ref()
+        .and_then(|id| member_map.get(&id.to_string()))
+}
+
+/// Whether a history entry moves a story to another workflow state.
+pub(crate) fn changes_state(entry: &api::types::History) -> bool {
+    entry.actions.iter().any(|action| {
+        matches!(
+            action,
+            api::types::HistoryActionsItem::StoryUpdate(a)
+                if a.changes.as_ref().is_some_and(|c| c.workflow_state_id.is_some())
+        )
+    })
+}
+
+pub(crate) async fn build_member_lookup(
     cache_dir: &Path,
     client: &api::Client,
 ) -> Result<HashMap<String, String>, Box<dyn Error>> {
@@ -126,7 +142,7 @@ async fn build_member_lookup(
     Ok(map)
 }
 
-fn format_action(
+pub(crate) fn format_action(
     action: &api::types::HistoryActionsItem,
     state_map: &HashMap<i64, String>,
     label_map: &HashMap<i64, String>,
@@ -376,3 +392,288 @@ fn format_story_update(
 
     parts.join(", ")
 }
+
+/// Collect workflow state names from the references attached to history entries.
+pub(crate) fn reference_state_names<'a>(
+    entries: impl IntoIterator<Item = &'a api::types::History>,
+) -> HashMap<i64, String> {
+    let mut map = HashMap::new();
+    for entry in entries {
+        for reference in &entry.references {
+            if let api::types::HistoryReferencesItem::WorkflowState(ws) = reference
+                && let api::types::HistoryReferenceWorkflowStateId::Int64(id) = ws.id
+            {
+                map.insert(id, ws.name.clone());
+            }
+        }
+    }
+    map
+}
+
+/// Collect label names from the references attached to history entries.
+pub(crate) fn reference_label_names<'a>(
+    entries: impl IntoIterator<Item = &'a api::types::History>,
+) -> HashMap<i64, String> {
+    let mut map = HashMap::new();
+    for entry in entries {
+        for reference in &entry.references {
+            if let api::types::HistoryReferencesItem::Label(lb) = reference
+                && let api::types::HistoryReferenceLabelId::Int64(id) = lb.id
+            {
+                map.insert(id, lb.name.clone());
+            }
+        }
+    }
+    map
+}
+
+/// A workflow state change parsed from a story's history.
+pub(crate) struct StateTransition {
+    pub changed_at: DateTime<Utc>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// Extract workflow state changes from history entries, ordered by time.
+///
+/// Story creation is reported as a transition from `None` into the initial state.
+pub(crate) fn state_transitions(entries: &[api::types::History]) -> Vec<StateTransition> {
+    use api::types::HistoryActionsItem::*;
+
+    let mut transitions = Vec::new();
+    for entry in entries {
+        let Ok(changed_at) = DateTime::parse_from_rfc3339(&entry.changed_at) else {
+            continue;
+        };
+        let changed_at = changed_at.with_timezone(&Utc);
+        for action in &entry.actions {
+            match action {
+                StoryCreate(a) if a.workflow_state_id.is_some() => {
+                    transitions.push(StateTransition {
+                        changed_at,
+                        from: None,
+                        to: a.workflow_state_id,
+                    });
+                }
+                StoryUpdate(a) => {
+                    if let Some(ws) = a
+                        .changes
+                        .as_ref()
+                        .and_then(|c| c.workflow_state_id.as_ref())
+                    {
+                        transitions.push(StateTransition {
+                            changed_at,
+                            from: ws.old,
+                            to: ws.new,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    transitions.sort_by_key(|t| t.changed_at);
+    transitions
+}
+
+/// When the story was most recently placed in `epic_id`, either at creation
+/// or by a later update, according to its history.
+pub(crate) fn epic_added_at(
+    entries: &[api::types::History],
+    epic_id: i64,
+) -> Option<DateTime<Utc>> {
+    use api::types::HistoryActionsItem::*;
+
+    last_matching_action(entries, |action| match action {
+        StoryCreate(a) => a.epic_id == Some(epic_id),
+        StoryUpdate(a) => a
+            .changes
+            .as_ref()
+            .and_then(|c| c.epic_id.as_ref())
+            .is_some_and(|e| e.new == Some(epic_id)),
+        _ => false,
+    })
+}
+
+/// When the story was most recently placed in `iteration_id`, either at
+/// creation or by a later update, according to its history.
+pub(crate) fn iteration_added_at(
+    entries: &[api::types::History],
+    iteration_id: i64,
+) -> Option<DateTime<Utc>> {
+    use api::types::HistoryActionsItem::*;
+
+    last_matching_action(entries, |action| match action {
+        StoryCreate(a) => a.iteration_id == Some(iteration_id),
+        StoryUpdate(a) => a
+            .changes
+            .as_ref()
+            .and_then(|c| c.iteration_id.as_ref())
+            .is_some_and(|i| i.new == Some(iteration_id)),
+        _ => false,
+    })
+}
+
+fn last_matching_action(
+    entries: &[api::types::History],
+    matches: impl Fn(&api::types::HistoryActionsItem) -> bool,
+) -> Option<DateTime<Utc>> {
+    let mut latest = None;
+    for entry in entries {
+        let Ok(changed_at) = DateTime::parse_from_rfc3339(&entry.changed_at) else {
+            continue;
+        };
+        let changed_at = changed_at.with_timezone(&Utc);
+        if entry.actions.iter().any(&matches) && latest.is_none_or(|t| changed_at > t) {
+            latest = Some(changed_at);
+        }
+    }
+    latest
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedHistory {
+    updated_at: Option<DateTime<Utc>>,
+    entries: Vec<api::types::History>,
+}
+
+/// Fetch a story's history, reusing the local cache when the story has not
+/// been updated since the history was last fetched.
+pub(crate) async fn fetch_history_cached(
+    client: &api::Client,
+    story_id: i64,
+    updated_at: Option<DateTime<Utc>>,
+    cache_dir: &Path,
+    refresh: bool,
+) -> Result<Vec<api::types::History>, Box<dyn Error>> {
+    let path = history_cache_path(cache_dir, story_id);
+
+    if !refresh
+        && updated_at.is_some()
+        && let Some(cached) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<CachedHistory>(&data).ok())
+        && cached.updated_at == updated_at
+    {
+        return Ok(cached.entries);
+    }
+
+    let entries = client
+        .story_history()
+        .story_public_id(story_id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get story history: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner();
+
+    let cached = CachedHistory {
+        updated_at,
+        entries,
+    };
+    write_history_cache(&path, &cached);
+
+    Ok(cached.entries)
+}
+
+/// When a story counted towards a container (epic or iteration) and when it
+/// moved into and out of a done state, for burnup and burndown charts.
+pub(crate) struct StoryTimeline {
+    /// The story's current estimate.
+    pub points: i64,
+    /// The day the story joined the container, or was created.
+    pub added: NaiveDate,
+    transitions: Vec<(NaiveDate, bool)>,
+}
+
+impl StoryTimeline {
+    /// Whether the story ended `date` in a done state.
+    pub fn done_on(&self, date: NaiveDate) -> bool {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|(d, _)| *d <= date)
+            .is_some_and(|(_, done)| *done)
+    }
+
+    /// The day of the story's last recorded change.
+    pub fn last_change(&self) -> NaiveDate {
+        self.transitions
+            .iter()
+            .map(|(d, _)| *d)
+            .chain([self.added])
+            .max()
+            .unwrap_or(self.added)
+    }
+}
+
+/// Build each story's timeline from its (cached) history. `added_at` finds
+/// when the story joined the container; stories without such an entry count
+/// from their creation. Stories without state changes in their history fall
+/// back to `completed_at`.
+pub(crate) async fn story_timelines(
+    stories: &[api::types::StorySlim],
+    added_at: impl Fn(&[api::types::History]) -> Option<DateTime<Utc>>,
+    state_types: &HashMap<i64, String>,
+    client: &api::Client,
+    cache_dir: &Path,
+    refresh: bool,
+) -> Result<Vec<StoryTimeline>, Box<dyn Error>> {
+    let is_done = |id: Option<i64>| {
+        id.and_then(|id| state_types.get(&id))
+            .is_some_and(|t| t == "done")
+    };
+
+    let mut timelines = Vec::with_capacity(stories.len());
+    for story in stories {
+        let entries =
+            fetch_history_cached(client, story.id, story.updated_at, cache_dir, refresh).await?;
+
+        let added = added_at(&entries).unwrap_or(story.created_at).date_naive();
+
+        let mut transitions: Vec<(NaiveDate, bool)> = state_transitions(&entries)
+            .iter()
+            .map(|t| (t.changed_at.date_naive(), is_done(t.to)))
+            .collect();
+        if transitions.is_empty()
+            && let Some(completed_at) = story.completed_at
+        {
+            transitions.push((completed_at.date_naive(), true));
+        }
+
+        timelines.push(StoryTimeline {
+            points: story.estimate.unwrap_or(0),
+            added,
+            transitions,
+        });
+    }
+    Ok(timelines)
+}
+
+fn history_cache_path(cache_dir: &Path, story_id: i64) -> PathBuf {
+    cache_dir
+        .join("story_history")
+        .join(format!("{story_id}.json"))
+}
+
+fn write_history_cache(path: &Path, cached: &CachedHistory) {
+    let Ok(json) = serde_json::to_string(cached) else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let _ = std::fs::write(path, json);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+    }
+}
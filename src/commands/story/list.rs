@@ -43,6 +43,10 @@ pub struct ListArgs {
     /// Include story descriptions in output
     #[arg(long, visible_alias = "descriptions")]
     pub desc: bool,
+
+    /// Include archived stories
+    #[arg(long)]
+    pub archived: bool,
 }
 
 pub async fn run(
@@ -76,6 +80,7 @@ pub async fn run(
         .map_err(|e| format!("Invalid label name: {e}"))?;
 
     let include_desc = args.desc;
+    let include_archived = args.archived;
     let epic_id = args.epic_id;
     let project_id = args.project_id;
 
@@ -103,6 +108,9 @@ pub async fn run(
             if include_desc {
                 b = b.includes_description(Some(true));
             }
+            if !include_archived {
+                b = b.archived(Some(false));
+            }
             b
         })
         .send()
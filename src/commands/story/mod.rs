@@ -1,25 +1,34 @@
+mod archive;
 pub mod branch;
+mod clone;
 pub mod commit;
 mod create;
 mod delete;
 mod get;
 pub mod git;
+mod graph;
 pub mod helpers;
-mod history;
+pub(crate) mod history;
 mod list;
+mod trash;
 mod update;
+mod watch;
 pub mod wizard;
 
 pub mod comment;
 pub mod link;
+pub mod subtask;
 pub mod task;
 
 pub use branch::BranchArgs;
+pub use clone::CloneArgs;
 pub use commit::CommitArgs;
 pub use create::CreateArgs;
+pub use graph::GraphArgs;
 pub use history::HistoryArgs;
 pub use list::ListArgs;
 pub use update::UpdateArgs;
+pub use watch::WatchArgs;
 
 use std::error::Error;
 use std::path::PathBuf;
@@ -49,6 +58,8 @@ pub enum StoryAction {
     },
     /// List/search stories
     List(Box<list::ListArgs>),
+    /// Copy a story, including its tasks, labels and custom fields
+    Clone(Box<clone::CloneArgs>),
     /// Delete a story
     Delete {
         /// The ID of the story to delete
@@ -57,6 +68,27 @@ pub enum StoryAction {
         /// Confirm the irreversible deletion
         #[arg(long)]
         confirm: bool,
+        /// Save a snapshot to the local trash so it can be restored later
+        #[arg(long)]
+        trash: bool,
+    },
+    /// Archive a story (hidden from list and search by default)
+    Archive {
+        /// The ID of the story to archive
+        #[arg(long)]
+        id: i64,
+    },
+    /// Unarchive a story
+    Unarchive {
+        /// The ID of the story to unarchive
+        #[arg(long)]
+        id: i64,
+    },
+    /// Recreate a deleted story from the local trash
+    Restore {
+        /// The ID of the deleted story
+        #[arg(long)]
+        from_trash: i64,
     },
     /// Manage checklist tasks on a story
     Task(task::TaskArgs),
@@ -64,8 +96,14 @@ pub enum StoryAction {
     Link(link::LinkArgs),
     /// Manage comments on a story
     Comment(comment::CommentArgs),
+    /// Manage sub-task stories of a parent story
+    Subtask(subtask::SubtaskArgs),
     /// Show the change history of a story
     History(history::HistoryArgs),
+    /// Show the dependency graph around a story, epic or iteration
+    Graph(Box<graph::GraphArgs>),
+    /// Poll a story and print changes as they happen
+    Watch(watch::WatchArgs),
     /// Generate a git branch name from a story
     Branch(branch::BranchArgs),
     /// Create a git commit with story ID prefix
@@ -114,13 +152,24 @@ pub async fn run(
         StoryAction::Update(update_args) => update::run(update_args, client, &cache_dir, out).await,
         StoryAction::Get { id } => get::run(*id, client, &cache_dir, out).await,
         StoryAction::List(list_args) => list::run(list_args, client, &cache_dir, out).await,
-        StoryAction::Delete { id, confirm } => delete::run(*id, *confirm, client, out).await,
+        StoryAction::Clone(clone_args) => clone::run(clone_args, client, &cache_dir, out).await,
+        StoryAction::Delete { id, confirm, trash } => {
+            delete::run(*id, *confirm, *trash, client, &cache_dir, out).await
+        }
+        StoryAction::Archive { id } => archive::run(*id, true, client, out).await,
+        StoryAction::Unarchive { id } => archive::run(*id, false, client, out).await,
+        StoryAction::Restore { from_trash } => {
+            trash::restore(*from_trash, client, &cache_dir, out).await
+        }
         StoryAction::Task(task_args) => task::run(task_args, client, out).await,
         StoryAction::Link(link_args) => link::run(link_args, client, out).await,
         StoryAction::Comment(args) => comment::run(args, client, &cache_dir, out).await,
+        StoryAction::Subtask(args) => subtask::run(args, client, &cache_dir, out).await,
         StoryAction::History(history_args) => {
             history::run(history_args, client, &cache_dir, out).await
         }
+        StoryAction::Graph(graph_args) => graph::run(graph_args, client, &cache_dir, out).await,
+        StoryAction::Watch(watch_args) => watch::run(watch_args, client, &cache_dir, out).await,
         StoryAction::Branch(branch_args) => branch::run(branch_args, client, out).await,
         StoryAction::Commit(commit_args) => commit::run(commit_args, out),
     }
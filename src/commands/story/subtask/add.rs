@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::output::OutputConfig;
+
+use super::super::create;
+
+#[derive(Args)]
+pub struct AddSubtaskArgs {
+    /// The parent story ID
+    #[arg(long)]
+    pub story_id: i64,
+
+    /// The name of the sub-task story
+    #[arg(long)]
+    pub name: String,
+
+    /// The description of the sub-task story
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// The type of story (feature, bug, chore)
+    #[arg(long, name = "type")]
+    pub story_type: Option<String>,
+
+    /// Owner(s) by @mention_name or UUID (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub owner: Vec<String>,
+
+    /// The workflow state name or ID
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// The story point estimate
+    #[arg(long)]
+    pub estimate: Option<i64>,
+}
+
+pub async fn run(
+    args: &AddSubtaskArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let parent = super::get_parent(args.story_id, client).await?;
+
+    let mut create_args = super::inherited_create_args(&parent, &args.name);
+    create_args.description = args.description.clone();
+    create_args.story_type = args.story_type.clone();
+    create_args.owner = args.owner.clone();
+    create_args.state = args.state.clone();
+    create_args.estimate = args.estimate;
+
+    create::run(&create_args, client, cache_dir, out).await
+}
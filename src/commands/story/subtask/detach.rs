@@ -0,0 +1,54 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+pub async fn run(
+    story_id: i64,
+    sub_task_id: i64,
+    client: &api::Client,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let sub_task = client
+        .get_story()
+        .story_public_id(sub_task_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get story: {}", crate::api::format_api_error(&e)))?;
+
+    if sub_task.parent_story_id != Some(story_id) {
+        return Err(format!("Story {sub_task_id} is not a sub-task of story {story_id}").into());
+    }
+
+    // The generated params skip `None`, so clearing the parent needs an explicit null
+    let body = serde_json::json!({ "parent_story_id": null });
+    let path = format!("/api/v3/stories/{sub_task_id}");
+
+    if out.is_dry_run() {
+        return out.dry_run_request("PUT", &path, Some(&body));
+    }
+
+    api::send_json(client, reqwest::Method::PUT, &path, &body)
+        .await
+        .map_err(|e| format!("Failed to update story: {e}"))?;
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::json!({"id": sub_task_id, "parent_story_id": null})
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{sub_task_id}");
+        return Ok(());
+    }
+    out_println!(
+        out,
+        "Detached story {sub_task_id} - {} from story {story_id}",
+        sub_task.name
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::path::Path;
+
+use crate::api;
+use crate::output::{OutputConfig, Table};
+
+use super::super::helpers::build_workflow_state_id_map;
+use crate::commands::epic::helpers::resolve_member_name;
+use crate::out_println;
+
+pub async fn run(
+    story_id: i64,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let sub_tasks = client
+        .list_story_sub_tasks()
+        .story_public_id(story_id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to list sub-tasks: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if out.is_machine_readable() {
+        let json: Vec<serde_json::Value> = sub_tasks
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.id,
+                    "name": s.name,
+                    "story_type": s.story_type,
+                    "workflow_state_id": s.workflow_state_id,
+                    "owner_ids": s.owner_ids,
+                    "completed": s.completed,
+                })
+            })
+            .collect();
+        out_println!(out, "{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if out.is_quiet() {
+        for s in sub_tasks.iter() {
+            out_println!(out, "{}", s.id);
+        }
+        return Ok(());
+    }
+
+    if sub_tasks.is_empty() {
+        out_println!(out, "No sub-tasks on story {story_id}");
+        return Ok(());
+    }
+
+    let state_map = build_workflow_state_id_map(client, cache_dir).await?;
+
+    let mut table = Table::new(vec!["ID", "Type", "State", "Owners", "Name"]);
+    for s in sub_tasks.iter() {
+        let owners: Vec<String> = s
+            .owner_ids
+            .iter()
+            .map(|id| resolve_member_name(id, cache_dir))
+            .collect();
+        table.add_row(vec![
+            s.id.to_string(),
+            s.story_type.clone(),
+            state_map
+                .get(&s.workflow_state_id)
+                .cloned()
+                .unwrap_or_else(|| s.workflow_state_id.to_string()),
+            owners.join(", "),
+            s.name.clone(),
+        ]);
+    }
+    out.write_str(format_args!("{}", table.render()))?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+mod add;
+mod detach;
+mod list;
+mod promote;
+
+pub use add::AddSubtaskArgs;
+pub use promote::PromoteArgs;
+
+use std::error::Error;
+use std::path::Path;
+
+use clap::{Args, Subcommand};
+
+use crate::api;
+use crate::commands::iteration::IterationRef;
+use crate::output::OutputConfig;
+
+#[derive(Args)]
+pub struct SubtaskArgs {
+    #[command(subcommand)]
+    pub action: SubtaskAction,
+}
+
+#[derive(Subcommand)]
+pub enum SubtaskAction {
+    /// List the sub-task stories of a story
+    List {
+        /// The parent story ID
+        #[arg(long)]
+        story_id: i64,
+    },
+    /// Create a sub-task story that inherits the parent's epic, iteration and team
+    Add(add::AddSubtaskArgs),
+    /// Detach a sub-task story from its parent
+    Detach {
+        /// The parent story ID
+        #[arg(long)]
+        story_id: i64,
+        /// The sub-task story ID
+        #[arg(long)]
+        id: i64,
+    },
+    /// Convert a checklist task into a sub-task story
+    Promote(promote::PromoteArgs),
+}
+
+pub async fn run(
+    args: &SubtaskArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    match &args.action {
+        SubtaskAction::List { story_id } => list::run(*story_id, client, cache_dir, out).await,
+        SubtaskAction::Add(add_args) => add::run(add_args, client, cache_dir, out).await,
+        SubtaskAction::Detach { story_id, id } => detach::run(*story_id, *id, client, out).await,
+        SubtaskAction::Promote(promote_args) => {
+            promote::run(promote_args, client, cache_dir, out).await
+        }
+    }
+}
+
+/// Fetch the parent story, failing with a clear message if it does not exist.
+async fn get_parent(
+    story_id: i64,
+    client: &api::Client,
+) -> Result<api::ResponseValue<api::types::Story>, Box<dyn Error>> {
+    client
+        .get_story()
+        .story_public_id(story_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get story: {}", crate::api::format_api_error(&e)).into())
+}
+
+/// Build `story create` arguments for a sub-task that inherits the parent's
+/// epic, iteration and team.
+fn inherited_create_args(parent: &api::types::Story, name: &str) -> super::CreateArgs {
+    super::CreateArgs {
+        interactive: false,
+        name: Some(name.to_string()),
+        description: None,
+        story_type: None,
+        owner: vec![],
+        state: None,
+        epic_id: parent.epic_id,
+        estimate: None,
+        labels: vec![],
+        group_id: parent.group_id.map(|g| g.to_string()),
+        iteration_id: parent.iteration_id.map(IterationRef::Id),
+        custom_fields: vec![],
+        parent_story_id: Some(parent.id),
+    }
+}
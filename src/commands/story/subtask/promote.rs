@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::output::OutputConfig;
+
+use super::super::create;
+use crate::out_println;
+
+#[derive(Args)]
+pub struct PromoteArgs {
+    /// The story ID that owns the checklist task
+    #[arg(long)]
+    pub story_id: i64,
+
+    /// The checklist task ID to promote
+    #[arg(long)]
+    pub task_id: i64,
+
+    /// Keep the checklist task instead of deleting it after promotion
+    #[arg(long)]
+    pub keep_task: bool,
+}
+
+pub async fn run(
+    args: &PromoteArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let parent = super::get_parent(args.story_id, client).await?;
+
+    let task = client
+        .get_task()
+        .story_public_id(args.story_id)
+        .task_public_id(args.task_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get task: {}", crate::api::format_api_error(&e)))?;
+
+    // Story names are a single line; the full task text goes in the description
+    let name = task
+        .description
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        return Err(format!("Task {} has no description to use as a name", task.id).into());
+    }
+
+    let mut create_args = super::inherited_create_args(&parent, &name);
+    create_args.description = Some(task.description.clone());
+    create_args.owner = task.owner_ids.iter().map(|id| id.to_string()).collect();
+    if task.complete {
+        let done_state = done_state_id(parent.workflow_id, client).await?;
+        create_args.state = Some(done_state.to_string());
+    }
+
+    create::run(&create_args, client, cache_dir, out).await?;
+
+    if args.keep_task {
+        return Ok(());
+    }
+
+    if out.is_dry_run() {
+        return out.dry_run_request::<serde_json::Value>(
+            "DELETE",
+            &format!("/api/v3/stories/{}/tasks/{}", args.story_id, args.task_id),
+            None,
+        );
+    }
+
+    client
+        .delete_task()
+        .story_public_id(args.story_id)
+        .task_public_id(args.task_id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to delete task: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+
+    if !out.is_machine_readable() && !out.is_quiet() {
+        out_println!(
+            out,
+            "Deleted task {} from story {}",
+            args.task_id,
+            args.story_id
+        );
+    }
+    Ok(())
+}
+
+/// First "done" state of the parent story's workflow.
+async fn done_state_id(workflow_id: i64, client: &api::Client) -> Result<i64, Box<dyn Error>> {
+    let workflows = client.list_workflows().send().await.map_err(|e| {
+        format!(
+            "Failed to list workflows: {}",
+            crate::api::format_api_error(&e)
+        )
+    })?;
+
+    workflows
+        .iter()
+        .find(|wf| wf.id == workflow_id)
+        .and_then(|wf| wf.states.iter().find(|s| s.type_ == "done"))
+        .map(|s| s.id)
+        .ok_or_else(|| format!("No done state found in workflow {workflow_id}").into())
+}
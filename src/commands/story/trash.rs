@@ -0,0 +1,93 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::clone::{copy_create_args, copy_extras};
+use super::create;
+
+/// A deleted story as saved by `story delete --trash`. The full `Story`
+/// already embeds its tasks and comments.
+#[derive(Serialize, Deserialize)]
+struct TrashedStory {
+    deleted_at: DateTime<Utc>,
+    story: api::types::Story,
+}
+
+fn trash_path(cache_dir: &Path, story_id: i64) -> PathBuf {
+    cache_dir
+        .join("trash")
+        .join(format!("story_{story_id}.json"))
+}
+
+/// Write a snapshot of `story` to the trash directory before it is deleted.
+pub(super) fn save(story: &api::types::Story, cache_dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let path = trash_path(cache_dir, story.id);
+    let snapshot = TrashedStory {
+        deleted_at: Utc::now(),
+        story: story.clone(),
+    };
+    let json = serde_json::to_string_pretty(&snapshot)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create trash directory: {e}"))?;
+    }
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write trash snapshot: {e}"))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(path)
+}
+
+/// Recreate a deleted story from its trash snapshot, including state, parent,
+/// tasks, comments and links, then remove the snapshot.
+pub async fn restore(
+    id: i64,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let path = trash_path(cache_dir, id);
+    let data = std::fs::read_to_string(&path)
+        .map_err(|_| format!("No trashed story {id} found in {}", path.display()))?;
+    let snapshot: TrashedStory = serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to read trash snapshot {}: {e}", path.display()))?;
+    let source = &snapshot.story;
+
+    let mut create_args = copy_create_args(source);
+    create_args.state = Some(source.workflow_state_id.to_string());
+    create_args.parent_story_id = source.parent_story_id;
+    let extras = copy_extras(source, true, true)?;
+
+    let Some(story) = create::create_story(&create_args, &extras, client, cache_dir, out).await?
+    else {
+        return Ok(());
+    };
+
+    let _ = std::fs::remove_file(&path);
+
+    if out.is_machine_readable() {
+        out_println!(
+            out,
+            "{}",
+            serde_json::json!({"id": story.id, "name": story.name, "restored_from": id})
+        );
+        return Ok(());
+    }
+    if out.is_quiet() {
+        out_println!(out, "{}", story.id);
+        return Ok(());
+    }
+    out_println!(out, "Restored story {id} as {} - {}", story.id, story.name);
+    Ok(())
+}
@@ -4,6 +4,7 @@ use std::path::Path;
 use clap::Args;
 
 use crate::api;
+use crate::commands::iteration::helpers::{IterationRef, resolve_iteration_id};
 use crate::output::OutputConfig;
 
 use super::helpers::{
@@ -55,9 +56,15 @@ pub struct UpdateArgs {
     #[arg(long, value_delimiter = ',')]
     pub labels: Vec<String>,
 
-    /// The iteration ID to assign this story to
+    /// The iteration to assign this story to: an ID, or current, next or
+    /// previous
     #[arg(long)]
-    pub iteration_id: Option<i64>,
+    pub iteration_id: Option<IterationRef>,
+
+    /// Team (group) whose iterations `current`, `next` and `previous` refer
+    /// to, by @mention_name or UUID
+    #[arg(long, requires = "iteration_id")]
+    pub group: Option<String>,
 
     /// Set a custom field value (format: "FieldName=Value", repeatable)
     #[arg(long = "custom-field")]
@@ -107,6 +114,13 @@ pub async fn run(
         None => None,
     };
 
+    let resolved_iteration_id = match args.iteration_id {
+        Some(val) => {
+            Some(resolve_iteration_id(val, args.group.as_deref(), client, cache_dir).await?)
+        }
+        None => None,
+    };
+
     let labels: Vec<api::types::CreateLabelParams> = args
         .labels
         .iter()
@@ -206,7 +220,7 @@ pub async fn run(
                 ),
             );
         }
-        if let Some(iter_id) = args.iteration_id {
+        if let Some(iter_id) = resolved_iteration_id {
             body.insert("iteration_id".into(), serde_json::json!(iter_id));
         }
         if let Some(parent_id) = args.parent_story_id {
@@ -250,7 +264,7 @@ pub async fn run(
             if !labels.is_empty() {
                 b = b.labels(labels);
             }
-            if let Some(iter_id) = args.iteration_id {
+            if let Some(iter_id) = resolved_iteration_id {
                 b = b.iteration_id(Some(iter_id));
             }
             if let Some(parent_id) = args.parent_story_id {
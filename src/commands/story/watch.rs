@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+use clap::Args;
+
+use crate::api;
+use crate::output::OutputConfig;
+
+use super::helpers::{build_workflow_state_id_map, resolve_workflow_state_id};
+use super::history;
+use crate::out_println;
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// The ID of the story
+    #[arg(long)]
+    pub id: i64,
+
+    /// How often to poll for changes (e.g. 30s, 5m, 500ms)
+    #[arg(long, default_value = "30s", value_parser = parse_interval)]
+    pub interval: Duration,
+
+    /// Stop watching once the story reaches this workflow state (name or ID)
+    #[arg(long)]
+    pub until_state: Option<String>,
+
+    /// Shell command to run after each change
+    #[arg(long)]
+    pub exec: Option<String>,
+}
+
+/// Parse an interval such as `30s`, `5m`, `1h` or `500ms`; bare numbers are seconds.
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let invalid = || format!("Invalid interval '{value}': expected e.g. 30s, 5m, 500ms");
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let seconds = |factor: u64| {
+        number
+            .checked_mul(factor)
+            .map(Duration::from_secs)
+            .ok_or_else(invalid)
+    };
+    let duration = match unit {
+        "" | "s" => Duration::from_secs(number),
+        "ms" => Duration::from_millis(number),
+        "m" => seconds(60)?,
+        "h" => seconds(3600)?,
+        _ => {
+            return Err(format!(
+                "Invalid interval unit '{unit}': expected ms, s, m or h"
+            ));
+        }
+    };
+    if duration.is_zero() {
+        return Err("Interval must be greater than zero".to_string());
+    }
+    Ok(duration)
+}
+
+struct Snapshot {
+    story: api::types::Story,
+    history: Vec<api::types::History>,
+}
+
+pub async fn run(
+    args: &WatchArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let until_state = match &args.until_state {
+        Some(val) => Some(resolve_workflow_state_id(val, client, cache_dir).await?),
+        None => None,
+    };
+
+    let mut state_map = build_workflow_state_id_map(client, cache_dir).await?;
+    let member_map = history::build_member_lookup(cache_dir, client).await?;
+    let state_name = |map: &HashMap<i64, String>, id: i64| {
+        map.get(&id).cloned().unwrap_or_else(|| id.to_string())
+    };
+
+    let snapshot = fetch_snapshot(args.id, client).await?;
+    let mut seen_history: HashSet<uuid::Uuid> = snapshot.history.iter().map(|h| h.id).collect();
+    let mut seen_comments: HashSet<i64> = snapshot.story.comments.iter().map(|c| c.id).collect();
+    let mut current_state = snapshot.story.workflow_state_id;
+
+    if out.is_machine_readable() {
+        print_event(
+            out,
+            serde_json::json!({
+                "event": "watching",
+                "id": snapshot.story.id,
+                "name": snapshot.story.name,
+                "state": state_name(&state_map, current_state),
+            }),
+        )?;
+    } else if !out.is_quiet() {
+        out_println!(
+            out,
+            "Watching story {} - {} ({}), polling every {:?}",
+            snapshot.story.id,
+            snapshot.story.name,
+            state_name(&state_map, current_state),
+            args.interval
+        );
+    }
+
+    if until_state == Some(current_state) {
+        if out.is_machine_readable() {
+            print_event(
+                out,
+                serde_json::json!({
+                    "event": "reached",
+                    "id": args.id,
+                    "state": state_name(&state_map, current_state),
+                }),
+            )?;
+        } else if !out.is_quiet() {
+            out_println!(
+                out,
+                "Story {} is already in {}",
+                args.id,
+                state_name(&state_map, current_state)
+            );
+        }
+        return Ok(());
+    }
+
+    loop {
+        tokio::time::sleep(args.interval).await;
+
+        // A failed poll is reported and retried on the next tick
+        let snapshot = match fetch_snapshot(args.id, client).await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                if out.is_machine_readable() {
+                    print_event(
+                        out,
+                        serde_json::json!({ "event": "error", "message": e.to_string() }),
+                    )?;
+                } else if !out.is_quiet() {
+                    out_println!(out, "{e}; retrying in {:?}", args.interval);
+                }
+                continue;
+            }
+        };
+        let mut changed = false;
+
+        let mut new_entries: Vec<&api::types::History> = snapshot
+            .history
+            .iter()
+            .filter(|h| !seen_history.contains(&h.id))
+            .collect();
+        new_entries.sort_by(|a, b| a.changed_at.cmp(&b.changed_at));
+        if !new_entries.is_empty() {
+            changed = true;
+            for (id, name) in history::reference_state_names(new_entries.iter().copied()) {
+                state_map.entry(id).or_insert(name);
+            }
+            let label_map = history::reference_label_names(new_entries.iter().copied());
+            for entry in &new_entries {
+                if out.is_machine_readable() {
+                    for action in &entry.actions {
+                        if let Some(change) =
+                            history::format_action(action, &state_map, &label_map, &member_map)
+                        {
+                            print_event(
+                                out,
+                                serde_json::json!({
+                                    "event": "history",
+                                    "changed_at": entry.changed_at,
+                                    "member": history::actor(entry, &member_map),
+                                    "change": change,
+                                }),
+                            )?;
+                        }
+                    }
+                } else if !out.is_quiet() {
+                    history::print_entry(out, entry, &state_map, &label_map, &member_map)?;
+                }
+                seen_history.insert(entry.id);
+            }
+        }
+
+        for comment in &snapshot.story.comments {
+            if !seen_comments.insert(comment.id) || comment.deleted {
+                continue;
+            }
+            changed = true;
+            let author = comment
+                .author_id
+                .as_ref()
+                .and_then(|id| member_map.get(&id.to_string()));
+            if out.is_machine_readable() {
+                print_event(
+                    out,
+                    serde_json::json!({
+                        "event": "comment",
+                        "id": comment.id,
+                        "created_at": comment.created_at,
+                        "author": author,
+                        "text": comment.text,
+                    }),
+                )?;
+            } else if !out.is_quiet() {
+                out_println!(
+                    out,
+                    "[{}] {} commented: {}",
+                    comment.created_at.to_rfc3339(),
+                    author
+                        .map(|m| format!("@{m}"))
+                        .unwrap_or_else(|| "(unknown)".to_string()),
+                    comment.text.as_deref().unwrap_or("")
+                );
+            }
+        }
+
+        let new_state = snapshot.story.workflow_state_id;
+        if new_state != current_state {
+            changed = true;
+            let from = state_name(&state_map, current_state);
+            let to = state_name(&state_map, new_state);
+            if out.is_machine_readable() {
+                print_event(
+                    out,
+                    serde_json::json!({ "event": "state", "from": from, "to": to }),
+                )?;
+            } else if out.is_quiet() {
+                out_println!(out, "{to}");
+            } else if !new_entries.iter().any(|e| history::changes_state(e)) {
+                // Usually the history entry above already showed the change
+                out_println!(out, "State: {from} -> {to}");
+            }
+            current_state = new_state;
+        }
+
+        if changed && let Some(cmd) = &args.exec {
+            run_exec(cmd, args.id, &state_name(&state_map, current_state), out).await?;
+        }
+
+        if until_state == Some(current_state) {
+            if out.is_machine_readable() {
+                print_event(
+                    out,
+                    serde_json::json!({
+                        "event": "reached",
+                        "id": args.id,
+                        "state": state_name(&state_map, current_state),
+                    }),
+                )?;
+            } else if !out.is_quiet() {
+                out_println!(
+                    out,
+                    "Story {} reached {}",
+                    args.id,
+                    state_name(&state_map, current_state)
+                );
+            }
+            return Ok(());
+        }
+    }
+}
+
+/// Print one event as a single line of JSON.
+fn print_event(out: &OutputConfig, event: serde_json::Value) -> Result<(), Box<dyn Error>> {
+    out_println!(out, "{}", serde_json::to_string(&event)?);
+    Ok(())
+}
+
+async fn fetch_snapshot(id: i64, client: &api::Client) -> Result<Snapshot, Box<dyn Error>> {
+    let story = client
+        .get_story()
+        .story_public_id(id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get story: {}", crate::api::format_api_error(&e)))?
+        .into_inner();
+
+    let history = client
+        .story_history()
+        .story_public_id(id)
+        .send()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to get story history: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?
+        .into_inner();
+
+    Ok(Snapshot { story, history })
+}
+
+/// Run the `--exec` command through the shell with the story ID and state
+/// exported. The command shares the terminal, so its output is not captured.
+async fn run_exec(
+    cmd: &str,
+    story_id: i64,
+    state: &str,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    #[cfg(unix)]
+    let mut command = {
+        let mut c = tokio::process::Command::new("sh");
+        c.arg("-c").arg(cmd);
+        c
+    };
+    #[cfg(not(unix))]
+    let mut command = {
+        let mut c = tokio::process::Command::new("cmd");
+        c.arg("/C").arg(cmd);
+        c
+    };
+
+    let status = command
+        .env("SHORTCUT_STORY_ID", story_id.to_string())
+        .env("SHORTCUT_STORY_STATE", state)
+        .status()
+        .await
+        .map_err(|e| format!("Failed to run --exec command: {e}"))?;
+
+    if !status.success() {
+        if out.is_machine_readable() {
+            print_event(
+                out,
+                serde_json::json!({ "event": "exec_failed", "status": status.code() }),
+            )?;
+        } else if !out.is_quiet() {
+            out_println!(out, "--exec command exited with {status}");
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use crate::commands::iteration::IterationRef;
 use crate::interactive::{IdChoice, MemberChoice, Prompter, UuidChoice};
 
 use super::create::CreateArgs;
@@ -80,7 +81,9 @@ pub fn run_wizard(
     let iteration_id = if base.iteration_id.is_some() {
         base.iteration_id
     } else {
-        prompter.prompt_optional_select_id("Iteration", choices.iteration_choices)?
+        prompter
+            .prompt_optional_select_id("Iteration", choices.iteration_choices)?
+            .map(IterationRef::Id)
     };
 
     let custom_fields = if !base.custom_fields.is_empty() {
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+use crate::stl::plan::Plan;
+use crate::stl::reconciler::SyncAction;
+use crate::stl::{executor, parser, state, validator, vars};
+
+use super::sync_stl;
+
+#[derive(Args)]
+pub struct ApplyArgs {
+    /// Path to a plan file written by `template plan -o`
+    pub plan: String,
+
+    /// Skip the interactive confirmation prompt
+    #[arg(long)]
+    pub confirm: bool,
+}
+
+pub async fn run(
+    args: &ApplyArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    let plan = Plan::load(&args.plan)?;
+
+    // Refuse to run a plan made from a different template or state
+    let mut template = parser::parse_from_path(&plan.template.path)?;
+    plan.check_unchanged(&template)?;
+
+    // Use the vars the plan was made with
+    plan.apply_vars(&mut template)?;
+    vars::resolve(&mut template, &[], None)?;
+
+    let mut errors = validator::validate(&template);
+    errors.extend(validator::validate_for_sync(&template));
+    if !errors.is_empty() {
+        out_println!(out, "Validation errors:");
+        for err in &errors {
+            out_println!(out, "  - {err}");
+        }
+        return Err(format!("{} validation error(s) found", errors.len()).into());
+    }
+
+    let actions: Vec<SyncAction> = plan.actions.into_iter().map(|a| a.action).collect();
+    if actions.is_empty() {
+        out_println!(out, "Nothing to do — the plan has no actions.");
+        return Ok(());
+    }
+
+    // Execute
+    let state_path = PathBuf::from(&plan.state.path);
+    let mut sync_state = state::load_state(&state_path)?.unwrap_or_else(state::SyncState::new);
+    let result = executor::execute_sync(
+        &mut template,
+        &actions,
+        &mut sync_state,
+        &state_path,
+        client,
+        cache_dir,
+        out,
+        plan.prune,
+        args.confirm,
+    )
+    .await?;
+
+    sync_stl::report(&result, out)
+}
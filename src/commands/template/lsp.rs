@@ -0,0 +1,8 @@
+use std::error::Error;
+
+use crate::stl::lsp;
+
+/// Serve the STL language server on stdin/stdout until the editor exits.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    lsp::serve(std::io::stdin().lock(), std::io::stdout())
+}
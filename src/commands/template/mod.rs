@@ -1,8 +1,11 @@
+pub mod apply_stl;
 mod create;
 mod delete;
 mod get;
 pub mod init;
 mod list;
+pub mod lsp;
+pub mod plan_stl;
 pub mod run_stl;
 pub mod sync_stl;
 mod update;
@@ -56,10 +59,16 @@ pub enum TemplateAction {
     Run(Box<run_stl::RunArgs>),
     /// Declaratively sync a template file against existing resources
     Sync(Box<sync_stl::SyncArgs>),
+    /// Preview a sync and save it as a plan file for review
+    Plan(Box<plan_stl::PlanArgs>),
+    /// Apply a saved plan file, if its template and state are unchanged
+    Apply(Box<apply_stl::ApplyArgs>),
     /// Validate a template file without executing
     Validate(validate_stl::ValidateArgs),
     /// Initialize STL agent instructions in CLAUDE.md
     Init(init::InitArgs),
+    /// Run a language server for template files over stdio
+    Lsp,
 }
 
 pub async fn run(
@@ -81,7 +90,12 @@ pub async fn run(
         TemplateAction::Delete { id, confirm } => delete::run(id, *confirm, client, out).await,
         TemplateAction::Run(run_args) => run_stl::run(run_args, client, &cache_dir, out).await,
         TemplateAction::Sync(sync_args) => sync_stl::run(sync_args, client, &cache_dir, out).await,
+        TemplateAction::Plan(plan_args) => plan_stl::run(plan_args, client, &cache_dir, out).await,
+        TemplateAction::Apply(apply_args) => {
+            apply_stl::run(apply_args, client, &cache_dir, out).await
+        }
         TemplateAction::Validate(validate_args) => validate_stl::run(validate_args, out).await,
         TemplateAction::Init(init_args) => init::run(init_args, out).await,
+        TemplateAction::Lsp => lsp::run(),
     }
 }
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api;
+use crate::interactive::{Prompter, TerminalPrompter};
+use crate::out_println;
+use crate::output::OutputConfig;
+use crate::stl::plan::{self, FieldChange, Plan, PlannedAction};
+use crate::stl::reconciler::SyncAction;
+use crate::stl::types::Template;
+use crate::stl::{parser, reconciler, state, validator, vars};
+
+use super::sync_stl::parse_var_arg;
+
+#[derive(Args)]
+pub struct PlanArgs {
+    /// Path to the .shortcut.yml or .shortcut.json template file
+    pub file: String,
+
+    /// Save the plan to this file for `template apply`
+    #[arg(short = 'o', long = "out")]
+    pub plan_file: Option<String>,
+
+    /// Override state file location (default: file.state.json)
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Plan deletion of resources that exist in state but were removed from the template
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Pass/override a variable (repeatable, format: key=value)
+    #[arg(long = "var", value_parser = parse_var_arg)]
+    pub vars: Vec<(String, String)>,
+}
+
+pub async fn run(
+    args: &PlanArgs,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+) -> Result<(), Box<dyn Error>> {
+    if args.file == "-" {
+        return Err("template plan needs a template file; stdin cannot be planned".into());
+    }
+
+    // Parse
+    let mut template = parser::parse_from_path(&args.file)?;
+
+    // Apply CLI --var overrides, prompting for missing required vars on a TTY
+    let prompter = atty::is(atty::Stream::Stdin).then_some(&TerminalPrompter as &dyn Prompter);
+    vars::resolve(&mut template, &args.vars, prompter)?;
+
+    // Validate (standard + sync-specific)
+    let mut errors = validator::validate(&template);
+    errors.extend(validator::validate_for_sync(&template));
+    if !errors.is_empty() {
+        out_println!(out, "Validation errors:");
+        for err in &errors {
+            out_println!(out, "  - {err}");
+        }
+        return Err(format!("{} validation error(s) found", errors.len()).into());
+    }
+
+    // Load state
+    let state_path_str = args.state.clone().unwrap_or_else(|| {
+        state::default_state_path(&args.file)
+            .to_string_lossy()
+            .to_string()
+    });
+    let state_path = std::path::PathBuf::from(&state_path_str);
+    let existing_state = state::load_state(&state_path)?;
+
+    // Reconcile and diff against live entities
+    let actions = reconciler::reconcile(&template.operations, &existing_state)
+        .map_err(|e| -> Box<dyn Error> { e.into() })?;
+    let planned = plan::plan_actions(
+        &mut template,
+        existing_state.as_ref(),
+        &actions,
+        client,
+        cache_dir,
+    )
+    .await?;
+    let plan = Plan::new(&args.file, &template, &state_path, args.prune, planned)?;
+
+    let json = serde_json::to_string_pretty(&plan)?;
+    if let Some(path) = &args.plan_file {
+        std::fs::write(path, format!("{json}\n"))
+            .map_err(|e| format!("Failed to write plan '{path}': {e}"))?;
+    }
+
+    if out.is_machine_readable() {
+        out_println!(out, "{json}");
+        return Ok(());
+    }
+
+    print_plan(&plan, &template, out)?;
+    match &args.plan_file {
+        Some(path) => out_println!(
+            out,
+            "\nSaved plan to {path}. Apply it with `shortcut template apply {path}`."
+        ),
+        None if !plan.actions.is_empty() => out_println!(
+            out,
+            "\nPass -o <file> to save this plan for `template apply`."
+        ),
+        None => {}
+    }
+    Ok(())
+}
+
+/// Print each planned action with its field changes, then a summary.
+fn print_plan(plan: &Plan, template: &Template, out: &OutputConfig) -> Result<(), Box<dyn Error>> {
+    if plan.actions.is_empty() {
+        out_println!(out, "Nothing to do — template and state are in sync.");
+        return Ok(());
+    }
+
+    for planned in &plan.actions {
+        out_println!(out, "{}", describe(planned, template, plan.prune));
+        let shows_changes = matches!(
+            planned.action,
+            SyncAction::Update { .. } | SyncAction::UpdateEntry { .. }
+        );
+        if planned.changes.is_empty() && shows_changes {
+            out_println!(out, "      (no changes)");
+        }
+        for change in &planned.changes {
+            out_println!(out, "      {}", describe_change(change));
+        }
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for planned in &plan.actions {
+        *counts.entry(planned.action.summary_verb()).or_default() += 1;
+    }
+    let counts: Vec<String> = counts
+        .into_iter()
+        .map(|(verb, count)| format!("{count} {verb}"))
+        .collect();
+    out_println!(
+        out,
+        "\nPlan: {} action(s) ({}).",
+        plan.actions.len(),
+        counts.join(", ")
+    );
+    Ok(())
+}
+
+/// One line describing what an action will do.
+fn describe(planned: &PlannedAction, template: &Template, prune: bool) -> String {
+    let op = |index: &usize| &template.operations[*index];
+    match &planned.action {
+        SyncAction::Create { op_index, alias } => {
+            format!("+ create {} (alias: {alias})", op(op_index).entity)
+        }
+        SyncAction::CreateEntry {
+            op_index,
+            alias,
+            key,
+        } => format!(
+            "+ create {} entry '{key}' (alias: {alias})",
+            op(op_index).entity
+        ),
+        SyncAction::Update {
+            op_index,
+            alias,
+            existing_id,
+        } => format!(
+            "~ update {} {} (alias: {alias})",
+            op(op_index).entity,
+            display_id(existing_id)
+        ),
+        SyncAction::UpdateEntry {
+            op_index,
+            alias,
+            key,
+            existing_id,
+        } => format!(
+            "~ update {} {} entry '{key}' (alias: {alias})",
+            op(op_index).entity,
+            display_id(existing_id)
+        ),
+        SyncAction::OrphanEntry {
+            alias,
+            key,
+            entity,
+            id,
+        } => format!(
+            "{} orphaned {entity} {} entry '{key}' (alias: {alias}){}",
+            if prune { "-" } else { "!" },
+            display_id(id),
+            orphan_note(prune)
+        ),
+        SyncAction::Orphan { alias, entity, ids } => {
+            let ids: Vec<String> = ids.iter().map(display_id).collect();
+            format!(
+                "{} orphaned {entity} '{alias}' (ids: {}){}",
+                if prune { "-" } else { "!" },
+                ids.join(", "),
+                orphan_note(prune)
+            )
+        }
+        SyncAction::Skip { op_index, reason } => {
+            format!("  skip operation {} ({reason})", op_index + 1)
+        }
+        SyncAction::RunSideEffect { op_index } | SyncAction::Passthrough { op_index } => {
+            let op = op(op_index);
+            format!("> {} {} (operation {})", op.action, op.entity, op_index + 1)
+        }
+    }
+}
+
+fn orphan_note(prune: bool) -> &'static str {
+    if prune {
+        " — will be deleted"
+    } else {
+        " — left in place; plan with --prune to delete"
+    }
+}
+
+fn describe_change(change: &FieldChange) -> String {
+    if change.from.is_null() {
+        format!("{}: {}", change.field, change.to)
+    } else {
+        format!("{}: {} -> {}", change.field, change.from, change.to)
+    }
+}
+
+fn display_id(id: &serde_json::Value) -> String {
+    match id {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
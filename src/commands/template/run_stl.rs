@@ -4,9 +4,10 @@ use std::path::Path;
 use clap::Args;
 
 use crate::api;
+use crate::interactive::{Prompter, TerminalPrompter};
 use crate::out_println;
 use crate::output::OutputConfig;
-use crate::stl::{executor, parser, validator};
+use crate::stl::{executor, parser, validator, vars};
 
 #[derive(Args)]
 pub struct RunArgs {
@@ -20,6 +21,10 @@ pub struct RunArgs {
     /// Pass/override a variable (repeatable, format: key=value)
     #[arg(long = "var", value_parser = parse_var_arg)]
     pub vars: Vec<(String, String)>,
+
+    /// Run up to N independent operations at once
+    #[arg(long, default_value_t = 1, value_parser = parse_concurrency)]
+    pub concurrency: usize,
 }
 
 fn parse_var_arg(arg: &str) -> Result<(String, String), String> {
@@ -29,6 +34,15 @@ fn parse_var_arg(arg: &str) -> Result<(String, String), String> {
     Ok((key.to_string(), value.to_string()))
 }
 
+fn parse_concurrency(arg: &str) -> Result<usize, String> {
+    match arg.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!(
+            "Invalid --concurrency '{arg}': expected a positive number"
+        )),
+    }
+}
+
 pub async fn run(
     args: &RunArgs,
     client: &api::Client,
@@ -38,18 +52,9 @@ pub async fn run(
     // Parse
     let mut template = parser::parse_from_path(&args.file)?;
 
-    // Apply CLI --var overrides
-    if !args.vars.is_empty() {
-        let vars = template.vars.get_or_insert_with(Default::default);
-        for (key, value) in &args.vars {
-            if !vars.contains_key(key) {
-                return Err(
-                    format!("--var '{key}' is not declared in the template's vars block").into(),
-                );
-            }
-            vars.insert(key.clone(), serde_yaml::Value::String(value.clone()));
-        }
-    }
+    // Apply CLI --var overrides, prompting for missing required vars on a TTY
+    let prompter = atty::is(atty::Stream::Stdin).then_some(&TerminalPrompter as &dyn Prompter);
+    vars::resolve(&mut template, &args.vars, prompter)?;
 
     // Validate
     let errors = validator::validate(&template);
@@ -62,7 +67,15 @@ pub async fn run(
     }
 
     // Execute
-    let result = executor::execute(&mut template, client, cache_dir, out, args.confirm).await?;
+    let result = executor::execute(
+        &mut template,
+        client,
+        cache_dir,
+        out,
+        args.confirm,
+        args.concurrency,
+    )
+    .await?;
 
     // Print summary
     if out.is_machine_readable() {
@@ -70,43 +83,88 @@ pub async fn run(
     } else if !out.is_dry_run() {
         let summary = &result.summary;
         if summary.failed == 0 {
+            let skipped = if summary.skipped > 0 {
+                format!(" ({} skipped)", summary.skipped)
+            } else {
+                String::new()
+            };
             out_println!(
                 out,
-                "\nExecuted {}/{} operations successfully.",
+                "\nExecuted {}/{} operations successfully{}.",
                 summary.succeeded,
-                summary.total
+                summary.total,
+                skipped
             );
         } else {
+            let skipped = if summary.skipped > 0 {
+                format!(", {} skipped", summary.skipped)
+            } else {
+                String::new()
+            };
             out_println!(
                 out,
-                "\nExecuted {}/{} operations ({} failed).",
+                "\nExecuted {}/{} operations ({} failed{}).",
                 summary.succeeded,
                 summary.total,
-                summary.failed
+                summary.failed,
+                skipped
             );
-            // List completed operations
-            let completed: Vec<&_> = result
-                .operations
-                .iter()
-                .filter(|r| r.status == "success")
-                .collect();
-            if !completed.is_empty() {
-                out_println!(out, "Completed:");
-                for op in completed {
-                    let id_str = op
-                        .result
-                        .as_ref()
-                        .and_then(|r| r.get("id"))
-                        .map(|v| format!(" {}", v))
-                        .unwrap_or_default();
-                    out_println!(
-                        out,
-                        "  operation {} ({} {}{})",
-                        op.index + 1,
-                        op.action,
-                        op.entity,
-                        id_str
-                    );
+            if let Some(rollback) = &result.rollback {
+                let undone = rollback
+                    .iter()
+                    .filter(|r| r.status == "rolled_back")
+                    .count();
+                out_println!(
+                    out,
+                    "Rolled back {}/{} completed operations.",
+                    undone,
+                    rollback.len()
+                );
+                let remaining: Vec<&_> = rollback
+                    .iter()
+                    .filter(|r| r.status != "rolled_back")
+                    .collect();
+                if !remaining.is_empty() {
+                    out_println!(out, "Not rolled back:");
+                    for r in remaining {
+                        let id_str = r.id.as_ref().map(|v| format!(" {v}")).unwrap_or_default();
+                        out_println!(
+                            out,
+                            "  operation {} ({} {}{}): {} — {}",
+                            r.index + 1,
+                            r.action,
+                            r.entity,
+                            id_str,
+                            r.status,
+                            r.detail
+                        );
+                    }
+                }
+            } else {
+                // List completed operations
+                let completed: Vec<&_> = result
+                    .operations
+                    .iter()
+                    .filter(|r| r.status == "success")
+                    .collect();
+                if !completed.is_empty() {
+                    out_println!(out, "Completed:");
+                    for op in completed {
+                        let id_str = op
+                            .result
+                            .as_ref()
+                            .and_then(|r| r.get("id"))
+                            .map(|v| format!(" {}", v))
+                            .unwrap_or_default();
+                        out_println!(
+                            out,
+                            "  operation {} ({} {}{})",
+                            op.index + 1,
+                            op.action,
+                            op.entity,
+                            id_str
+                        );
+                    }
                 }
             }
         }
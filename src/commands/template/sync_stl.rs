@@ -4,9 +4,11 @@ use std::path::Path;
 use clap::Args;
 
 use crate::api;
+use crate::interactive::{Prompter, TerminalPrompter};
 use crate::out_println;
 use crate::output::OutputConfig;
-use crate::stl::{executor, parser, reconciler, state, validator};
+use crate::stl::types::ExecutionResult;
+use crate::stl::{executor, parser, reconciler, state, validator, vars};
 
 #[derive(Args)]
 pub struct SyncArgs {
@@ -30,7 +32,7 @@ pub struct SyncArgs {
     pub vars: Vec<(String, String)>,
 }
 
-fn parse_var_arg(arg: &str) -> Result<(String, String), String> {
+pub(super) fn parse_var_arg(arg: &str) -> Result<(String, String), String> {
     let (key, value) = arg
         .split_once('=')
         .ok_or_else(|| format!("Invalid --var format '{arg}': expected key=value"))?;
@@ -46,18 +48,9 @@ pub async fn run(
     // Parse
     let mut template = parser::parse_from_path(&args.file)?;
 
-    // Apply CLI --var overrides
-    if !args.vars.is_empty() {
-        let vars = template.vars.get_or_insert_with(Default::default);
-        for (key, value) in &args.vars {
-            if !vars.contains_key(key) {
-                return Err(
-                    format!("--var '{key}' is not declared in the template's vars block").into(),
-                );
-            }
-            vars.insert(key.clone(), serde_yaml::Value::String(value.clone()));
-        }
-    }
+    // Apply CLI --var overrides, prompting for missing required vars on a TTY
+    let prompter = atty::is(atty::Stream::Stdin).then_some(&TerminalPrompter as &dyn Prompter);
+    vars::resolve(&mut template, &args.vars, prompter)?;
 
     // Validate (standard + sync-specific)
     let mut errors = validator::validate(&template);
@@ -103,9 +96,13 @@ pub async fn run(
     )
     .await?;
 
-    // Print summary
+    report(&result, out)
+}
+
+/// Print the outcome of a sync, failing if any action failed.
+pub(super) fn report(result: &ExecutionResult, out: &OutputConfig) -> Result<(), Box<dyn Error>> {
     if out.is_machine_readable() {
-        out_println!(out, "{}", serde_json::to_string_pretty(&result)?);
+        out_println!(out, "{}", serde_json::to_string_pretty(result)?);
     } else if !out.is_dry_run() {
         let summary = &result.summary;
         if summary.failed == 0 {
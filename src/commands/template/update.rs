@@ -7,6 +7,7 @@ use crate::api;
 use crate::out_println;
 use crate::output::OutputConfig;
 
+use crate::commands::iteration::helpers::{IterationRef, resolve_iteration_id};
 use crate::commands::story::helpers::{
     resolve_custom_field_args, resolve_owners, resolve_workflow_state_id,
 };
@@ -58,9 +59,14 @@ pub struct UpdateArgs {
     #[arg(long, value_delimiter = ',')]
     pub labels: Vec<String>,
 
-    /// The default iteration ID
+    /// The default iteration (ID or current, next, previous)
     #[arg(long)]
-    pub iteration_id: Option<i64>,
+    pub iteration_id: Option<IterationRef>,
+
+    /// Team (group) whose iterations `current`, `next` and `previous` refer
+    /// to, by @mention_name or UUID
+    #[arg(long, requires = "iteration_id")]
+    pub group: Option<String>,
 
     /// Set a default custom field value (format: "FieldName=Value", repeatable)
     #[arg(long = "custom-field")]
@@ -114,6 +120,13 @@ pub async fn run(
             None => None,
         };
 
+        let resolved_iteration_id = match args.iteration_id {
+            Some(val) => {
+                Some(resolve_iteration_id(val, args.group.as_deref(), client, cache_dir).await?)
+            }
+            None => None,
+        };
+
         let labels: Vec<api::types::CreateLabelParams> = args
             .labels
             .iter()
@@ -156,7 +169,7 @@ pub async fn run(
         if !labels.is_empty() {
             contents.labels = labels;
         }
-        if let Some(iter_id) = args.iteration_id {
+        if let Some(iter_id) = resolved_iteration_id {
             contents.iteration_id = Some(iter_id);
         }
         if !custom_field_params.is_empty() {
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use clap::Args;
 
 use crate::api;
+use crate::commands::iteration::helpers::{IterationRef, resolve_iteration_id};
 use crate::out_println;
 use crate::output::OutputConfig;
 
@@ -54,9 +55,14 @@ pub struct UseArgs {
     #[arg(long, value_delimiter = ',')]
     pub labels: Vec<String>,
 
-    /// Override the iteration ID
+    /// Override the iteration (ID or current, next, previous)
     #[arg(long)]
-    pub iteration_id: Option<i64>,
+    pub iteration_id: Option<IterationRef>,
+
+    /// Team (group) whose iterations `current`, `next` and `previous` refer
+    /// to, by @mention_name or UUID
+    #[arg(long, requires = "iteration_id")]
+    pub group: Option<String>,
 
     /// Set a custom field value (format: "FieldName=Value", repeatable)
     #[arg(long = "custom-field")]
@@ -143,7 +149,12 @@ pub async fn run(
     let estimate = args.estimate.or(sc.estimate);
 
     // Iteration: CLI flag wins
-    let iteration_id = args.iteration_id.or(sc.iteration_id);
+    let iteration_id = match args.iteration_id {
+        Some(val) => {
+            Some(resolve_iteration_id(val, args.group.as_deref(), client, cache_dir).await?)
+        }
+        None => sc.iteration_id,
+    };
 
     // Labels: CLI flag wins (non-empty means override), else convert template labels
     let labels: Vec<api::types::CreateLabelParams> = if !args.labels.is_empty() {
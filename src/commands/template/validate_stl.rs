@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::error::Error;
 
 use clap::Args;
 
 use crate::out_println;
 use crate::output::OutputConfig;
+use crate::stl::validator::ValidationError;
 use crate::stl::{parser, validator};
 
 #[derive(Args)]
@@ -22,9 +24,42 @@ pub async fn run(args: &ValidateArgs, out: &OutputConfig) -> Result<(), Box<dyn
         Ok(())
     } else {
         out_println!(out, "Validation errors:");
+        let mut sources = HashMap::new();
         for err in &errors {
             out_println!(out, "  - {err}");
+            for line in snippet(err, &mut sources) {
+                out_println!(out, "    {line}");
+            }
         }
         Err(format!("{} validation error(s) found", errors.len()).into())
     }
 }
+
+/// The source line an error points at, with a caret under the column.
+///
+/// Files are read once and cached in `sources`; errors without a position
+/// (or from stdin) get no snippet.
+fn snippet(err: &ValidationError, sources: &mut HashMap<String, Option<String>>) -> Vec<String> {
+    let (Some(origin), Some(position)) = (&err.origin, err.position) else {
+        return Vec::new();
+    };
+    let content = sources
+        .entry(origin.file.clone())
+        .or_insert_with(|| std::fs::read_to_string(&origin.file).ok());
+    let Some(text) = content
+        .as_deref()
+        .and_then(|c| c.lines().nth(position.line - 1))
+    else {
+        return Vec::new();
+    };
+    let number = position.line.to_string();
+    let gutter = " ".repeat(number.len());
+    vec![
+        format!("{gutter} |"),
+        format!("{number} | {text}"),
+        format!(
+            "{gutter} | {}^",
+            " ".repeat(position.column.saturating_sub(1))
+        ),
+    ]
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use shortcut_cli::cli::{Cli, Command};
+use shortcut_cli::commands::template::TemplateAction;
 use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
 use shortcut_cli::{api, auth, commands, project};
 
@@ -57,6 +58,10 @@ async fn main() {
             }
             Err(e) => Err(e.into()),
         },
+        // Editors start the language server anywhere; it needs no project or token
+        Command::Template(args) if matches!(args.action, TemplateAction::Lsp) => {
+            commands::template::lsp::run()
+        }
         command => match project::discover() {
             Ok(root) => {
                 let store = auth::FileTokenStore {
@@ -100,6 +105,10 @@ async fn main() {
                         }
                         Err(e) => Err(e.into()),
                     },
+                    Command::KeyResult(args) => match api::authenticated_client(&store) {
+                        Ok(client) => commands::key_result::run(&args, &client, &output).await,
+                        Err(e) => Err(e.into()),
+                    },
                     Command::Label(args) => match api::authenticated_client(&store) {
                         Ok(client) => {
                             commands::label::run(&args, &client, root.cache_dir(), &output).await
@@ -112,6 +121,10 @@ async fn main() {
                         }
                         Err(e) => Err(e.into()),
                     },
+                    Command::Milestone(args) => match api::authenticated_client(&store) {
+                        Ok(client) => commands::milestone::run(&args, &client, &output).await,
+                        Err(e) => Err(e.into()),
+                    },
                     Command::Objective(args) => match api::authenticated_client(&store) {
                         Ok(client) => commands::objective::run(&args, &client, &output).await,
                         Err(e) => Err(e.into()),
@@ -120,6 +133,12 @@ async fn main() {
                         Ok(client) => commands::project::run(&args, &client, &output).await,
                         Err(e) => Err(e.into()),
                     },
+                    Command::Report(args) => match api::authenticated_client(&store) {
+                        Ok(client) => {
+                            commands::report::run(&args, &client, root.cache_dir(), &output).await
+                        }
+                        Err(e) => Err(e.into()),
+                    },
                     Command::Search(args) => match api::authenticated_client(&store) {
                         Ok(client) => commands::search::run(&args, &client, &output).await,
                         Err(e) => Err(e.into()),
@@ -61,6 +61,19 @@ impl OutputConfig {
         (config, buf)
     }
 
+    /// Create an OutputConfig with the same settings that writes to a new
+    /// buffer, so output produced concurrently can be replayed in order.
+    pub fn buffered(&self) -> (Self, SharedBuffer) {
+        let (config, buf) = Self::with_buffer(self.mode.clone(), self.color_mode.clone());
+        (config.with_dry_run(self.dry_run), buf)
+    }
+
+    /// Write bytes collected from a buffered OutputConfig as they are.
+    pub fn write_raw(&self, bytes: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        self.writer.lock().unwrap().write_all(bytes)?;
+        Ok(())
+    }
+
     /// Enable or disable dry-run mode.
     pub fn with_dry_run(mut self, enabled: bool) -> Self {
         self.dry_run = enabled;
@@ -259,10 +272,7 @@ pub fn format_template<T: serde::Serialize>(
 
     // Find all {field} placeholders
     let mut start = 0;
-    loop {
-        let Some(open) = result[start..].find('{') else {
-            break;
-        };
+    while let Some(open) = result[start..].find('{') {
         let open = start + open;
         let Some(close) = result[open..].find('}') else {
             break;
@@ -296,6 +306,142 @@ fn resolve_field(value: &serde_json::Value, path: &str) -> serde_json::Value {
     current.clone()
 }
 
+// ── CSV ──────────────────────────────────────────────────────────────
+
+/// Join fields into a single CSV line, quoting fields that need it.
+pub fn csv_row(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            if f.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// ── ASCII chart ──────────────────────────────────────────────────────
+
+/// One line on an [`ascii_chart`], drawn with `marker`.
+pub struct ChartSeries<'a> {
+    pub name: &'a str,
+    pub marker: char,
+    pub values: &'a [f64],
+}
+
+const CHART_MAX_COLUMNS: usize = 60;
+
+/// Render series that share an x-axis as a fixed-height ASCII chart, with the
+/// y-axis scaled from zero to the largest value and `x_labels` printed under
+/// the first and last columns. Later series are drawn over earlier ones.
+/// Long series are sampled down to at most 60 columns.
+pub fn ascii_chart(series: &[ChartSeries], height: usize, x_labels: (&str, &str)) -> String {
+    let len = series.iter().map(|s| s.values.len()).max().unwrap_or(0);
+    if len == 0 || height < 2 {
+        return String::new();
+    }
+
+    let columns = len.min(CHART_MAX_COLUMNS);
+    let sample = |col: usize| {
+        if columns == 1 {
+            0
+        } else {
+            col * (len - 1) / (columns - 1)
+        }
+    };
+
+    let max = series
+        .iter()
+        .flat_map(|s| s.values.iter().copied())
+        .fold(0.0_f64, f64::max);
+    let max = if max > 0.0 { max } else { 1.0 };
+
+    let mut grid = vec![vec![' '; columns]; height];
+    for s in series {
+        for (col, cell) in (0..columns).map(|c| (c, sample(c))) {
+            let Some(&value) = s.values.get(cell) else {
+                continue;
+            };
+            let row = ((value.max(0.0) / max) * (height - 1) as f64).round() as usize;
+            grid[height - 1 - row][col] = s.marker;
+        }
+    }
+
+    let fmt_value = |v: f64| {
+        if v.fract() == 0.0 {
+            format!("{v:.0}")
+        } else {
+            format!("{v:.1}")
+        }
+    };
+    let top = fmt_value(max);
+    let width = top.len().max(1);
+
+    let mut out = String::new();
+    for (i, row) in grid.iter().enumerate() {
+        let label = if i == 0 {
+            top.clone()
+        } else if i == height - 1 {
+            "0".to_string()
+        } else {
+            String::new()
+        };
+        let axis = if label.is_empty() { '│' } else { '┤' };
+        let line: String = row.iter().collect();
+        out.push_str(&format!("{label:>width$} {axis}{}\n", line.trim_end()));
+    }
+    out.push_str(&format!("{:>width$} └{}\n", "", "─".repeat(columns)));
+
+    let (first, last) = x_labels;
+    let gap = (columns + 1)
+        .saturating_sub(first.len() + last.len())
+        .max(1);
+    out.push_str(&format!(
+        "{:>width$}  {first}{}{last}\n",
+        "",
+        " ".repeat(gap)
+    ));
+
+    let legend: Vec<String> = series
+        .iter()
+        .map(|s| format!("{} {}", s.marker, s.name))
+        .collect();
+    out.push_str(&format!("{:>width$}  {}\n", "", legend.join("   ")));
+    out
+}
+
+/// Render `percent` (clamped to 0–100) as a fixed-width bar.
+pub fn progress_bar(percent: i64, width: usize) -> String {
+    let filled = (percent.clamp(0, 100) as usize * width + 50) / 100;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// `part` as a whole-number percentage of `whole`; 0 when `whole` is not positive.
+pub fn percent(part: f64, whole: f64) -> i64 {
+    if whole <= 0.0 {
+        0
+    } else {
+        (part * 100.0 / whole).round() as i64
+    }
+}
+
+/// Round to one decimal place.
+pub fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Format story points without a trailing `.0`, e.g. `5` or `2.5`.
+pub fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{points:.0}")
+    } else {
+        format!("{points:.1}")
+    }
+}
+
 // ── Color style helpers ──────────────────────────────────────────────
 
 pub fn style_id(id: impl fmt::Display) -> String {
@@ -320,6 +466,15 @@ pub fn style_state_type(state_type: &str) -> String {
     }
 }
 
+pub fn style_health(status: &str) -> String {
+    match status {
+        "On Track" => format!("{}", status.green()),
+        "At Risk" => format!("{}", status.yellow()),
+        "Off Track" => format!("{}", status.red()),
+        _ => format!("{}", status.dimmed()),
+    }
+}
+
 pub fn style_mention(mention: &str) -> String {
     format!("{}", mention.bold().cyan())
 }
@@ -391,6 +546,47 @@ mod tests {
         assert_eq!(table.render(), "");
     }
 
+    #[test]
+    fn csv_row_quotes_special_characters() {
+        let row = csv_row(&[
+            "42".to_string(),
+            "Fix, then ship".to_string(),
+            "say \"hi\"".to_string(),
+        ]);
+        assert_eq!(row, "42,\"Fix, then ship\",\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn ascii_chart_plots_series() {
+        let chart = ascii_chart(
+            &[
+                ChartSeries {
+                    name: "Scope",
+                    marker: '-',
+                    values: &[4.0, 4.0, 4.0],
+                },
+                ChartSeries {
+                    name: "Done",
+                    marker: '*',
+                    values: &[0.0, 2.0, 4.0],
+                },
+            ],
+            3,
+            ("01-01", "01-03"),
+        );
+        assert_eq!(
+            chart,
+            "4 ┤--*\n  │ *\n0 ┤*\n  └───\n   01-01 01-03\n   - Scope   * Done\n"
+        );
+    }
+
+    #[test]
+    fn progress_bar_rounds_and_clamps() {
+        assert_eq!(progress_bar(42, 10), "████░░░░░░");
+        assert_eq!(progress_bar(0, 4), "░░░░");
+        assert_eq!(progress_bar(150, 4), "████");
+    }
+
     #[test]
     fn color_helpers() {
         // Test with color disabled - no ANSI codes
@@ -48,7 +48,7 @@ impl ProjectRoot {
     }
 }
 
-fn fnv1a_hex(data: &[u8]) -> String {
+pub(crate) fn fnv1a_hex(data: &[u8]) -> String {
     let mut hash: u64 = 0xcbf29ce484222325;
     for &byte in data {
         hash ^= byte as u64;
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use super::resolver::{split_ref, yaml_to_json};
+
+/// A parsed `when:` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(serde_json::Value),
+    Var(String),
+    Ref(String),
+    Exists(Box<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Var(String),
+    Ref(String),
+    Str(String),
+    Num(serde_json::Number),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Var(name) => write!(f, "$var({name})"),
+            Token::Ref(path) => write!(f, "$ref({path})"),
+            Token::Str(s) => write!(f, "'{s}'"),
+            Token::Num(n) => write!(f, "{n}"),
+            Token::Ident(word) => write!(f, "{word}"),
+            Token::Op(op) => write!(f, "{op}"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+        }
+    }
+}
+
+/// Parse a `when:` expression.
+///
+/// Grammar (lowest precedence first):
+/// - `a || b`, `a or b`
+/// - `a && b`, `a and b`
+/// - `!a`, `not a`
+/// - `a == b`, `!=`, `<`, `<=`, `>`, `>=`
+/// - `$var(name)`, `$ref(alias.field)`, `exists(...)`, `'string'`, numbers,
+///   `true`, `false`, `null`, `( ... )`
+pub fn parse(expr: &str) -> Result<Expr, String> {
+    let tokens = tokenize(expr)?;
+    if tokens.is_empty() {
+        return Err("empty expression".to_string());
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let parsed = parser.or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(parsed),
+        Some(token) => Err(format!("unexpected '{token}'")),
+    }
+}
+
+/// Evaluate a parsed expression to a boolean.
+///
+/// `$ref()`s to aliases that have no result (the operation was skipped or
+/// failed under `on_error: continue`) and missing fields evaluate to `null`.
+pub fn evaluate(
+    expr: &Expr,
+    vars: &HashMap<String, serde_yaml::Value>,
+    results: &HashMap<String, serde_json::Value>,
+) -> Result<bool, String> {
+    value_of(expr, vars, results).map(|v| truthy(&v))
+}
+
+/// Collect the variable names and `$ref()` paths used by an expression.
+pub fn references<'a>(expr: &'a Expr, vars: &mut Vec<&'a str>, refs: &mut Vec<&'a str>) {
+    match expr {
+        Expr::Literal(_) => {}
+        Expr::Var(name) => vars.push(name),
+        Expr::Ref(path) => refs.push(path),
+        Expr::Exists(inner) | Expr::Not(inner) => references(inner, vars, refs),
+        Expr::And(a, b) | Expr::Or(a, b) | Expr::Compare(a, _, b) => {
+            references(a, vars, refs);
+            references(b, vars, refs);
+        }
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..].iter().collect();
+        if c.is_whitespace() {
+            i += 1;
+        } else if let Some(prefix) = ["$var(", "$ref("].iter().find(|p| rest.starts_with(**p)) {
+            let Some(end) = rest.find(')') else {
+                return Err(format!("unclosed '{prefix}'"));
+            };
+            let inner = rest[prefix.len()..end].trim().to_string();
+            if inner.is_empty() {
+                return Err(format!("empty '{prefix})'"));
+            }
+            tokens.push(if *prefix == "$var(" {
+                Token::Var(inner)
+            } else {
+                Token::Ref(inner)
+            });
+            i += rest[..=end].chars().count();
+        } else if c == '\'' || c == '"' {
+            let mut s = String::new();
+            let mut j = i + 1;
+            loop {
+                match chars.get(j) {
+                    None => return Err("unterminated string".to_string()),
+                    Some('\\') if chars.get(j + 1).is_some() => {
+                        s.push(chars[j + 1]);
+                        j += 2;
+                    }
+                    Some(q) if *q == c => break,
+                    Some(other) => {
+                        s.push(*other);
+                        j += 1;
+                    }
+                }
+            }
+            tokens.push(Token::Str(s));
+            i = j + 1;
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()))
+        {
+            let mut j = i + 1;
+            while j < chars.len() && (chars[j].is_ascii_digit() || chars[j] == '.') {
+                j += 1;
+            }
+            let text: String = chars[i..j].iter().collect();
+            let number = text
+                .parse::<i64>()
+                .ok()
+                .map(serde_json::Number::from)
+                .or_else(|| {
+                    text.parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                })
+                .ok_or_else(|| format!("invalid number '{text}'"))?;
+            tokens.push(Token::Num(number));
+            i = j;
+        } else if c.is_ascii_alphabetic() {
+            let mut j = i + 1;
+            while j < chars.len() && (chars[j].is_ascii_alphanumeric() || chars[j] == '_') {
+                j += 1;
+            }
+            tokens.push(Token::Ident(chars[i..j].iter().collect()));
+            i = j;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if let Some(op) = ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!"]
+            .iter()
+            .find(|op| rest.starts_with(**op))
+        {
+            tokens.push(Token::Op(op));
+            i += op.len();
+        } else {
+            return Err(format!("unexpected character '{c}'"));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Consume the next token if it is the given operator or keyword.
+    fn eat(&mut self, op: &str, keyword: &str) -> bool {
+        let matched = match self.peek() {
+            Some(Token::Op(o)) => *o == op,
+            Some(Token::Ident(word)) => word == keyword,
+            _ => false,
+        };
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat("||", "or") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.not()?;
+        while self.eat("&&", "and") {
+            left = Expr::And(Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat("!", "not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.operand()?;
+        let op = match self.peek() {
+            Some(Token::Op("==")) => CompareOp::Eq,
+            Some(Token::Op("!=")) => CompareOp::Ne,
+            Some(Token::Op("<")) => CompareOp::Lt,
+            Some(Token::Op("<=")) => CompareOp::Le,
+            Some(Token::Op(">")) => CompareOp::Gt,
+            Some(Token::Op(">=")) => CompareOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.operand()?;
+        Ok(Expr::Compare(Box::new(left), op, Box::new(right)))
+    }
+
+    fn operand(&mut self) -> Result<Expr, String> {
+        match self.next()? {
+            Token::Var(name) => Ok(Expr::Var(name)),
+            Token::Ref(path) => Ok(Expr::Ref(path)),
+            Token::Str(s) => Ok(Expr::Literal(serde_json::Value::String(s))),
+            Token::Num(n) => Ok(Expr::Literal(serde_json::Value::Number(n))),
+            Token::LParen => {
+                let inner = self.or()?;
+                self.expect_rparen()?;
+                Ok(inner)
+            }
+            Token::Ident(word) => match word.as_str() {
+                "true" => Ok(Expr::Literal(serde_json::Value::Bool(true))),
+                "false" => Ok(Expr::Literal(serde_json::Value::Bool(false))),
+                "null" => Ok(Expr::Literal(serde_json::Value::Null)),
+                "exists" => {
+                    if self.next()? != Token::LParen {
+                        return Err("expected '(' after exists".to_string());
+                    }
+                    let inner = match self.next()? {
+                        Token::Var(name) => Expr::Var(name),
+                        Token::Ref(path) => Expr::Ref(path),
+                        _ => return Err("exists() takes a $var() or $ref()".to_string()),
+                    };
+                    self.expect_rparen()?;
+                    Ok(Expr::Exists(Box::new(inner)))
+                }
+                _ => Err(format!(
+                    "unknown word '{word}' (quote strings, e.g. '{word}')"
+                )),
+            },
+            token => Err(format!("unexpected '{token}'")),
+        }
+    }
+
+    fn expect_rparen(&mut self) -> Result<(), String> {
+        match self.next()? {
+            Token::RParen => Ok(()),
+            token => Err(format!("expected ')' but found '{token}'")),
+        }
+    }
+}
+
+fn value_of(
+    expr: &Expr,
+    vars: &HashMap<String, serde_yaml::Value>,
+    results: &HashMap<String, serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    use serde_json::Value;
+
+    Ok(match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Var(name) => vars
+            .get(name)
+            .map(yaml_to_json)
+            .ok_or_else(|| format!("undeclared variable '{name}'"))?,
+        Expr::Ref(path) => ref_value(path, results),
+        Expr::Exists(inner) => Value::Bool(!value_of(inner, vars, results)?.is_null()),
+        Expr::Not(inner) => Value::Bool(!truthy(&value_of(inner, vars, results)?)),
+        Expr::And(a, b) => Value::Bool(
+            truthy(&value_of(a, vars, results)?) && truthy(&value_of(b, vars, results)?),
+        ),
+        Expr::Or(a, b) => Value::Bool(
+            truthy(&value_of(a, vars, results)?) || truthy(&value_of(b, vars, results)?),
+        ),
+        Expr::Compare(a, op, b) => {
+            let left = value_of(a, vars, results)?;
+            let right = value_of(b, vars, results)?;
+            Value::Bool(compare(&left, *op, &right)?)
+        }
+    })
+}
+
+/// Look up `alias` or `alias.path.to.field`. A bare alias yields the
+/// result's `id`, as `$ref()` does elsewhere.
+fn ref_value(path: &str, results: &HashMap<String, serde_json::Value>) -> serde_json::Value {
+    let (alias, field) = split_ref(path, |a| results.contains_key(a));
+    let Some(result) = results.get(alias) else {
+        return serde_json::Value::Null;
+    };
+    let mut current = result;
+    for part in field.into_iter().flat_map(|f| f.split('.')) {
+        let next = match current {
+            serde_json::Value::Array(arr) => part.parse::<usize>().ok().and_then(|i| arr.get(i)),
+            _ => current.get(part),
+        };
+        match next {
+            Some(value) => current = value,
+            None => return serde_json::Value::Null,
+        }
+    }
+    if field.is_none() && current.is_object() {
+        return current
+            .get("id")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+    }
+    current.clone()
+}
+
+fn truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => false,
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+        // `--var flag=false` arrives as a string
+        serde_json::Value::String(s) => !matches!(s.as_str(), "" | "false" | "0"),
+        serde_json::Value::Array(arr) => !arr.is_empty(),
+        serde_json::Value::Object(_) => true,
+    }
+}
+
+fn compare(
+    left: &serde_json::Value,
+    op: CompareOp,
+    right: &serde_json::Value,
+) -> Result<bool, String> {
+    use serde_json::Value;
+    use std::cmp::Ordering;
+
+    // Strings from `--var` compare against numbers and booleans by value
+    let coerced;
+    let (left, right) = match (left, right) {
+        (Value::String(s), Value::Number(_) | Value::Bool(_)) => {
+            coerced = serde_json::from_str::<Value>(s).unwrap_or_else(|_| left.clone());
+            (&coerced, right)
+        }
+        (Value::Number(_) | Value::Bool(_), Value::String(s)) => {
+            coerced = serde_json::from_str::<Value>(s).unwrap_or_else(|_| right.clone());
+            (left, &coerced)
+        }
+        _ => (left, right),
+    };
+
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => Ok(ordering.map_or(left == right, |o| o == Ordering::Equal)),
+        CompareOp::Ne => Ok(ordering.map_or(left != right, |o| o != Ordering::Equal)),
+        _ => {
+            let ordering = ordering.ok_or_else(|| {
+                format!(
+                    "cannot compare {} with {} using '{}'",
+                    type_name(left),
+                    type_name(right),
+                    op.symbol()
+                )
+            })?;
+            Ok(match op {
+                CompareOp::Lt => ordering == Ordering::Less,
+                CompareOp::Le => ordering != Ordering::Greater,
+                CompareOp::Gt => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })
+        }
+    }
+}
+
+fn type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "list",
+        serde_json::Value::Object(_) => "object",
+    }
+}
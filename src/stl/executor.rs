@@ -1,18 +1,28 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
+use std::future::Future;
 use std::path::Path;
+use std::pin::Pin;
+use std::task::Poll;
 
 use crate::api;
 use crate::commands::{custom_field, epic, group, member, story};
 use crate::out_println;
 use crate::output::OutputConfig;
 
+use super::condition;
+use super::finder::{describe_criteria, describe_for_each, find_entity, for_each_items};
 use super::reconciler::SyncAction;
-use super::resolver::{resolve_refs, substitute_vars, yaml_mapping_to_json, yaml_to_json};
+use super::resolver::{
+    resolve_refs, substitute_item, substitute_vars, yaml_mapping_to_json, yaml_to_json,
+};
+use super::rollback::Journal;
 use super::state::{EntryState, ResourceState, SyncState, TaskEntry};
 use super::types::*;
+use super::validator;
 
-/// Common parameters shared across sync execution helper functions.
+/// Common parameters shared across execution helper functions.
 struct SyncExecContext<'a> {
     results: &'a HashMap<String, serde_json::Value>,
     client: &'a api::Client,
@@ -24,38 +34,28 @@ struct SyncExecContext<'a> {
 }
 
 /// Execute a validated template.
+///
+/// Operations run as soon as the operations they `$ref()` have finished, with
+/// up to `concurrency` units (single operations, repeat entries, for_each
+/// items) in flight. Progress and results are reported in operation order
+/// whichever call finishes first; with a concurrency of 1 the run is
+/// sequential.
 pub async fn execute(
     template: &mut Template,
     client: &api::Client,
     cache_dir: &Path,
     out: &OutputConfig,
     confirm: bool,
+    concurrency: usize,
 ) -> Result<ExecutionResult, Box<dyn Error>> {
     // Pre-pass: substitute all $var() in the entire operations tree
+    substitute_template_vars(template)?;
     let vars = template.vars.clone().unwrap_or_default();
-    for op in &mut template.operations {
-        if let Some(fields) = &mut op.fields {
-            let mut val = serde_yaml::Value::Mapping(fields.clone());
-            substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
-            if let serde_yaml::Value::Mapping(m) = val {
-                *fields = m;
-            }
-        }
-        if let Some(repeat) = &mut op.repeat {
-            for entry in repeat.iter_mut() {
-                let mut val = serde_yaml::Value::Mapping(entry.clone());
-                substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
-                if let serde_yaml::Value::Mapping(m) = val {
-                    *entry = m;
-                }
-            }
-        }
-        if let Some(id) = &mut op.id {
-            substitute_vars(id, &vars).map_err(|errs| errs.join("; "))?;
-        }
-    }
 
-    // Count total operations (expanding repeats)
+    let template: &Template = template;
+
+    // Count total operations (expanding repeats). for_each items are added
+    // once their source has been fetched.
     let total = count_operations(&template.operations);
     let doc_on_error = &template.on_error;
     let show_progress = !out.is_machine_readable();
@@ -67,382 +67,752 @@ pub async fn execute(
         }
     }
 
-    let mut results: HashMap<String, serde_json::Value> = HashMap::new();
-    let mut op_results: Vec<OperationResult> = Vec::new();
-    let mut op_counter = 0;
+    // Only journal (and snapshot before updates) when something can roll back
+    let uses_rollback = *doc_on_error == Some(ErrorHandling::Rollback)
+        || template
+            .operations
+            .iter()
+            .any(|op| op.on_error == Some(ErrorHandling::Rollback));
+    let journal = (uses_rollback && !out.is_dry_run()).then(|| RefCell::new(Journal::default()));
+
+    let mut schedule = Schedule {
+        ops: &template.operations,
+        vars: &vars,
+        doc_on_error: doc_on_error.as_ref(),
+        dependencies: validator::dependencies(template),
+        inputs: validator::input_dependencies(template),
+        states: template
+            .operations
+            .iter()
+            .map(|_| OpState::default())
+            .collect(),
+        queue: BTreeMap::new(),
+        results: HashMap::new(),
+        op_results: Vec::new(),
+        output: OrderedOutput::new(out, &template.operations),
+        show_progress,
+        stop: None,
+    };
 
-    for op in template.operations.iter() {
-        let should_continue_on_error = op
-            .on_error
-            .as_ref()
-            .or(doc_on_error.as_ref())
-            .map(|e| *e == ErrorHandling::Continue)
-            .unwrap_or(false);
-
-        if let Some(repeat) = &op.repeat {
-            // Expand repeat into N sub-operations
-            let shared_fields = op.fields.as_ref().cloned().unwrap_or_default();
-            let mut repeat_results: Vec<serde_json::Value> = Vec::new();
-
-            for entry in repeat.iter() {
-                op_counter += 1;
-                // Merge shared fields with repeat entry (entry overrides shared)
-                let merged = merge_mappings(&shared_fields, entry);
-                let mut json_body = yaml_mapping_to_json(&merged);
-
-                // Resolve $ref()
-                if let Err(e) = resolve_refs(&mut json_body, &results) {
-                    let op_result = OperationResult {
-                        index: op_counter - 1,
-                        action: op.action.to_string(),
-                        entity: op.entity.to_string(),
-                        status: "failed".to_string(),
-                        result: None,
-                        error: Some(e.clone()),
-                    };
-                    if show_progress {
-                        out_println!(
-                            out,
-                            "[{}/{}] FAILED: {} {} — {}",
-                            op_counter,
-                            total,
-                            op.action,
-                            op.entity,
-                            e
-                        );
-                    }
-                    op_results.push(op_result);
-                    if !should_continue_on_error {
-                        return Ok(build_result(op_results, total));
-                    }
-                    continue;
+    {
+        let mut running: Vec<Pin<Box<dyn Future<Output = Finished> + '_>>> = Vec::new();
+        loop {
+            if schedule.stop.is_none() {
+                schedule.start_ready()?;
+            }
+            while schedule.stop.is_none() && running.len() < concurrency.max(1) {
+                // Dry-run placeholders take the unit's number, which is only
+                // known once earlier for_each operations have their items
+                if out.is_dry_run()
+                    && schedule
+                        .queue
+                        .first_key_value()
+                        .is_some_and(|(slot, _)| !schedule.output.is_numbered(slot.0))
+                {
+                    break;
                 }
+                let Some((_, unit)) = schedule.queue.pop_first() else {
+                    break;
+                };
+                running.push(launch(
+                    unit,
+                    &schedule,
+                    client,
+                    cache_dir,
+                    out,
+                    journal.as_ref(),
+                ));
+            }
+            if running.is_empty() {
+                break;
+            }
+            let finished = next_finished(&mut running).await;
+            schedule.finish(finished)?;
+        }
+    }
+    schedule.output.flush()?;
 
-                // Extract story_id before resolve_entity_fields removes it.
-                let story_id = json_body.get("story_id").and_then(|v| v.as_i64());
+    // Every for_each that ran knows its items now, so results can be numbered
+    let numbering = &schedule.output;
+    let op_results = schedule
+        .op_results
+        .into_iter()
+        .map(|(slot, result)| OperationResult {
+            index: numbering.number(slot) - 1,
+            ..result
+        })
+        .collect();
+    let rollback = journal
+        .map(RefCell::into_inner)
+        .filter(|_| schedule.stop == Some(true));
+    conclude(
+        op_results,
+        numbering.total(template.operations.len()),
+        rollback,
+        |slot| numbering.number(slot) - 1,
+        client,
+        out,
+        show_progress,
+    )
+    .await
+}
 
-                // Resolve entity-specific fields
-                if let Err(e) =
-                    resolve_entity_fields(&op.entity, &op.action, &mut json_body, client, cache_dir)
-                        .await
-                {
-                    let op_result = OperationResult {
-                        index: op_counter - 1,
-                        action: op.action.to_string(),
-                        entity: op.entity.to_string(),
-                        status: "failed".to_string(),
-                        result: None,
-                        error: Some(e.to_string()),
-                    };
-                    if show_progress {
-                        out_println!(
-                            out,
-                            "[{}/{}] FAILED: {} {} — {}",
-                            op_counter,
-                            total,
-                            op.action,
-                            op.entity,
-                            e
-                        );
-                    }
-                    op_results.push(op_result);
-                    if !should_continue_on_error {
-                        return Ok(build_result(op_results, total));
-                    }
-                    continue;
-                }
+// ── Scheduling ─────────────────────────────────────────────────────
+
+/// An operation index and a position within the operation (its repeat entry
+/// or for_each item).
+pub(super) type Slot = (usize, usize);
+
+/// Stands in for a unit's "n/total" in what it prints until [`OrderedOutput`]
+/// writes it out, since the numbers depend on how many items earlier for_each
+/// operations turn out to have.
+const PROGRESS: &str = "\u{0}progress\u{0}";
+
+/// What a unit of work does.
+enum Task {
+    /// A plain operation.
+    Single,
+    /// One entry of a `repeat`.
+    Entry(usize),
+    /// One item of a `for_each`.
+    Item(serde_json::Value),
+    /// Fetch the items of a `for_each`.
+    Items,
+}
 
-                if out.is_dry_run() {
-                    if show_progress {
-                        out_println!(
-                            out,
-                            "[{}/{}] {} {}",
-                            op_counter,
-                            total,
-                            op.action,
-                            op.entity
-                        );
-                        let pretty = serde_json::to_string_pretty(&json_body)?;
-                        out_println!(out, "  {}", pretty.replace('\n', "\n  "));
-                        out_println!(out, "");
-                    }
-                    let op_result = OperationResult {
-                        index: op_counter - 1,
-                        action: op.action.to_string(),
-                        entity: op.entity.to_string(),
-                        status: "success".to_string(),
-                        result: Some(dry_run_placeholder(&op.entity, op_counter)),
-                        error: None,
-                    };
-                    repeat_results.push(dry_run_placeholder(&op.entity, op_counter));
-                    op_results.push(op_result);
-                    continue;
-                }
+/// One API call (or lookup) of an operation.
+struct Unit {
+    slot: Slot,
+    task: Task,
+}
 
-                // Execute the API call via generated client
-                match dispatch_api_call(client, &op.action, &op.entity, None, story_id, json_body)
-                    .await
-                {
-                    Ok(response) => {
-                        if show_progress {
-                            let name = response.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                            let id_display = response
-                                .get("id")
-                                .map(json_value_display)
-                                .unwrap_or_default();
-                            let action_past = action_past_tense(&op.action);
-                            if name.is_empty() {
-                                out_println!(
-                                    out,
-                                    "[{}/{}] {} {} {}",
-                                    op_counter,
-                                    total,
-                                    action_past,
-                                    op.entity,
-                                    id_display
-                                );
-                            } else {
-                                out_println!(
-                                    out,
-                                    "[{}/{}] {} {} {} - {}",
-                                    op_counter,
-                                    total,
-                                    action_past,
-                                    op.entity,
-                                    id_display,
-                                    name
-                                );
-                            }
-                        }
-                        repeat_results.push(response.clone());
-                        op_results.push(OperationResult {
-                            index: op_counter - 1,
-                            action: op.action.to_string(),
-                            entity: op.entity.to_string(),
-                            status: "success".to_string(),
-                            result: Some(response),
-                            error: None,
-                        });
-                    }
-                    Err(e) => {
-                        let err_msg = e.to_string();
-                        if show_progress {
-                            out_println!(
-                                out,
-                                "[{}/{}] FAILED: {} {} — {}",
-                                op_counter,
-                                total,
-                                op.action,
-                                op.entity,
-                                err_msg
-                            );
-                        }
-                        op_results.push(OperationResult {
-                            index: op_counter - 1,
-                            action: op.action.to_string(),
-                            entity: op.entity.to_string(),
-                            status: "failed".to_string(),
-                            result: None,
-                            error: Some(err_msg),
-                        });
-                        if !should_continue_on_error {
-                            return Ok(build_result(op_results, total));
-                        }
+/// The outcome of a unit, with the progress lines it printed.
+struct Finished {
+    slot: Slot,
+    fetched_items: bool,
+    outcome: Result<Outcome, String>,
+    output: Vec<u8>,
+}
+
+enum Outcome {
+    Response(serde_json::Value),
+    Items(Vec<serde_json::Value>),
+}
+
+#[derive(Default)]
+struct OpState {
+    started: bool,
+    done: bool,
+    /// Skipped, so its alias has no result.
+    skipped: bool,
+    /// Units queued or running.
+    remaining: usize,
+    /// Successful results by position, stored under the alias when done.
+    responses: Vec<(usize, serde_json::Value)>,
+}
+
+/// Tracks which operations can run, given the `$ref()`s between them, and
+/// collects their results in operation order.
+struct Schedule<'a> {
+    ops: &'a [Operation],
+    vars: &'a HashMap<String, serde_yaml::Value>,
+    doc_on_error: Option<&'a ErrorHandling>,
+    dependencies: Vec<Vec<usize>>,
+    /// Dependencies outside `when`, whose results the requests are built from.
+    inputs: Vec<Vec<usize>>,
+    states: Vec<OpState>,
+    /// Units ready to run by slot, so the earliest runs first and a
+    /// concurrency of 1 keeps template order.
+    queue: BTreeMap<Slot, Unit>,
+    results: HashMap<String, serde_json::Value>,
+    /// Results by slot; they are numbered once the run is over.
+    op_results: Vec<(Slot, OperationResult)>,
+    output: OrderedOutput<'a>,
+    show_progress: bool,
+    /// Set once an operation fails without `on_error: continue`; true when
+    /// its policy is `rollback`.
+    stop: Option<bool>,
+}
+
+impl Schedule<'_> {
+    /// Start every operation whose dependencies are done. Operations that are
+    /// skipped or fail their `when` finish at once, which may free others.
+    fn start_ready(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut progressed = true;
+        while progressed && self.stop.is_none() {
+            progressed = false;
+            for idx in 0..self.ops.len() {
+                if self.stop.is_some() {
+                    break;
+                }
+                let ready = !self.states[idx].started
+                    && self.dependencies[idx]
+                        .iter()
+                        .all(|&dep| self.states[dep].done);
+                if ready {
+                    self.start(idx)?;
+                    progressed = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, idx: usize) -> Result<(), Box<dyn Error>> {
+        let op = &self.ops[idx];
+        self.states[idx].started = true;
+
+        if let Some(when) = &op.when {
+            let run = condition::parse(when)
+                .and_then(|expr| condition::evaluate(&expr, self.vars, &self.results))
+                .map_err(|e| format!("when: {e}"));
+            match run {
+                Ok(true) => {}
+                Ok(false) => return self.skip(idx, format!("when: {when}")),
+                Err(err_msg) => {
+                    let n = self.output.claim_size(idx);
+                    self.fail((idx, 0), err_msg);
+                    for pos in 0..n {
+                        self.output.complete((idx, pos))?;
                     }
+                    self.states[idx].done = true;
+                    return Ok(());
                 }
             }
+        }
 
-            // Store repeat results under alias (as array)
-            if let Some(alias) = &op.alias {
-                results.insert(alias.clone(), serde_json::Value::Array(repeat_results));
-            }
-        } else {
-            // Single operation
-            op_counter += 1;
-
-            let mut json_body = op
-                .fields
-                .as_ref()
-                .map(yaml_mapping_to_json)
-                .unwrap_or(serde_json::json!({}));
-
-            // Resolve the `id` field
-            let resolved_id = if let Some(id_val) = &op.id {
-                let mut json_id = yaml_to_json(id_val);
-                resolve_refs(&mut json_id, &results).map_err(|e| e.to_string())?;
-                Some(json_id)
-            } else {
-                None
-            };
+        // A request built from a skipped operation's result cannot be made
+        if let Some(&dep) = self.inputs[idx]
+            .iter()
+            .find(|&&dep| self.states[dep].skipped)
+        {
+            let alias = self.ops[dep].alias.as_deref().unwrap_or_default();
+            return self.skip(idx, format!("'{alias}' was skipped"));
+        }
 
-            // Resolve $ref() in body
-            if let Err(e) = resolve_refs(&mut json_body, &results) {
-                let op_result = OperationResult {
-                    index: op_counter - 1,
+        let tasks: Vec<Task> = if op.for_each.is_some() {
+            vec![Task::Items]
+        } else if let Some(repeat) = &op.repeat {
+            (0..repeat.len()).map(Task::Entry).collect()
+        } else {
+            vec![Task::Single]
+        };
+        if tasks.is_empty() {
+            self.complete_op(idx);
+        }
+        self.states[idx].remaining = tasks.len();
+        for (pos, task) in tasks.into_iter().enumerate() {
+            let slot = (idx, pos);
+            self.queue.insert(slot, Unit { slot, task });
+        }
+        Ok(())
+    }
+
+    /// Finish an operation without running it.
+    fn skip(&mut self, idx: usize, reason: String) -> Result<(), Box<dyn Error>> {
+        let op = &self.ops[idx];
+        // A skipped repeat reports each entry; a skipped for_each never
+        // fetched its items, so it counts once
+        let n = self.output.claim_size(idx);
+        for pos in 0..n {
+            let slot = (idx, pos);
+            self.line(
+                slot,
+                format!(
+                    "[{PROGRESS}] Skipped {} {} ({reason})",
+                    op.action, op.entity
+                ),
+            );
+            self.op_results.push((
+                slot,
+                OperationResult {
+                    index: 0,
                     action: op.action.to_string(),
                     entity: op.entity.to_string(),
-                    status: "failed".to_string(),
+                    status: "skipped".to_string(),
                     result: None,
-                    error: Some(e.clone()),
-                };
-                if show_progress {
-                    out_println!(
-                        out,
-                        "[{}/{}] FAILED: {} {} — {}",
-                        op_counter,
-                        total,
-                        op.action,
-                        op.entity,
-                        e
+                    error: None,
+                    reason: Some(reason.clone()),
+                },
+            ));
+            self.output.complete(slot)?;
+        }
+        self.states[idx].done = true;
+        self.states[idx].skipped = true;
+        Ok(())
+    }
+
+    /// Record the outcome of a unit.
+    fn finish(&mut self, finished: Finished) -> Result<(), Box<dyn Error>> {
+        let Finished {
+            slot,
+            fetched_items,
+            outcome,
+            output,
+        } = finished;
+        let idx = slot.0;
+        let op = &self.ops[idx];
+        self.output.append(slot, &output);
+        self.states[idx].remaining -= 1;
+
+        match outcome {
+            Ok(Outcome::Items(items)) => {
+                let count = items.len();
+                if let Some(for_each) = &op.for_each {
+                    self.line(
+                        slot,
+                        format!(
+                            "{} {} for each of {} {}",
+                            op.action,
+                            op.entity,
+                            count,
+                            describe_for_each(&yaml_mapping_to_json(for_each))
+                        ),
                     );
                 }
-                op_results.push(op_result);
-                if !should_continue_on_error {
-                    return Ok(build_result(op_results, total));
+                self.states[idx].remaining += count;
+                for (pos, item) in items.into_iter().enumerate() {
+                    let slot = (idx, pos);
+                    self.queue.insert(
+                        slot,
+                        Unit {
+                            slot,
+                            task: Task::Item(item),
+                        },
+                    );
+                }
+                self.output.set_size(idx, count)?;
+                if self.states[idx].remaining == 0 {
+                    self.complete_op(idx);
                 }
-                continue;
+                return Ok(());
             }
+            Ok(Outcome::Response(response)) => {
+                self.states[idx].responses.push((slot.1, response.clone()));
+                self.op_results.push((
+                    slot,
+                    OperationResult {
+                        index: 0,
+                        action: op.action.to_string(),
+                        entity: op.entity.to_string(),
+                        status: "success".to_string(),
+                        result: Some(response),
+                        error: None,
+                        reason: None,
+                    },
+                ));
+            }
+            Err(err_msg) => {
+                // A for_each whose items could not be fetched counts once
+                if fetched_items {
+                    self.output.set_size(idx, 1)?;
+                }
+                self.fail(slot, err_msg);
+            }
+        }
+        self.output.complete(slot)?;
+        if self.states[idx].remaining == 0 {
+            self.complete_op(idx);
+        }
+        Ok(())
+    }
 
-            // Extract story_id before resolve_entity_fields removes it.
-            let story_id = json_body.get("story_id").and_then(|v| v.as_i64());
-
-            // Resolve entity-specific fields
-            if let Err(e) =
-                resolve_entity_fields(&op.entity, &op.action, &mut json_body, client, cache_dir)
-                    .await
-            {
-                let op_result = OperationResult {
-                    index: op_counter - 1,
-                    action: op.action.to_string(),
-                    entity: op.entity.to_string(),
-                    status: "failed".to_string(),
-                    result: None,
-                    error: Some(e.to_string()),
+    /// Report a failed unit and stop unless its operation continues on error.
+    fn fail(&mut self, slot: Slot, err_msg: String) {
+        let op = &self.ops[slot.0];
+        self.line(
+            slot,
+            format!(
+                "[{PROGRESS}] FAILED: {} {} — {}",
+                op.action, op.entity, err_msg
+            ),
+        );
+        self.op_results.push((
+            slot,
+            OperationResult {
+                index: 0,
+                action: op.action.to_string(),
+                entity: op.entity.to_string(),
+                status: "failed".to_string(),
+                result: None,
+                error: Some(err_msg),
+                reason: None,
+            },
+        ));
+        let on_error = op.on_error.as_ref().or(self.doc_on_error);
+        if on_error != Some(&ErrorHandling::Continue) && self.stop.is_none() {
+            self.stop = Some(on_error == Some(&ErrorHandling::Rollback));
+        }
+    }
+
+    /// Mark an operation done and store its result under its alias.
+    fn complete_op(&mut self, idx: usize) {
+        let op = &self.ops[idx];
+        let state = &mut self.states[idx];
+        state.done = true;
+        let mut responses = std::mem::take(&mut state.responses);
+        let Some(alias) = &op.alias else {
+            return;
+        };
+        if op.repeat.is_some() || op.for_each.is_some() {
+            responses.sort_by_key(|(pos, _)| *pos);
+            let list = responses.into_iter().map(|(_, r)| r).collect();
+            self.results
+                .insert(alias.clone(), serde_json::Value::Array(list));
+        } else if let Some((_, response)) = responses.pop() {
+            self.results.insert(alias.clone(), response);
+        }
+    }
+
+    fn line(&mut self, slot: Slot, line: String) {
+        if self.show_progress {
+            self.output.append(slot, format!("{line}\n").as_bytes());
+        }
+    }
+
+    /// Build the request body and target id of a unit from its operation,
+    /// resolving `$ref()`s against the results so far.
+    fn prepare(
+        &self,
+        unit: &Unit,
+    ) -> Result<(serde_json::Value, Option<serde_json::Value>), String> {
+        let op = &self.ops[unit.slot.0];
+        let (fields, id) = match &unit.task {
+            Task::Single => (
+                serde_yaml::Value::Mapping(op.fields.clone().unwrap_or_default()),
+                op.id.clone(),
+            ),
+            Task::Entry(entry) => {
+                let shared = op.fields.clone().unwrap_or_default();
+                let entry = op
+                    .repeat
+                    .as_ref()
+                    .and_then(|r| r.get(*entry))
+                    .ok_or("repeat entry missing")?;
+                (
+                    serde_yaml::Value::Mapping(merge_mappings(&shared, entry)),
+                    None,
+                )
+            }
+            Task::Item(item) => {
+                let mut fields = serde_yaml::Value::Mapping(op.fields.clone().unwrap_or_default());
+                // Without an explicit id, target the item itself (create and
+                // link take no id)
+                let mut id = if matches!(op.action, Action::Create | Action::Link) {
+                    op.id.clone()
+                } else {
+                    Some(
+                        op.id
+                            .clone()
+                            .unwrap_or_else(|| serde_yaml::Value::String("$item(id)".to_string())),
+                    )
                 };
-                if show_progress {
-                    out_println!(
-                        out,
-                        "[{}/{}] FAILED: {} {} — {}",
-                        op_counter,
-                        total,
-                        op.action,
-                        op.entity,
-                        e
-                    );
+                substitute_item(&mut fields, item).map_err(|errs| errs.join("; "))?;
+                if let Some(id) = &mut id {
+                    substitute_item(id, item).map_err(|errs| errs.join("; "))?;
                 }
-                op_results.push(op_result);
-                if !should_continue_on_error {
-                    return Ok(build_result(op_results, total));
-                }
-                continue;
+                (fields, id)
             }
+            Task::Items => return Err("for_each items are fetched, not prepared".to_string()),
+        };
 
-            if out.is_dry_run() {
-                if show_progress {
-                    out_println!(
-                        out,
-                        "[{}/{}] {} {}",
-                        op_counter,
-                        total,
-                        op.action,
-                        op.entity
-                    );
-                    if !json_body_is_empty(&json_body) {
-                        let pretty = serde_json::to_string_pretty(&json_body)?;
-                        out_println!(out, "  {}", pretty.replace('\n', "\n  "));
-                    }
-                    out_println!(out, "");
-                }
-                let placeholder = dry_run_placeholder(&op.entity, op_counter);
-                if let Some(alias) = &op.alias {
-                    results.insert(alias.clone(), placeholder.clone());
-                }
-                op_results.push(OperationResult {
-                    index: op_counter - 1,
-                    action: op.action.to_string(),
-                    entity: op.entity.to_string(),
-                    status: "success".to_string(),
-                    result: Some(placeholder),
-                    error: None,
-                });
-                continue;
+        let mut body = match &fields {
+            serde_yaml::Value::Mapping(m) => yaml_mapping_to_json(m),
+            _ => serde_json::json!({}),
+        };
+        let id = match id {
+            Some(id) => {
+                let mut json_id = yaml_to_json(&id);
+                resolve_refs(&mut json_id, &self.results)?;
+                Some(json_id)
             }
+            None => None,
+        };
+        resolve_refs(&mut body, &self.results)?;
+        Ok((body, id))
+    }
+}
 
-            // Execute the API call via generated client
-            match dispatch_api_call(
+/// Start a unit. Everything it needs from the schedule is taken up front,
+/// so the schedule can record other results while it runs.
+fn launch<'a>(
+    unit: Unit,
+    schedule: &Schedule<'a>,
+    client: &'a api::Client,
+    cache_dir: &'a Path,
+    out: &'a OutputConfig,
+    journal: Option<&'a RefCell<Journal>>,
+) -> Pin<Box<dyn Future<Output = Finished> + 'a>> {
+    let op = &schedule.ops[unit.slot.0];
+    let show_progress = schedule.show_progress;
+    let (buffer_out, buffer) = out.buffered();
+
+    if let Task::Items = unit.task {
+        let source = op
+            .for_each
+            .as_ref()
+            .map(yaml_mapping_to_json)
+            .unwrap_or_default();
+        let results = schedule.results.clone();
+        return Box::pin(async move {
+            let outcome = for_each_items(&source, &results, client)
+                .await
+                .map(Outcome::Items)
+                .map_err(|e| format!("for_each: {e}"));
+            Finished {
+                slot: unit.slot,
+                fetched_items: true,
+                outcome,
+                output: Vec::new(),
+            }
+        });
+    }
+
+    let prepared = schedule.prepare(&unit);
+    let number = schedule.output.number(unit.slot);
+    Box::pin(async move {
+        let outcome = match prepared {
+            Ok((body, id)) => perform(
+                op,
+                body,
+                id,
                 client,
-                &op.action,
-                &op.entity,
-                resolved_id.as_ref(),
-                story_id,
-                json_body,
+                cache_dir,
+                &buffer_out,
+                PROGRESS,
+                unit.slot,
+                number,
+                show_progress,
+                journal,
             )
             .await
-            {
-                Ok(response) => {
-                    if show_progress {
-                        let action_past = action_past_tense(&op.action);
-                        print_success_line(
-                            out,
-                            op_counter,
-                            total,
-                            &action_past,
-                            &op.action,
-                            &op.entity,
-                            &response,
-                            resolved_id.as_ref(),
-                        )?;
-                    }
-                    if let Some(alias) = &op.alias {
-                        results.insert(alias.clone(), response.clone());
-                    }
-                    op_results.push(OperationResult {
-                        index: op_counter - 1,
-                        action: op.action.to_string(),
-                        entity: op.entity.to_string(),
-                        status: "success".to_string(),
-                        result: Some(response),
-                        error: None,
-                    });
+            .map(Outcome::Response)
+            .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        let output = std::mem::take(&mut *buffer.lock().unwrap());
+        Finished {
+            slot: unit.slot,
+            fetched_items: false,
+            outcome,
+            output,
+        }
+    })
+}
+
+/// Wait for the first running unit to finish and remove it.
+async fn next_finished(
+    running: &mut Vec<Pin<Box<dyn Future<Output = Finished> + '_>>>,
+) -> Finished {
+    std::future::poll_fn(|cx| {
+        for i in 0..running.len() {
+            if let Poll::Ready(finished) = running[i].as_mut().poll(cx) {
+                drop(running.swap_remove(i));
+                return Poll::Ready(finished);
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Writes what units print in operation order, whichever finishes first,
+/// numbering each unit as it goes out. Output after a for_each waits until
+/// its items are known, since they are numbered before it.
+struct OrderedOutput<'a> {
+    out: &'a OutputConfig,
+    /// How many units each operation reports; unknown for a for_each until
+    /// its items are fetched.
+    sizes: Vec<Option<usize>>,
+    /// Which operations are for_each operations. Their items count towards
+    /// the totals shown from that operation on.
+    for_each: Vec<bool>,
+    /// The next slot to write.
+    next: Slot,
+    /// Output by slot, and whether that slot's unit has finished.
+    pending: BTreeMap<Slot, (Vec<u8>, bool)>,
+}
+
+impl<'a> OrderedOutput<'a> {
+    fn new(out: &'a OutputConfig, ops: &[Operation]) -> Self {
+        let sizes: Vec<Option<usize>> = ops
+            .iter()
+            .map(|op| {
+                op.for_each
+                    .is_none()
+                    .then(|| op.repeat.as_ref().map_or(1, |r| r.len()))
+            })
+            .collect();
+        OrderedOutput {
+            out,
+            for_each: sizes.iter().map(Option::is_none).collect(),
+            sizes,
+            next: (0, 0),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    fn append(&mut self, slot: Slot, bytes: &[u8]) {
+        self.pending
+            .entry(slot)
+            .or_default()
+            .0
+            .extend_from_slice(bytes);
+    }
+
+    /// Record how many items a for_each has and write what that unblocks.
+    fn set_size(&mut self, op: usize, size: usize) -> Result<(), Box<dyn Error>> {
+        self.sizes[op] = Some(size);
+        self.write_ready()
+    }
+
+    /// The units an operation reports, fixing a for_each that never ran at one.
+    fn claim_size(&mut self, op: usize) -> usize {
+        *self.sizes[op].get_or_insert(1)
+    }
+
+    /// Mark a slot finished and write everything that is now in order.
+    fn complete(&mut self, slot: Slot) -> Result<(), Box<dyn Error>> {
+        self.pending.entry(slot).or_default().1 = true;
+        self.write_ready()
+    }
+
+    fn write_ready(&mut self) -> Result<(), Box<dyn Error>> {
+        while let Some(&Some(size)) = self.sizes.get(self.next.0) {
+            let slot = self.next;
+            if slot.1 < size {
+                if !self.pending.get(&slot).is_some_and(|(_, done)| *done) {
+                    break;
                 }
-                Err(e) => {
-                    let err_msg = e.to_string();
-                    if show_progress {
-                        out_println!(
-                            out,
-                            "[{}/{}] FAILED: {} {} — {}",
-                            op_counter,
-                            total,
-                            op.action,
-                            op.entity,
-                            err_msg
-                        );
-                    }
-                    op_results.push(OperationResult {
-                        index: op_counter - 1,
-                        action: op.action.to_string(),
-                        entity: op.entity.to_string(),
-                        status: "failed".to_string(),
-                        result: None,
-                        error: Some(err_msg),
-                    });
-                    if !should_continue_on_error {
-                        return Ok(build_result(op_results, total));
-                    }
+                self.next.1 += 1;
+            } else {
+                self.next = (slot.0 + 1, 0);
+                if size > 0 {
+                    continue;
                 }
+                // An operation with nothing to number, like a for_each
+                // without items, may still have printed a line
+            }
+            self.write(slot)?;
+        }
+        Ok(())
+    }
+
+    /// Write whatever is left, e.g. after a run stopped early.
+    fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        let slots: Vec<Slot> = self.pending.keys().copied().collect();
+        for slot in slots {
+            self.write(slot)?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, slot: Slot) -> Result<(), Box<dyn Error>> {
+        if let Some((bytes, _)) = self.pending.remove(&slot) {
+            let progress = format!("{}/{}", self.number(slot), self.total(slot.0));
+            let text = String::from_utf8_lossy(&bytes).replace(PROGRESS, &progress);
+            self.out.write_raw(text.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Whether the slots of an operation can be numbered yet.
+    fn is_numbered(&self, op: usize) -> bool {
+        self.sizes[..op].iter().all(Option::is_some)
+    }
+
+    /// The number a slot is reported under, counting from 1.
+    fn number(&self, (op, pos): Slot) -> usize {
+        self.sizes[..op].iter().flatten().sum::<usize>() + pos + 1
+    }
+
+    /// The total shown with an operation's lines: every unit known so far,
+    /// leaving out the items of for_each operations after it.
+    fn total(&self, op: usize) -> usize {
+        self.sizes
+            .iter()
+            .zip(&self.for_each)
+            .enumerate()
+            .filter(|(i, (_, for_each))| !**for_each || *i <= op)
+            .filter_map(|(_, (size, _))| *size)
+            .sum()
+    }
+}
+
+/// Run one operation instance: look it up, or resolve its fields and call
+/// the API, printing its progress line with `progress` as its "n/total".
+/// In dry-run mode the result is a placeholder whose ID is `number`.
+#[allow(clippy::too_many_arguments)]
+async fn perform(
+    op: &Operation,
+    mut json_body: serde_json::Value,
+    resolved_id: Option<serde_json::Value>,
+    client: &api::Client,
+    cache_dir: &Path,
+    out: &OutputConfig,
+    progress: &str,
+    slot: Slot,
+    number: usize,
+    show_progress: bool,
+    journal: Option<&RefCell<Journal>>,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    // Lookups run even in dry-run mode since they only read
+    if op.action == Action::Find {
+        return execute_find(op, &json_body, client, out, progress, show_progress).await;
+    }
+
+    // Extract story_id before resolve_entity_fields removes it.
+    let story_id = json_body.get("story_id").and_then(|v| v.as_i64());
+    resolve_entity_fields(&op.entity, &op.action, &mut json_body, client, cache_dir).await?;
+
+    if out.is_dry_run() {
+        if show_progress {
+            out_println!(out, "[{progress}] {} {}", op.action, op.entity);
+            if !json_body_is_empty(&json_body) {
+                let pretty = serde_json::to_string_pretty(&json_body)?;
+                out_println!(out, "  {}", pretty.replace('\n', "\n  "));
             }
+            out_println!(out, "");
         }
+        return Ok(dry_run_placeholder(&op.entity, number));
     }
 
-    Ok(build_result(op_results, total))
+    let response = dispatch_journaled(
+        journal,
+        slot,
+        client,
+        &op.action,
+        &op.entity,
+        resolved_id.as_ref(),
+        story_id,
+        json_body,
+    )
+    .await?;
+
+    if show_progress {
+        let action_past = action_past_tense(&op.action);
+        print_success_line(
+            out,
+            progress,
+            &action_past,
+            &op.action,
+            &op.entity,
+            &response,
+            resolved_id.as_ref(),
+        )?;
+    }
+
+    Ok(response)
 }
 
 // ── Helpers ────────────────────────────────────────────────────────
 
-/// Count total operations including repeat expansions.
+/// Count total operations including repeat expansions. for_each operations
+/// count as none until their items are known.
 fn count_operations(operations: &[Operation]) -> usize {
     operations
         .iter()
+        .filter(|op| op.for_each.is_none())
         .map(|op| op.repeat.as_ref().map(|r| r.len()).unwrap_or(1))
         .sum()
 }
@@ -462,7 +832,11 @@ fn print_confirmation_summary(
 
     // Summarize by action+entity
     let mut counts: HashMap<(String, String), usize> = HashMap::new();
-    for op in &template.operations {
+    for op in template
+        .operations
+        .iter()
+        .filter(|op| op.for_each.is_none())
+    {
         let n = op.repeat.as_ref().map(|r| r.len()).unwrap_or(1);
         *counts
             .entry((op.action.to_string(), op.entity.to_string()))
@@ -480,6 +854,17 @@ fn print_confirmation_summary(
         };
         out_println!(out, "  {action:<8} {count} {plural}");
     }
+    for op in &template.operations {
+        if let Some(for_each) = &op.for_each {
+            out_println!(
+                out,
+                "  {:<8} each of {} ({})",
+                op.action.to_string(),
+                describe_for_each(&yaml_mapping_to_json(for_each)),
+                op.entity
+            );
+        }
+    }
     out_println!(out, "");
 
     Ok(())
@@ -500,18 +885,100 @@ fn prompt_confirm() -> Result<bool, Box<dyn Error>> {
 fn build_result(op_results: Vec<OperationResult>, total: usize) -> ExecutionResult {
     let succeeded = op_results.iter().filter(|r| r.status == "success").count();
     let failed = op_results.iter().filter(|r| r.status == "failed").count();
+    let skipped = op_results.iter().filter(|r| r.status == "skipped").count();
     ExecutionResult {
         operations: op_results,
         summary: ExecutionSummary {
             total,
             succeeded,
             failed,
+            skipped,
         },
+        rollback: None,
+    }
+}
+
+/// Build the final result, first undoing what already ran when `rollback`
+/// holds the journal of a run stopped by an `on_error: rollback` failure.
+async fn conclude(
+    mut op_results: Vec<OperationResult>,
+    total: usize,
+    rollback: Option<Journal>,
+    index: impl Fn(Slot) -> usize,
+    client: &api::Client,
+    out: &OutputConfig,
+    show_progress: bool,
+) -> Result<ExecutionResult, Box<dyn Error>> {
+    op_results.sort_by_key(|r| r.index);
+    let mut result = build_result(op_results, total);
+    if let Some(journal) = rollback {
+        result.rollback = Some(journal.roll_back(index, client, out, show_progress).await?);
     }
+    Ok(result)
+}
+
+/// Substitute `$var()` throughout every operation of the template.
+pub(super) fn substitute_template_vars(template: &mut Template) -> Result<(), Box<dyn Error>> {
+    let vars = template.vars.clone().unwrap_or_default();
+    for op in &mut template.operations {
+        if let Some(fields) = &mut op.fields {
+            let mut val = serde_yaml::Value::Mapping(fields.clone());
+            substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
+            if let serde_yaml::Value::Mapping(m) = val {
+                *fields = m;
+            }
+        }
+        if let Some(repeat) = &mut op.repeat {
+            for entry in repeat.iter_mut() {
+                let mut val = serde_yaml::Value::Mapping(entry.clone());
+                substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
+                if let serde_yaml::Value::Mapping(m) = val {
+                    *entry = m;
+                }
+            }
+        }
+        if let Some(id) = &mut op.id {
+            substitute_vars(id, &vars).map_err(|errs| errs.join("; "))?;
+        }
+        if let Some(for_each) = &mut op.for_each {
+            let mut val = serde_yaml::Value::Mapping(for_each.clone());
+            substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
+            if let serde_yaml::Value::Mapping(m) = val {
+                *for_each = m;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Results keyed by alias for every resource in the state, so `$ref()` can
+/// point at already-synced resources.
+pub(super) fn seed_results(state: &SyncState) -> HashMap<String, serde_json::Value> {
+    let mut results = HashMap::new();
+    for (alias, resource) in &state.resources {
+        match resource {
+            ResourceState::Single { id, entity, .. } => {
+                results.insert(
+                    alias.clone(),
+                    serde_json::json!({"id": id, "entity_type": entity}),
+                );
+            }
+            ResourceState::Repeat {
+                entries, entity, ..
+            } => {
+                let arr: Vec<serde_json::Value> = entries
+                    .values()
+                    .map(|e| serde_json::json!({"id": e.id, "entity_type": entity}))
+                    .collect();
+                results.insert(alias.clone(), serde_json::Value::Array(arr));
+            }
+        }
+    }
+    results
 }
 
 /// Merge two YAML mappings. Values from `override_mapping` take precedence.
-fn merge_mappings(
+pub(super) fn merge_mappings(
     base: &serde_yaml::Mapping,
     override_mapping: &serde_yaml::Mapping,
 ) -> serde_yaml::Mapping {
@@ -523,7 +990,7 @@ fn merge_mappings(
 }
 
 /// Resolve entity-specific fields (members, states, etc.) in the JSON body.
-async fn resolve_entity_fields(
+pub(super) async fn resolve_entity_fields(
     entity: &Entity,
     action: &Action,
     body: &mut serde_json::Value,
@@ -795,8 +1262,33 @@ fn normalize_link_verb(verb: &str) -> String {
     }
 }
 
+/// Dispatch an API call, recording it in `journal` (if any) so it can be
+/// rolled back. Updates snapshot the entity first; a failed snapshot fails
+/// the operation rather than leaving a change that can't be undone.
+#[allow(clippy::too_many_arguments)]
+async fn dispatch_journaled(
+    journal: Option<&RefCell<Journal>>,
+    slot: Slot,
+    client: &api::Client,
+    action: &Action,
+    entity: &Entity,
+    id: Option<&serde_json::Value>,
+    story_id: Option<i64>,
+    body: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let Some(journal) = journal else {
+        return dispatch_api_call(client, action, entity, id, story_id, body).await;
+    };
+    let snapshot = Journal::snapshot(client, action, entity, id, story_id, &body).await?;
+    let response = dispatch_api_call(client, action, entity, id, story_id, body).await?;
+    journal
+        .borrow_mut()
+        .record(slot, action, entity, id, story_id, snapshot, &response);
+    Ok(response)
+}
+
 /// Dispatch an API call through the generated Progenitor client.
-async fn dispatch_api_call(
+pub(super) async fn dispatch_api_call(
     client: &api::Client,
     action: &Action,
     entity: &Entity,
@@ -1228,6 +1720,53 @@ async fn dispatch_api_call(
     }
 }
 
+/// Run a `find` operation and apply its `on_missing` policy.
+///
+/// With `on_missing: empty` a miss resolves to `{"id": null}`, so `$ref(alias)`
+/// becomes `null` in later operations.
+async fn execute_find(
+    op: &Operation,
+    criteria: &serde_json::Value,
+    client: &api::Client,
+    out: &OutputConfig,
+    progress: &str,
+    show_progress: bool,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    match find_entity(&op.entity, criteria, client).await? {
+        Some(found) => {
+            if show_progress {
+                print_success_line(
+                    out,
+                    progress,
+                    &action_past_tense(&op.action),
+                    &op.action,
+                    &op.entity,
+                    &found,
+                    None,
+                )?;
+            }
+            Ok(found)
+        }
+        None if op.on_missing == Some(MissingPolicy::Empty) => {
+            if show_progress {
+                out_println!(
+                    out,
+                    "[{progress}] No {} found with {}",
+                    op.entity,
+                    describe_criteria(criteria)
+                );
+            }
+            Ok(serde_json::json!({ "id": null }))
+        }
+        None => Err(format!(
+            "no {} found with {}",
+            op.entity,
+            describe_criteria(criteria)
+        )
+        .into()),
+    }
+}
+
 /// Extract an i64 ID from a JSON value.
 fn extract_i64_id(val: Option<&serde_json::Value>, context: &str) -> Result<i64, Box<dyn Error>> {
     val.and_then(|v| v.as_i64()).ok_or_else(|| context.into())
@@ -1256,6 +1795,7 @@ fn json_value_display(val: &serde_json::Value) -> String {
 /// Get past tense of an action for output.
 fn action_past_tense(action: &Action) -> String {
     match action {
+        Action::Find => "Found".to_string(),
         Action::Create => "Created".to_string(),
         Action::Update => "Updated".to_string(),
         Action::Delete => "Deleted".to_string(),
@@ -1271,8 +1811,7 @@ fn action_past_tense(action: &Action) -> String {
 #[allow(clippy::too_many_arguments)]
 fn print_success_line(
     out: &OutputConfig,
-    counter: usize,
-    total: usize,
+    progress: &str,
     action_past: &str,
     action: &Action,
     entity: &Entity,
@@ -1289,9 +1828,7 @@ fn print_success_line(
     if action == &Action::Comment || name.is_empty() {
         out_println!(
             out,
-            "[{}/{}] {} {} {}",
-            counter,
-            total,
+            "[{progress}] {} {} {}",
             action_past,
             entity,
             id_display
@@ -1299,9 +1836,7 @@ fn print_success_line(
     } else {
         out_println!(
             out,
-            "[{}/{}] {} {} {} - {}",
-            counter,
-            total,
+            "[{progress}] {} {} {} - {}",
             action_past,
             entity,
             id_display,
@@ -1321,9 +1856,9 @@ fn json_body_is_empty(body: &serde_json::Value) -> bool {
 }
 
 /// Generate a placeholder result for dry-run mode.
-fn dry_run_placeholder(entity: &Entity, counter: usize) -> serde_json::Value {
+fn dry_run_placeholder(entity: &Entity, id: usize) -> serde_json::Value {
     serde_json::json!({
-        "id": counter * 1000,
+        "id": id,
         "entity_type": entity.to_string(),
     })
 }
@@ -1347,28 +1882,7 @@ pub async fn execute_sync(
     confirm: bool,
 ) -> Result<ExecutionResult, Box<dyn Error>> {
     // Pre-pass: substitute all $var()
-    let vars = template.vars.clone().unwrap_or_default();
-    for op in &mut template.operations {
-        if let Some(fields) = &mut op.fields {
-            let mut val = serde_yaml::Value::Mapping(fields.clone());
-            substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
-            if let serde_yaml::Value::Mapping(m) = val {
-                *fields = m;
-            }
-        }
-        if let Some(repeat) = &mut op.repeat {
-            for entry in repeat.iter_mut() {
-                let mut val = serde_yaml::Value::Mapping(entry.clone());
-                substitute_vars(&mut val, &vars).map_err(|errs| errs.join("; "))?;
-                if let serde_yaml::Value::Mapping(m) = val {
-                    *entry = m;
-                }
-            }
-        }
-        if let Some(id) = &mut op.id {
-            substitute_vars(id, &vars).map_err(|errs| errs.join("; "))?;
-        }
-    }
+    substitute_template_vars(template)?;
 
     let total = actions.len();
     let show_progress = !out.is_machine_readable();
@@ -1381,28 +1895,8 @@ pub async fn execute_sync(
         }
     }
 
-    let mut results: HashMap<String, serde_json::Value> = HashMap::new();
-
     // Seed results from existing state so $ref() works for already-synced resources
-    for (alias, resource) in &state.resources {
-        match resource {
-            ResourceState::Single { id, entity, .. } => {
-                results.insert(
-                    alias.clone(),
-                    serde_json::json!({"id": id, "entity_type": entity}),
-                );
-            }
-            ResourceState::Repeat {
-                entries, entity, ..
-            } => {
-                let arr: Vec<serde_json::Value> = entries
-                    .values()
-                    .map(|e| serde_json::json!({"id": e.id, "entity_type": entity}))
-                    .collect();
-                results.insert(alias.clone(), serde_json::Value::Array(arr));
-            }
-        }
-    }
+    let mut results = seed_results(state);
 
     let mut op_results: Vec<OperationResult> = Vec::new();
     let doc_on_error = &template.on_error;
@@ -1455,6 +1949,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1465,6 +1960,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1520,6 +2016,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1530,6 +2027,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1595,6 +2093,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1605,6 +2104,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1667,6 +2167,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1677,6 +2178,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1705,6 +2207,7 @@ pub async fn execute_sync(
                     status: "success".to_string(),
                     result: None,
                     error: None,
+                    reason: Some(reason.clone()),
                 });
             }
 
@@ -1741,6 +2244,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1751,6 +2255,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1778,6 +2283,9 @@ pub async fn execute_sync(
 
                 match result {
                     Ok(response) => {
+                        if let Some(alias) = &op.alias {
+                            results.insert(alias.clone(), response.clone());
+                        }
                         op_results.push(OperationResult {
                             index: counter - 1,
                             action: op.action.to_string(),
@@ -1785,6 +2293,7 @@ pub async fn execute_sync(
                             status: "success".to_string(),
                             result: Some(response),
                             error: None,
+                            reason: None,
                         });
                     }
                     Err(e) => {
@@ -1795,6 +2304,7 @@ pub async fn execute_sync(
                             status: "failed".to_string(),
                             result: None,
                             error: Some(e.to_string()),
+                            reason: None,
                         });
                         if !should_continue {
                             return Ok(build_result(op_results, total));
@@ -1854,6 +2364,7 @@ pub async fn execute_sync(
                         status: "success".to_string(),
                         result: None,
                         error: None,
+                        reason: None,
                     });
                 } else {
                     if show_progress {
@@ -1875,6 +2386,7 @@ pub async fn execute_sync(
                         status: "success".to_string(),
                         result: None,
                         error: None,
+                        reason: None,
                     });
                 }
             }
@@ -1938,6 +2450,7 @@ pub async fn execute_sync(
                         status: "success".to_string(),
                         result: None,
                         error: None,
+                        reason: None,
                     });
                 } else {
                     if show_progress {
@@ -1959,6 +2472,7 @@ pub async fn execute_sync(
                         status: "success".to_string(),
                         result: None,
                         error: None,
+                        reason: None,
                     });
                 }
             }
@@ -2016,7 +2530,7 @@ async fn execute_single_create(
                 out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
             }
         }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
+        return Ok(dry_run_placeholder(&op.entity, ctx.counter * 1000));
     }
 
     let response = dispatch_api_call(
@@ -2095,7 +2609,7 @@ async fn execute_single_update(
                 out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
             }
         }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
+        return Ok(dry_run_placeholder(&op.entity, ctx.counter * 1000));
     }
 
     let response = dispatch_api_call(
@@ -2184,7 +2698,7 @@ async fn execute_entry_create(
                 out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
             }
         }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
+        return Ok(dry_run_placeholder(&op.entity, ctx.counter * 1000));
     }
 
     let response = dispatch_api_call(
@@ -2270,7 +2784,7 @@ async fn execute_entry_update(
                 out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
             }
         }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
+        return Ok(dry_run_placeholder(&op.entity, ctx.counter * 1000));
     }
 
     let response = dispatch_api_call(
@@ -2362,7 +2876,7 @@ async fn execute_side_effect(
                 out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
             }
         }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
+        return Ok(dry_run_placeholder(&op.entity, ctx.counter * 1000));
     }
 
     let response = dispatch_api_call(
@@ -2379,8 +2893,7 @@ async fn execute_side_effect(
         let action_past = action_past_tense(&op.action);
         print_success_line(
             ctx.out,
-            ctx.counter,
-            ctx.total,
+            &format!("{}/{}", ctx.counter, ctx.total),
             &action_past,
             &op.action,
             &op.entity,
@@ -2397,13 +2910,21 @@ async fn execute_passthrough(
     op: &Operation,
     ctx: &SyncExecContext<'_>,
 ) -> Result<serde_json::Value, Box<dyn Error>> {
-    let mut json_body = op
-        .fields
-        .as_ref()
+    execute_with(op, op.fields.as_ref(), op.id.as_ref(), ctx).await
+}
+
+/// Execute one instance of an operation with the given fields and id.
+async fn execute_with(
+    op: &Operation,
+    fields: Option<&serde_yaml::Mapping>,
+    id: Option<&serde_yaml::Value>,
+    ctx: &SyncExecContext<'_>,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let mut json_body = fields
         .map(yaml_mapping_to_json)
         .unwrap_or(serde_json::json!({}));
 
-    let resolved_id = if let Some(id_val) = &op.id {
+    let resolved_id = if let Some(id_val) = id {
         let mut json_id = yaml_to_json(id_val);
         resolve_refs(&mut json_id, ctx.results).map_err(|e| -> Box<dyn Error> { e.into() })?;
         Some(json_id)
@@ -2412,63 +2933,28 @@ async fn execute_passthrough(
     };
 
     resolve_refs(&mut json_body, ctx.results).map_err(|e| -> Box<dyn Error> { e.into() })?;
-    let story_id = json_body.get("story_id").and_then(|v| v.as_i64());
-    resolve_entity_fields(
-        &op.entity,
-        &op.action,
-        &mut json_body,
+    perform(
+        op,
+        json_body,
+        resolved_id,
         ctx.client,
         ctx.cache_dir,
+        ctx.out,
+        &format!("{}/{}", ctx.counter, ctx.total),
+        // Sync numbers its actions, which have no repeat entries of their own
+        (ctx.counter - 1, 0),
+        ctx.counter * 1000,
+        ctx.show_progress,
+        None,
     )
-    .await?;
-
-    if ctx.out.is_dry_run() {
-        if ctx.show_progress {
-            out_println!(
-                ctx.out,
-                "[{}/{}] {} {}",
-                ctx.counter,
-                ctx.total,
-                op.action,
-                op.entity
-            );
-            if !json_body_is_empty(&json_body) {
-                let pretty = serde_json::to_string_pretty(&json_body)?;
-                out_println!(ctx.out, "  {}", pretty.replace('\n', "\n  "));
-            }
-        }
-        return Ok(dry_run_placeholder(&op.entity, ctx.counter));
-    }
-
-    let response = dispatch_api_call(
-        ctx.client,
-        &op.action,
-        &op.entity,
-        resolved_id.as_ref(),
-        story_id,
-        json_body,
-    )
-    .await?;
-
-    if ctx.show_progress {
-        let action_past = action_past_tense(&op.action);
-        print_success_line(
-            ctx.out,
-            ctx.counter,
-            ctx.total,
-            &action_past,
-            &op.action,
-            &op.entity,
-            &response,
-            resolved_id.as_ref(),
-        )?;
-    }
-
-    Ok(response)
+    .await
 }
 
 /// Find the repeat entry matching the given key.
-fn find_repeat_entry(op: &Operation, key: &str) -> Result<serde_yaml::Mapping, Box<dyn Error>> {
+pub(super) fn find_repeat_entry(
+    op: &Operation,
+    key: &str,
+) -> Result<serde_yaml::Mapping, Box<dyn Error>> {
     let repeat = op.repeat.as_ref().ok_or("expected repeat block")?;
     for entry in repeat {
         if let Some(serde_yaml::Value::String(k)) =
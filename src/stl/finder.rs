@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::api;
+
+use super::resolver::resolve_refs;
+use super::types::Entity;
+
+/// Number of search results fetched when looking an entity up.
+const SEARCH_PAGE_SIZE: i64 = 25;
+
+/// Entities a `find` operation can look up.
+pub const FINDABLE_ENTITIES: &[Entity] = &[
+    Entity::Story,
+    Entity::Epic,
+    Entity::Iteration,
+    Entity::Label,
+    Entity::Group,
+    Entity::Objective,
+];
+
+/// Entities that support `query` (Shortcut search syntax) in a `find`.
+pub const SEARCHABLE_ENTITIES: &[Entity] = &[
+    Entity::Story,
+    Entity::Epic,
+    Entity::Iteration,
+    Entity::Objective,
+];
+
+/// Keys naming the source of a `for_each`; exactly one must be given.
+pub const FOR_EACH_SOURCES: &[&str] = &["query", "epic", "iteration", "items"];
+
+/// Largest page the search API returns.
+const FOR_EACH_PAGE_SIZE: i64 = 250;
+
+/// Fetch the items a `for_each` operation runs once per.
+///
+/// `query` is a story search (following every page), `epic` and `iteration`
+/// list their stories, and `items: $ref(alias)` reuses the results of an
+/// earlier repeat or for_each operation. `done: true|false` then keeps only
+/// completed or unfinished stories.
+pub async fn for_each_items(
+    source: &serde_json::Value,
+    results: &HashMap<String, serde_json::Value>,
+    client: &api::Client,
+) -> Result<Vec<serde_json::Value>, Box<dyn Error>> {
+    let mut items = if let Some(items_ref) = source.get("items") {
+        let expr = items_ref.as_str().unwrap_or_default().trim();
+        let alias = expr
+            .strip_prefix("$ref(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| format!("for_each items must be a $ref(alias), got '{expr}'"))?;
+        match results.get(alias) {
+            Some(serde_json::Value::Array(arr)) => arr.clone(),
+            Some(_) => {
+                return Err(format!(
+                    "for_each items: alias '{alias}' is not a repeat or for_each result"
+                )
+                .into());
+            }
+            None => return Err(format!("for_each items: alias '{alias}' not found").into()),
+        }
+    } else {
+        let mut source = source.clone();
+        resolve_refs(&mut source, results)?;
+        if let Some(query) = source.get("query").and_then(|v| v.as_str()) {
+            search_all_stories(query, client).await?
+        } else if let Some(epic) = source.get("epic") {
+            let id = epic
+                .as_i64()
+                .ok_or_else(|| format!("for_each epic must be an epic ID, got {epic}"))?;
+            let stories = client
+                .list_epic_stories()
+                .epic_public_id(id)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to list epic stories: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            into_items(serde_json::to_value(&*stories)?)
+        } else if let Some(iteration) = source.get("iteration") {
+            let id = iteration.as_i64().ok_or_else(|| {
+                format!("for_each iteration must be an iteration ID, got {iteration}")
+            })?;
+            let stories = client
+                .list_iteration_stories()
+                .iteration_public_id(id)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to list iteration stories: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            into_items(serde_json::to_value(&*stories)?)
+        } else {
+            return Err(format!("for_each requires one of {}", FOR_EACH_SOURCES.join(", ")).into());
+        }
+    };
+
+    if let Some(done) = source.get("done").and_then(|v| v.as_bool()) {
+        items.retain(|item| item.get("completed").and_then(|v| v.as_bool()) == Some(done));
+    }
+    Ok(items)
+}
+
+/// Describe the source of a `for_each` for the confirmation summary and
+/// progress output.
+pub fn describe_for_each(source: &serde_json::Value) -> String {
+    let display = |v: &serde_json::Value| match v {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let mut description = if let Some(query) = source.get("query") {
+        format!("stories matching '{}'", display(query))
+    } else if let Some(epic) = source.get("epic") {
+        format!("stories in epic {}", display(epic))
+    } else if let Some(iteration) = source.get("iteration") {
+        format!("stories in iteration {}", display(iteration))
+    } else if let Some(items) = source.get("items") {
+        format!("results of {}", display(items))
+    } else {
+        "no source".to_string()
+    };
+    match source.get("done").and_then(|v| v.as_bool()) {
+        Some(true) => description.push_str(" (done)"),
+        Some(false) => description.push_str(" (not done)"),
+        None => {}
+    }
+    description
+}
+
+/// Run a story search and follow every page of results.
+async fn search_all_stories(
+    query: &str,
+    client: &api::Client,
+) -> Result<Vec<serde_json::Value>, Box<dyn Error>> {
+    let query = if query.contains("is:archived") {
+        query.to_string()
+    } else {
+        format!("{query} !is:archived")
+    };
+    let query = query
+        .parse::<api::types::SearchStoriesQuery>()
+        .map_err(|e| format!("Invalid query: {e}"))?;
+
+    let mut items = Vec::new();
+    let mut next: Option<String> = None;
+    loop {
+        let mut req = client
+            .search_stories()
+            .query(query.clone())
+            .page_size(FOR_EACH_PAGE_SIZE);
+        if let Some(token) = &next {
+            req = req.next(token.clone());
+        }
+        let page = req.send().await.map_err(|e| {
+            format!(
+                "Failed to search stories: {}",
+                crate::api::format_api_error(&e)
+            )
+        })?;
+        items.extend(into_items(serde_json::to_value(&page.data)?));
+        match &page.next {
+            Some(token) if !token.is_empty() && !page.data.is_empty() => next = Some(token.clone()),
+            _ => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Look up an existing entity for a `find` operation.
+///
+/// `criteria` holds either `name` (exact, case-insensitive; groups also match
+/// their mention name) or `query` (Shortcut search syntax, best match wins).
+/// Archived entities are ignored. Returns `None` when nothing matches and an
+/// error when a name matches more than one entity.
+pub async fn find_entity(
+    entity: &Entity,
+    criteria: &serde_json::Value,
+    client: &api::Client,
+) -> Result<Option<serde_json::Value>, Box<dyn Error>> {
+    let name = criteria.get("name").and_then(|v| v.as_str());
+    let query = criteria.get("query").and_then(|v| v.as_str());
+
+    match (name, query) {
+        (Some(name), _) => {
+            let candidates = match entity {
+                // Stories can't be listed workspace-wide; search by name
+                // instead, as a phrase so punctuation in it is not parsed as
+                // search syntax, and read every page for exact matches.
+                Entity::Story => {
+                    let phrase = name.replace('"', " ");
+                    search_all_stories(&format!("name:\"{}\"", phrase.trim()), client).await?
+                }
+                _ => list(entity, client).await?,
+            };
+            pick_by_name(entity, name, candidates)
+        }
+        (None, Some(query)) => Ok(search(entity, query, client)
+            .await?
+            .into_iter()
+            .find(|v| !is_archived(v))),
+        (None, None) => Err(format!("find {entity} requires 'name' or 'query'").into()),
+    }
+}
+
+/// Describe the criteria of a `find` for progress and error messages.
+pub fn describe_criteria(criteria: &serde_json::Value) -> String {
+    if let Some(name) = criteria.get("name").and_then(|v| v.as_str()) {
+        format!("name '{name}'")
+    } else if let Some(query) = criteria.get("query").and_then(|v| v.as_str()) {
+        format!("query '{query}'")
+    } else {
+        "no criteria".to_string()
+    }
+}
+
+fn is_archived(value: &serde_json::Value) -> bool {
+    value.get("archived").and_then(|v| v.as_bool()) == Some(true)
+}
+
+fn pick_by_name(
+    entity: &Entity,
+    name: &str,
+    candidates: Vec<serde_json::Value>,
+) -> Result<Option<serde_json::Value>, Box<dyn Error>> {
+    let wanted = name.trim_start_matches('@');
+    let mut matches: Vec<serde_json::Value> = candidates
+        .into_iter()
+        .filter(|v| !is_archived(v))
+        .filter(|v| {
+            let field_matches = |field: &str| {
+                v.get(field)
+                    .and_then(|f| f.as_str())
+                    .is_some_and(|f| f.eq_ignore_ascii_case(name))
+            };
+            field_matches("name")
+                || (*entity == Entity::Group
+                    && v.get("mention_name")
+                        .and_then(|f| f.as_str())
+                        .is_some_and(|f| f.eq_ignore_ascii_case(wanted)))
+        })
+        .collect();
+
+    match matches.len() {
+        0 => Ok(None),
+        1 => Ok(matches.pop()),
+        n => {
+            let ids: Vec<String> = matches
+                .iter()
+                .filter_map(|v| v.get("id"))
+                .map(|id| match id {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            Err(format!(
+                "{n} {entity}s are named '{name}' ({}); use a query or an id instead",
+                ids.join(", ")
+            )
+            .into())
+        }
+    }
+}
+
+async fn list(
+    entity: &Entity,
+    client: &api::Client,
+) -> Result<Vec<serde_json::Value>, Box<dyn Error>> {
+    let value = match entity {
+        Entity::Epic => {
+            let epics = client.list_epics().send().await.map_err(|e| {
+                format!("Failed to list epics: {}", crate::api::format_api_error(&e))
+            })?;
+            serde_json::to_value(&*epics)?
+        }
+        Entity::Iteration => {
+            let iterations = client.list_iterations().send().await.map_err(|e| {
+                format!(
+                    "Failed to list iterations: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+            serde_json::to_value(&*iterations)?
+        }
+        Entity::Label => {
+            let labels = client.list_labels().slim(true).send().await.map_err(|e| {
+                format!(
+                    "Failed to list labels: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+            serde_json::to_value(&*labels)?
+        }
+        Entity::Group => {
+            let groups = client.list_groups().send().await.map_err(|e| {
+                format!(
+                    "Failed to list groups: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+            serde_json::to_value(&*groups)?
+        }
+        Entity::Objective => {
+            let objectives = client.list_objectives().send().await.map_err(|e| {
+                format!(
+                    "Failed to list objectives: {}",
+                    crate::api::format_api_error(&e)
+                )
+            })?;
+            serde_json::to_value(&*objectives)?
+        }
+        _ => return Err(format!("find is not supported for {entity}").into()),
+    };
+    Ok(into_items(value))
+}
+
+async fn search(
+    entity: &Entity,
+    query: &str,
+    client: &api::Client,
+) -> Result<Vec<serde_json::Value>, Box<dyn Error>> {
+    let value = match entity {
+        Entity::Story => {
+            let query = query
+                .parse::<api::types::SearchStoriesQuery>()
+                .map_err(|e| format!("Invalid query: {e}"))?;
+            let results = client
+                .search_stories()
+                .query(query)
+                .page_size(SEARCH_PAGE_SIZE)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to search stories: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            serde_json::to_value(&results.data)?
+        }
+        Entity::Epic => {
+            let query = query
+                .parse::<api::types::SearchEpicsQuery>()
+                .map_err(|e| format!("Invalid query: {e}"))?;
+            let results = client
+                .search_epics()
+                .query(query)
+                .page_size(SEARCH_PAGE_SIZE)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to search epics: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            serde_json::to_value(&results.data)?
+        }
+        Entity::Iteration => {
+            let query = query
+                .parse::<api::types::SearchIterationsQuery>()
+                .map_err(|e| format!("Invalid query: {e}"))?;
+            let results = client
+                .search_iterations()
+                .query(query)
+                .page_size(SEARCH_PAGE_SIZE)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to search iterations: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            serde_json::to_value(&results.data)?
+        }
+        Entity::Objective => {
+            let query = query
+                .parse::<api::types::SearchObjectivesQuery>()
+                .map_err(|e| format!("Invalid query: {e}"))?;
+            let results = client
+                .search_objectives()
+                .query(query)
+                .page_size(SEARCH_PAGE_SIZE)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to search objectives: {}",
+                        crate::api::format_api_error(&e)
+                    )
+                })?;
+            serde_json::to_value(&results.data)?
+        }
+        _ => return Err(format!("find {entity} does not support 'query'; use 'name'").into()),
+    };
+    Ok(into_items(value))
+}
+
+fn into_items(value: serde_json::Value) -> Vec<serde_json::Value> {
+    match value {
+        serde_json::Value::Array(items) => items,
+        _ => Vec::new(),
+    }
+}
+
+/// Fetch the current state of an entity by id. Tasks also need their story's id.
+pub async fn fetch_entity(
+    client: &api::Client,
+    entity: &Entity,
+    id: &serde_json::Value,
+    story_id: Option<i64>,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+    let int_id = || id.as_i64().ok_or("expected a numeric id");
+    let uuid_id = || -> Result<uuid::Uuid, Box<dyn Error>> {
+        Ok(id.as_str().ok_or("expected a UUID id")?.parse()?)
+    };
+    macro_rules! get {
+        ($call:ident, $param:ident, $id:expr) => {
+            serde_json::to_value(
+                &*client
+                    .$call()
+                    .$param($id)
+                    .send()
+                    .await
+                    .map_err(|e| api::format_api_error(&e))?,
+            )?
+        };
+    }
+    Ok(match entity {
+        Entity::Story => get!(get_story, story_public_id, int_id()?),
+        Entity::Epic => get!(get_epic, epic_public_id, int_id()?),
+        Entity::Iteration => get!(get_iteration, iteration_public_id, int_id()?),
+        Entity::Label => get!(get_label, label_public_id, int_id()?),
+        Entity::Objective => get!(get_objective, objective_public_id, int_id()?),
+        Entity::Milestone => get!(get_milestone, milestone_public_id, int_id()?),
+        Entity::Category => get!(get_category, category_public_id, int_id()?),
+        Entity::Project => get!(get_project, project_public_id, int_id()?),
+        Entity::Group => get!(get_group, group_public_id, uuid_id()?),
+        Entity::Document => get!(get_doc, doc_public_id, uuid_id()?),
+        Entity::Task => {
+            let story_id = story_id.ok_or("the task's story_id is unknown")?;
+            serde_json::to_value(
+                &*client
+                    .get_task()
+                    .story_public_id(story_id)
+                    .task_public_id(int_id()?)
+                    .send()
+                    .await
+                    .map_err(|e| api::format_api_error(&e))?,
+            )?
+        }
+        Entity::Comment | Entity::StoryLink => {
+            return Err(format!("{entity} cannot be updated").into());
+        }
+    })
+}
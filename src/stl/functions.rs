@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, SecondsFormat, Utc};
+
+use super::resolver::yaml_value_to_string;
+
+/// Deepest allowed nesting of calls inside one `$fn()`.
+const MAX_DEPTH: usize = 16;
+
+/// A parsed `$fn()` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(String),
+    Var(String),
+    Call(Function, Vec<Expr>),
+}
+
+/// The fixed function library. There is deliberately no way to reach the
+/// filesystem, network or shell from an expression, and `env()` cannot read
+/// the API token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Now,
+    Today,
+    DateAdd,
+    FormatDate,
+    Upper,
+    Lower,
+    Slug,
+    Env,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "now" => Function::Now,
+            "today" => Function::Today,
+            "date_add" => Function::DateAdd,
+            "format_date" => Function::FormatDate,
+            "upper" => Function::Upper,
+            "lower" => Function::Lower,
+            "slug" => Function::Slug,
+            "env" => Function::Env,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Function::Now => "now",
+            Function::Today => "today",
+            Function::DateAdd => "date_add",
+            Function::FormatDate => "format_date",
+            Function::Upper => "upper",
+            Function::Lower => "lower",
+            Function::Slug => "slug",
+            Function::Env => "env",
+        }
+    }
+
+    /// Minimum and maximum number of arguments.
+    fn arity(self) -> (usize, usize) {
+        match self {
+            Function::Now | Function::Today => (0, 0),
+            Function::DateAdd | Function::FormatDate => (2, 2),
+            Function::Upper | Function::Lower | Function::Slug => (1, 1),
+            Function::Env => (1, 2),
+        }
+    }
+}
+
+/// Find the next `$fn(...)` in `s` at or after `from`.
+///
+/// Returns the byte range of the whole call, including `$fn(` and the closing
+/// parenthesis, or an error if the call is never closed.
+pub fn next_call(s: &str, from: usize) -> Option<Result<(usize, usize), String>> {
+    let start = from + s[from..].find("$fn(")?;
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in s[start + 3..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(Ok((start, start + 3 + i + 1)));
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(Err(format!("unterminated {}", &s[start..])))
+}
+
+/// The expression inside a `$fn(...)` call returned by [`next_call`].
+pub fn inner(call: &str) -> &str {
+    &call[4..call.len() - 1]
+}
+
+/// Parse the expression inside `$fn(...)`.
+///
+/// Arguments are calls (`upper(...)`), `$var(name)`, quoted strings
+/// (`"a, b"` or `'a, b'`) or bare text up to the next `,` or `)` (`14d`).
+/// Unknown functions and wrong argument counts are parse errors.
+pub fn parse(expr: &str) -> Result<Expr, String> {
+    let mut parser = Parser { src: expr, pos: 0 };
+    let parsed = parser.expr(0)?;
+    parser.skip_ws();
+    if parser.pos < expr.len() {
+        return Err(format!(
+            "unexpected '{}' after expression",
+            &expr[parser.pos..]
+        ));
+    }
+    match parsed {
+        Expr::Call(..) => Ok(parsed),
+        _ => Err(format!("expected a function call, got '{}'", expr.trim())),
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expr(&mut self, depth: usize) -> Result<Expr, String> {
+        if depth > MAX_DEPTH {
+            return Err("expression is nested too deeply".to_string());
+        }
+        self.skip_ws();
+        let rest = self.rest();
+
+        if rest.starts_with("$ref(") {
+            return Err(
+                "$ref() cannot be used inside $fn(); only $var() values are available".to_string(),
+            );
+        }
+
+        if let Some(after) = rest.strip_prefix("$var(") {
+            let end = after
+                .find(')')
+                .ok_or_else(|| format!("unterminated $var({after}"))?;
+            let name = after[..end].to_string();
+            self.pos += 5 + end + 1;
+            return Ok(Expr::Var(name));
+        }
+
+        if let Some(q) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let end = rest[1..]
+                .find(q)
+                .ok_or_else(|| format!("unterminated string {rest}"))?;
+            let value = rest[1..1 + end].to_string();
+            self.pos += end + 2;
+            return Ok(Expr::Literal(value));
+        }
+
+        let ident_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if ident_len > 0 && rest[ident_len..].starts_with('(') {
+            let name = &rest[..ident_len];
+            let function =
+                Function::from_name(name).ok_or_else(|| format!("unknown function '{name}'"))?;
+            self.pos += ident_len + 1;
+            let args = self.args(depth)?;
+            let (min, max) = function.arity();
+            if args.len() < min || args.len() > max {
+                let expected = if min == max {
+                    format!("{min} argument{}", if min == 1 { "" } else { "s" })
+                } else {
+                    format!("{min} to {max} arguments")
+                };
+                return Err(format!("{name}() takes {expected}, got {}", args.len()));
+            }
+            return Ok(Expr::Call(function, args));
+        }
+
+        let len = rest.find([',', '(', ')']).unwrap_or(rest.len());
+        let text = rest[..len].trim_end();
+        if text.is_empty() {
+            return Err(match rest.chars().next() {
+                Some(c) => format!("unexpected '{c}'"),
+                None => "unexpected end of expression".to_string(),
+            });
+        }
+        self.pos += len;
+        Ok(Expr::Literal(text.to_string()))
+    }
+
+    /// Parse a comma-separated argument list up to and including `)`.
+    fn args(&mut self, depth: usize) -> Result<Vec<Expr>, String> {
+        let mut args = Vec::new();
+        self.skip_ws();
+        if let Some(after) = self.rest().strip_prefix(')') {
+            self.pos = self.src.len() - after.len();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expr(depth + 1)?);
+            self.skip_ws();
+            match self.rest().chars().next() {
+                Some(',') => self.pos += 1,
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(args);
+                }
+                _ => return Err("expected ',' or ')' in argument list".to_string()),
+            }
+        }
+    }
+}
+
+/// Collect the `$var()` names used by an expression.
+pub fn references<'a>(expr: &'a Expr, vars: &mut Vec<&'a str>) {
+    match expr {
+        Expr::Var(name) => vars.push(name),
+        Expr::Call(_, args) => {
+            for arg in args {
+                references(arg, vars);
+            }
+        }
+        Expr::Literal(_) => {}
+    }
+}
+
+/// Evaluate an expression to a string.
+pub fn evaluate(expr: &Expr, vars: &HashMap<String, serde_yaml::Value>) -> Result<String, String> {
+    match expr {
+        Expr::Literal(s) => Ok(s.clone()),
+        Expr::Var(name) => vars
+            .get(name)
+            .map(yaml_value_to_string)
+            .ok_or_else(|| format!("undeclared variable '{name}'")),
+        Expr::Call(function, args) => {
+            let args = args
+                .iter()
+                .map(|arg| evaluate(arg, vars))
+                .collect::<Result<Vec<_>, _>>()?;
+            call(*function, &args).map_err(|e| format!("{}(): {e}", function.name()))
+        }
+    }
+}
+
+fn call(function: Function, args: &[String]) -> Result<String, String> {
+    match function {
+        Function::Now => Ok(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)),
+        Function::Today => Ok(Utc::now().date_naive().to_string()),
+        Function::DateAdd => {
+            let offset = parse_duration(&args[1])?;
+            match parse_date(&args[0])? {
+                DateValue::Date(date) => date
+                    .checked_add_signed(offset)
+                    .map(|d| d.to_string())
+                    .ok_or_else(|| "date out of range".to_string()),
+                DateValue::DateTime(dt) => dt
+                    .checked_add_signed(offset)
+                    .map(|d| d.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+                    .ok_or_else(|| "date out of range".to_string()),
+            }
+        }
+        Function::FormatDate => {
+            let format = &args[1];
+            let items: Vec<Item> = StrftimeItems::new(format).collect();
+            if items.iter().any(|item| matches!(item, Item::Error)) {
+                return Err(format!("invalid format '{format}'"));
+            }
+            Ok(match parse_date(&args[0])? {
+                DateValue::Date(date) => date.format_with_items(items.into_iter()).to_string(),
+                DateValue::DateTime(dt) => dt.format_with_items(items.into_iter()).to_string(),
+            })
+        }
+        Function::Upper => Ok(args[0].to_uppercase()),
+        Function::Lower => Ok(args[0].to_lowercase()),
+        Function::Slug => Ok(slug(&args[0])),
+        // A shared template or include could otherwise copy the token into
+        // an entity
+        Function::Env if args[0] == crate::api::SHORTCUT_API_TOKEN_ENV => Err(format!(
+            "environment variable '{}' cannot be read from templates",
+            args[0]
+        )),
+        Function::Env => match (std::env::var(&args[0]), args.get(1)) {
+            (Ok(value), _) => Ok(value),
+            (Err(_), Some(default)) => Ok(default.clone()),
+            (Err(_), None) => Err(format!("environment variable '{}' is not set", args[0])),
+        },
+    }
+}
+
+enum DateValue {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+}
+
+fn parse_date(s: &str) -> Result<DateValue, String> {
+    let s = s.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(DateValue::Date(date));
+    }
+    DateTime::parse_from_rfc3339(s)
+        .map(DateValue::DateTime)
+        .map_err(|_| format!("expected a date (YYYY-MM-DD) or RFC 3339 timestamp, got '{s}'"))
+}
+
+/// Parse an offset like `14d`, `2w` or `-3d`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let invalid = || format!("expected a duration like 14d or 2w, got '{s}'");
+    let Some((unit_start, _)) = s.char_indices().last() else {
+        return Err(invalid());
+    };
+    let (count, unit) = s.split_at(unit_start);
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    match unit {
+        "d" => Duration::try_days(count),
+        "w" => Duration::try_weeks(count),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+/// Lowercase, with runs of anything but ASCII letters and digits collapsed to
+/// a single `-`.
+fn slug(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
@@ -0,0 +1,529 @@
+//! A small Language Server for STL templates over stdio.
+//!
+//! Supports full-document sync, diagnostics from the parser and validator,
+//! completion (operation keys, actions, entities, entity fields, `$ref()`
+//! aliases and `$var()` names) and go-to-definition for `$ref()` and
+//! `$var()`.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+use serde_json::{Value, json};
+
+use super::parser;
+use super::resolver::split_ref;
+use super::span::{KeySpan, Position, SourceMap};
+use super::types::{Action, Entity};
+use super::validator::{self, ValidationError};
+
+const OPERATION_KEYS: &[&str] = &[
+    "action",
+    "entity",
+    "alias",
+    "id",
+    "fields",
+    "repeat",
+    "for_each",
+    "when",
+    "on_error",
+    "on_missing",
+];
+const ACTIONS: &[&str] = &[
+    "find", "create", "update", "delete", "comment", "link", "unlink", "check", "uncheck",
+];
+const ENTITIES: &[&str] = &[
+    "story",
+    "epic",
+    "iteration",
+    "label",
+    "objective",
+    "milestone",
+    "category",
+    "group",
+    "document",
+    "project",
+    "task",
+    "comment",
+    "story_link",
+];
+
+// CompletionItemKind values from the LSP specification
+const KIND_FIELD: u32 = 5;
+const KIND_VARIABLE: u32 = 6;
+const KIND_PROPERTY: u32 = 10;
+const KIND_REFERENCE: u32 = 18;
+const KIND_ENUM_MEMBER: u32 = 20;
+
+/// Serve LSP requests from `input` until `exit`, writing to `output`.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(body) = read_message(&mut input)? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                // The id can't be known, so the reply carries a null one
+                send(
+                    &mut output,
+                    &json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": { "code": -32700, "message": format!("Parse error: {e}") },
+                    }),
+                )?;
+                continue;
+            }
+        };
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id").cloned();
+
+        let result = match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": ["(", " "] },
+                    "definitionProvider": true,
+                },
+                "serverInfo": { "name": "shortcut-stl", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => Some(Value::Null),
+            "exit" => return Ok(()),
+            "textDocument/didOpen" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                documents.insert(uri.to_string(), text.to_string());
+                publish_diagnostics(&mut output, uri, text)?;
+                None
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                // Full sync: the last change holds the whole document
+                if let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    documents.insert(uri.to_string(), text.to_string());
+                    publish_diagnostics(&mut output, uri, text)?;
+                }
+                None
+            }
+            "textDocument/didSave" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                if let Some(text) = documents.get(uri) {
+                    publish_diagnostics(&mut output, uri, text)?;
+                }
+                None
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                documents.remove(uri);
+                send(
+                    &mut output,
+                    &notification(
+                        "textDocument/publishDiagnostics",
+                        json!({ "uri": uri, "diagnostics": [] }),
+                    ),
+                )?;
+                None
+            }
+            "textDocument/completion" | "textDocument/definition" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = documents.get(uri).map(String::as_str).unwrap_or_default();
+                let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+                let character = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+                let cursor = to_position(text, line, character);
+                Some(if method == "textDocument/completion" {
+                    Value::Array(completions(text, &uri_to_path(uri), cursor))
+                } else {
+                    definition(text, &uri_to_path(uri), cursor).unwrap_or(Value::Null)
+                })
+            }
+            _ => {
+                if let Some(id) = &id {
+                    send(
+                        &mut output,
+                        &json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": -32601, "message": format!("Method not found: {method}") },
+                        }),
+                    )?;
+                }
+                None
+            }
+        };
+
+        if let (Some(id), Some(result)) = (id, result) {
+            send(
+                &mut output,
+                &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the body of one `Content-Length`-framed message. Returns `None` at
+/// end of input.
+fn read_message(input: &mut impl BufRead) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let length = length.ok_or("LSP message is missing Content-Length")?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn send(output: &mut impl Write, message: &Value) -> Result<(), Box<dyn Error>> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()?;
+    Ok(())
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn publish_diagnostics(
+    output: &mut impl Write,
+    uri: &str,
+    text: &str,
+) -> Result<(), Box<dyn Error>> {
+    let diagnostics = diagnostics(text, &uri_to_path(uri));
+    send(
+        output,
+        &notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        ),
+    )
+}
+
+/// Parse and validate a document. Problems outside the document itself
+/// (template-level errors, errors in included files) are reported on its
+/// first line.
+pub fn diagnostics(text: &str, path: &str) -> Vec<Value> {
+    let errors = match parser::parse_source(text, path) {
+        Ok(template) => validator::validate(&template),
+        Err(e) => {
+            let message = e.to_string();
+            let at = parse_error_position(&message).unwrap_or(Position { line: 1, column: 1 });
+            return vec![diagnostic(text, at, &message)];
+        }
+    };
+    errors
+        .iter()
+        .map(|err| match in_document(err, path) {
+            Some(at) => diagnostic(text, at, &err.message),
+            None => diagnostic(text, Position { line: 1, column: 1 }, &err.to_string()),
+        })
+        .collect()
+}
+
+fn in_document(err: &ValidationError, path: &str) -> Option<Position> {
+    err.origin
+        .as_ref()
+        .filter(|origin| origin.file == path)
+        .and(err.position)
+}
+
+/// Pull "at line L column C" out of a serde_yaml error.
+fn parse_error_position(message: &str) -> Option<Position> {
+    if message.contains("In included file") {
+        return None;
+    }
+    let rest = &message[message.rfind("at line ")? + 8..];
+    let (line, rest) = rest.split_once(" column ")?;
+    let column: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    Some(Position {
+        line: line.parse().ok()?,
+        column: column.parse().ok()?,
+    })
+}
+
+/// A diagnostic covering the word at `at`.
+fn diagnostic(text: &str, at: Position, message: &str) -> Value {
+    let line = text.lines().nth(at.line - 1).unwrap_or_default();
+    let start = at.column - 1;
+    let word: String = line
+        .chars()
+        .skip(start)
+        .take_while(|c| !c.is_whitespace())
+        .collect();
+    let len = word.trim_end_matches(':').chars().count().max(1);
+    json!({
+        "range": {
+            "start": lsp_position(text, at),
+            "end": lsp_position(text, Position { column: at.column + len, ..at }),
+        },
+        "severity": 1,
+        "source": "shortcut",
+        "message": message,
+    })
+}
+
+/// Completion items for the cursor position.
+pub fn completions(text: &str, path: &str, cursor: Position) -> Vec<Value> {
+    let line = text.lines().nth(cursor.line - 1).unwrap_or_default();
+    let before: String = line.chars().take(cursor.column - 1).collect();
+    let map = SourceMap::new(text);
+
+    if open_call(&before, "$ref(") {
+        return aliases(text, path, &map)
+            .into_iter()
+            .map(|(alias, _)| item(&alias, KIND_REFERENCE))
+            .collect();
+    }
+    if open_call(&before, "$var(") {
+        return var_spans(&map)
+            .map(|(name, _)| item(name, KIND_VARIABLE))
+            .collect();
+    }
+
+    let trimmed = before.trim_start();
+    let indent = before.len() - trimmed.len();
+    let (in_item, trimmed) = match trimmed.strip_prefix("- ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, trimmed),
+    };
+
+    // Values of `key: ` on the current line
+    if let Some((key, _)) = trimmed.split_once(':') {
+        let values: &[&str] = match key.trim() {
+            "action" => ACTIONS,
+            "entity" => ENTITIES,
+            "on_error" => &["continue", "rollback"],
+            "on_missing" => &["fail", "empty"],
+            _ => &[],
+        };
+        return values.iter().map(|v| item(v, KIND_ENUM_MEMBER)).collect();
+    }
+
+    let parent = enclosing(&map, cursor.line, indent, in_item);
+    let parent = parent.map(|span| span.path.as_str()).unwrap_or_default();
+    let parts: Vec<&str> = parent.split('.').collect();
+    match parts.as_slice() {
+        ["operations"] if in_item => keys(OPERATION_KEYS),
+        ["operations", _] if !in_item => keys(OPERATION_KEYS),
+        ["operations", index, "fields"] | ["operations", index, "repeat", _] => {
+            let value = |key: &str| {
+                map.get(&format!("operations.{index}.{key}"))
+                    .and_then(|span| span.value.as_ref())
+                    .map(|(_, v)| v.clone())
+                    .unwrap_or_default()
+            };
+            let action = serde_yaml::from_str::<Action>(&value("action"));
+            let entity = serde_yaml::from_str::<Entity>(&value("entity"));
+            match (action, entity) {
+                (Ok(action), Ok(entity)) => validator::allowed_fields(&action, &entity)
+                    .iter()
+                    .map(|f| item(f, KIND_FIELD))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `before` ends inside an unclosed `prefix...(`.
+fn open_call(before: &str, prefix: &str) -> bool {
+    before
+        .rfind(prefix)
+        .is_some_and(|start| !before[start..].contains(')'))
+}
+
+/// The innermost key or item above `line` that contains something indented
+/// `indent` columns. A new sequence item may sit at the same indent as its
+/// parent key.
+fn enclosing(map: &SourceMap, line: usize, indent: usize, in_item: bool) -> Option<&KeySpan> {
+    map.spans()
+        .iter()
+        .rev()
+        .filter(|span| span.key.line < line)
+        .find(|span| {
+            let column = span.key.column - 1;
+            let is_item = span
+                .path
+                .rsplit('.')
+                .next()
+                .is_some_and(|p| p.parse::<usize>().is_ok());
+            column < indent || (in_item && column == indent && !is_item)
+        })
+}
+
+fn keys(names: &[&str]) -> Vec<Value> {
+    names.iter().map(|k| item(k, KIND_PROPERTY)).collect()
+}
+
+fn item(label: &str, kind: u32) -> Value {
+    json!({ "label": label, "kind": kind })
+}
+
+/// Aliases visible in the document and where they are defined. Uses the
+/// parsed template when it parses (so included aliases are found), and the
+/// raw source otherwise.
+fn aliases(text: &str, path: &str, map: &SourceMap) -> Vec<(String, (String, Position))> {
+    if let Ok(template) = parser::parse_source(text, path) {
+        return template
+            .operations
+            .iter()
+            .filter_map(|op| {
+                let alias = op.alias.clone()?;
+                let origin = op.origin.as_ref()?;
+                let (at, _) = origin
+                    .spans
+                    .iter()
+                    .find(|s| s.path == "alias")?
+                    .value
+                    .clone()?;
+                Some((alias, (origin.file.clone(), at)))
+            })
+            .collect();
+    }
+    map.spans()
+        .iter()
+        .filter(|span| {
+            let parts: Vec<&str> = span.path.split('.').collect();
+            matches!(parts.as_slice(), ["operations", _, "alias"])
+        })
+        .filter_map(|span| {
+            let (at, alias) = span.value.clone()?;
+            Some((alias, (path.to_string(), at)))
+        })
+        .collect()
+}
+
+fn var_spans(map: &SourceMap) -> impl Iterator<Item = (&str, &KeySpan)> {
+    map.spans().iter().filter_map(|span| {
+        let name = span.path.strip_prefix("vars.")?;
+        (!name.contains('.')).then_some((name, span))
+    })
+}
+
+/// Where the `$ref()` or `$var()` under the cursor is defined.
+pub fn definition(text: &str, path: &str, cursor: Position) -> Option<Value> {
+    let line = text.lines().nth(cursor.line - 1)?;
+    let chars: Vec<char> = line.chars().collect();
+    let column = cursor.column - 1;
+
+    for prefix in ["$ref(", "$var("] {
+        let mut from = 0;
+        while let Some(start) = find_chars(&chars, prefix, from) {
+            let name_start = start + prefix.len();
+            let Some(end) = chars[name_start..].iter().position(|c| *c == ')') else {
+                break;
+            };
+            let end = name_start + end;
+            if (start..=end).contains(&column) {
+                let name: String = chars[name_start..end].iter().collect();
+                return if prefix == "$ref(" {
+                    let aliases = aliases(text, path, &SourceMap::new(text));
+                    let (base, _) = split_ref(&name, |a| aliases.iter().any(|(x, _)| x == a));
+                    let (_, (file, at)) = aliases.into_iter().find(|(a, _)| a == base)?;
+                    let content = if file == path {
+                        text.to_string()
+                    } else {
+                        std::fs::read_to_string(&file).ok()?
+                    };
+                    Some(location(&content, &file, at))
+                } else {
+                    let map = SourceMap::new(text);
+                    let (_, span) = var_spans(&map).find(|(n, _)| *n == name)?;
+                    Some(location(text, path, span.key))
+                };
+            }
+            from = end;
+        }
+    }
+    None
+}
+
+fn find_chars(chars: &[char], needle: &str, from: usize) -> Option<usize> {
+    let needle: Vec<char> = needle.chars().collect();
+    (from..chars.len().saturating_sub(needle.len() - 1))
+        .find(|&i| chars[i..i + needle.len()] == needle[..])
+}
+
+fn location(text: &str, path: &str, at: Position) -> Value {
+    let start = lsp_position(text, at);
+    json!({
+        "uri": path_to_uri(path),
+        "range": { "start": start, "end": start },
+    })
+}
+
+/// Convert a 1-based character position to a 0-based LSP (UTF-16) position.
+fn lsp_position(text: &str, at: Position) -> Value {
+    let line = text.lines().nth(at.line - 1).unwrap_or_default();
+    let character: usize = line.chars().take(at.column - 1).map(char::len_utf16).sum();
+    json!({ "line": at.line - 1, "character": character })
+}
+
+/// Convert a 0-based LSP (UTF-16) position to a 1-based character position.
+fn to_position(text: &str, line: usize, character: usize) -> Position {
+    let text = text.lines().nth(line).unwrap_or_default();
+    let mut units = 0;
+    let column = text
+        .chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units <= character
+        })
+        .count();
+    Position {
+        line: line + 1,
+        column: column + 1,
+    }
+}
+
+fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut decoded = Vec::new();
+    let bytes = path.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn path_to_uri(path: &str) -> String {
+    let encoded: String = path
+        .chars()
+        .map(|c| match c {
+            ' ' => "%20".to_string(),
+            '%' => "%25".to_string(),
+            '#' => "%23".to_string(),
+            '?' => "%3F".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("file://{encoded}")
+}
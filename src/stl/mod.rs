@@ -1,7 +1,15 @@
+pub mod condition;
 pub mod executor;
+pub mod finder;
+pub mod functions;
+pub mod lsp;
 pub mod parser;
+pub mod plan;
 pub mod reconciler;
 pub mod resolver;
+pub mod rollback;
+pub mod span;
 pub mod state;
 pub mod types;
 pub mod validator;
+pub mod vars;
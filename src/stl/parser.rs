@@ -1,13 +1,85 @@
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::fmt;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 
-use super::types::Template;
+use serde::Deserialize;
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+
+use super::resolver::{split_ref, substitute_vars, yaml_to_json};
+use super::span::SourceMap;
+use super::types::{ErrorHandling, Meta, Operation, Origin, Template};
+use super::vars;
+
+/// A template as written, before includes are expanded.
+#[derive(Debug, Deserialize)]
+struct RawTemplate {
+    version: u32,
+    meta: Option<Meta>,
+    vars: Option<HashMap<String, serde_yaml::Value>>,
+    on_error: Option<ErrorHandling>,
+    include: Option<Vec<IncludeSpec>>,
+    operations: Vec<RawOperation>,
+}
+
+/// A top-level `include:` entry.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludeSpec {
+    file: String,
+    #[serde(rename = "as")]
+    namespace: Option<String>,
+    vars: Option<HashMap<String, serde_yaml::Value>>,
+}
+
+/// An entry of `operations`: an operation, or `$include(file, name=value, ...)`.
+#[derive(Debug)]
+enum RawOperation {
+    Operation(Box<Operation>),
+    Include(IncludeSpec),
+}
+
+impl<'de> Deserialize<'de> for RawOperation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawOperationVisitor;
+
+        impl<'de> Visitor<'de> for RawOperationVisitor {
+            type Value = RawOperation;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an operation mapping or `$include(file, name=value, ...)`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<RawOperation, E> {
+                parse_include_call(v)
+                    .map(RawOperation::Include)
+                    .map_err(E::custom)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<RawOperation, A::Error> {
+                Operation::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(|op| RawOperation::Operation(Box::new(op)))
+            }
+        }
+
+        deserializer.deserialize_any(RawOperationVisitor)
+    }
+}
+
+/// The file a template was read from, for operation origins.
+struct Source<'a> {
+    file: &'a str,
+    map: SourceMap,
+}
 
 /// Parse a YAML string into a `Template`.
+///
+/// Includes are resolved relative to the current directory.
 pub fn parse(yaml: &str) -> Result<Template, Box<dyn Error>> {
-    let template: Template =
+    let raw: RawTemplate =
         serde_yaml::from_str(yaml).map_err(|e| format!("Failed to parse template YAML: {e}"))?;
-    Ok(template)
+    expand(raw, None, Path::new("."), &mut Vec::new())
 }
 
 /// Read and parse a template from a file path, or from stdin if the path is "-".
@@ -15,6 +87,10 @@ pub fn parse(yaml: &str) -> Result<Template, Box<dyn Error>> {
 /// Accepts both YAML (`.shortcut.yml`) and JSON (`.shortcut.json`) files.
 /// JSON files are parsed through `serde_yaml` (which handles JSON natively),
 /// but on error we re-parse with `serde_json` to produce clearer error messages.
+///
+/// Included templates are resolved relative to the including file and
+/// expanded in place; an include that leads back to a file already being
+/// included is an error.
 pub fn parse_from_path(path: &str) -> Result<Template, Box<dyn Error>> {
     let content = if path == "-" {
         let mut buf = String::new();
@@ -26,13 +102,38 @@ pub fn parse_from_path(path: &str) -> Result<Template, Box<dyn Error>> {
         std::fs::read_to_string(path).map_err(|e| format!("Failed to read file '{path}': {e}"))?
     };
 
-    let is_json = path.ends_with(".json");
+    parse_source(&content, path)
+}
 
-    match serde_yaml::from_str::<Template>(&content) {
+/// Parse template `content` that was read from `path` ("-" for stdin).
+///
+/// Includes are resolved relative to `path`, which need not exist on disk
+/// (e.g. an unsaved editor buffer).
+pub fn parse_source(content: &str, path: &str) -> Result<Template, Box<dyn Error>> {
+    let raw = deserialize(content, path.ends_with(".json"))?;
+    let (file, base_dir, mut stack) = if path == "-" {
+        ("<stdin>", PathBuf::from("."), Vec::new())
+    } else {
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+        let base_dir = Path::new(path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        (path, base_dir, vec![(canonical, path.to_string())])
+    };
+    let source = Source {
+        file,
+        map: SourceMap::new(content),
+    };
+    expand(raw, Some(source), &base_dir, &mut stack)
+}
+
+fn deserialize(content: &str, is_json: bool) -> Result<RawTemplate, Box<dyn Error>> {
+    match serde_yaml::from_str::<RawTemplate>(content) {
         Ok(template) => Ok(template),
         Err(yaml_err) if is_json => {
             // Re-parse with serde_json for a better error message
-            match serde_json::from_str::<serde_json::Value>(&content) {
+            match serde_json::from_str::<serde_json::Value>(content) {
                 Err(json_err) => Err(format!("Failed to parse template JSON: {json_err}").into()),
                 Ok(_) => {
                     // Valid JSON but doesn't match the Template schema
@@ -43,3 +144,369 @@ pub fn parse_from_path(path: &str) -> Result<Template, Box<dyn Error>> {
         Err(yaml_err) => Err(format!("Failed to parse template YAML: {yaml_err}").into()),
     }
 }
+
+/// Expand includes and record where each operation came from.
+///
+/// `stack` holds the files currently being included (canonical path and the
+/// path as written) for cycle detection.
+fn expand(
+    raw: RawTemplate,
+    source: Option<Source>,
+    base_dir: &Path,
+    stack: &mut Vec<(PathBuf, String)>,
+) -> Result<Template, Box<dyn Error>> {
+    let mut vars = raw.vars;
+    let var_specs = match &mut vars {
+        Some(vars) => {
+            vars::extract_specs(vars).map_err(|e| format!("Failed to parse template vars: {e}"))?
+        }
+        None => HashMap::new(),
+    };
+
+    let mut operations = Vec::new();
+    let mut namespaces = HashSet::new();
+
+    for spec in raw.include.unwrap_or_default() {
+        operations.extend(include(&spec, base_dir, stack, &mut namespaces)?);
+    }
+    for (index, entry) in raw.operations.into_iter().enumerate() {
+        match entry {
+            RawOperation::Operation(mut op) => {
+                op.origin = source.as_ref().map(|s| {
+                    let path = format!("operations.{index}");
+                    let start = s.map.get(&path).map(|span| span.key);
+                    Origin {
+                        file: s.file.to_string(),
+                        line: start.map(|p| p.line),
+                        column: start.map(|p| p.column),
+                        index,
+                        namespace: None,
+                        spans: s.map.under(&path),
+                    }
+                });
+                operations.push(*op);
+            }
+            RawOperation::Include(spec) => {
+                operations.extend(include(&spec, base_dir, stack, &mut namespaces)?);
+            }
+        }
+    }
+
+    Ok(Template {
+        version: raw.version,
+        meta: raw.meta,
+        vars,
+        on_error: raw.on_error,
+        operations,
+        var_specs,
+    })
+}
+
+/// Load an included template and return its operations with aliases
+/// namespaced and its vars bound.
+fn include(
+    spec: &IncludeSpec,
+    base_dir: &Path,
+    stack: &mut Vec<(PathBuf, String)>,
+    namespaces: &mut HashSet<String>,
+) -> Result<Vec<Operation>, Box<dyn Error>> {
+    let namespace = match &spec.namespace {
+        Some(ns) => ns.clone(),
+        None => default_namespace(&spec.file),
+    };
+    if !is_valid_namespace(&namespace) {
+        return Err(format!(
+            "include '{}': namespace '{namespace}' must match [a-zA-Z][a-zA-Z0-9_-]*; set 'as'",
+            spec.file
+        )
+        .into());
+    }
+    if !namespaces.insert(namespace.clone()) {
+        return Err(format!("include namespace '{namespace}' is used more than once").into());
+    }
+
+    let path = base_dir.join(&spec.file);
+    let display = path.to_string_lossy().to_string();
+    let canonical = std::fs::canonicalize(&path)
+        .map_err(|e| format!("Failed to read included file '{display}': {e}"))?;
+    if let Some(pos) = stack.iter().position(|(p, _)| *p == canonical) {
+        let chain: Vec<&str> = stack[pos..]
+            .iter()
+            .map(|(_, shown)| shown.as_str())
+            .chain(std::iter::once(display.as_str()))
+            .collect();
+        return Err(format!("include cycle: {}", chain.join(" -> ")).into());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read included file '{display}': {e}"))?;
+    let raw = deserialize(&content, display.ends_with(".json"))
+        .map_err(|e| format!("In included file '{display}': {e}"))?;
+    if raw.version != 1 {
+        return Err(format!(
+            "included file '{display}': unsupported version {}; only version 1 is supported",
+            raw.version
+        )
+        .into());
+    }
+
+    let source = Source {
+        file: &display,
+        map: SourceMap::new(&content),
+    };
+    let child_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    stack.push((canonical, display.clone()));
+    let included = expand(raw, Some(source), &child_dir, stack);
+    stack.pop();
+    let included = included?;
+
+    // Bind vars: the include's values override the included file's defaults
+    let mut vars = included.vars.clone().unwrap_or_default();
+    for (name, value) in spec.vars.iter().flatten() {
+        if !vars.contains_key(name) {
+            return Err(format!(
+                "include '{namespace}': variable '{name}' is not declared in '{display}'"
+            )
+            .into());
+        }
+        let value = match (included.var_specs.get(name), value) {
+            // Expressions are only known once the including template resolves
+            (Some(_), serde_yaml::Value::String(s)) if is_expression(s) => Ok(value.clone()),
+            (Some(spec), serde_yaml::Value::String(s)) => vars::coerce(spec, s),
+            (Some(spec), _) => vars::check_value(spec, value).map(|()| value.clone()),
+            (None, _) => Ok(value.clone()),
+        }
+        .map_err(|e| format!("include '{namespace}': variable '{name}': {e}"))?;
+        vars.insert(name.clone(), value);
+    }
+    if let Some(name) = vars::missing_required(&vars, &included.var_specs).first() {
+        return Err(format!("include '{namespace}': variable '{name}' is required").into());
+    }
+
+    let aliases: HashSet<String> = included
+        .operations
+        .iter()
+        .filter_map(|op| op.alias.clone())
+        .collect();
+    let mut operations = included.operations;
+    for op in &mut operations {
+        // Namespace references first, so $ref()s passed in through vars keep
+        // pointing at the including template's aliases
+        namespace_op_refs(op, &namespace, &aliases);
+        substitute_op_vars(op, &vars)
+            .map_err(|errs| format!("include '{namespace}' ('{display}'): {}", errs.join("; ")))?;
+
+        if let Some(alias) = &mut op.alias {
+            *alias = format!("{namespace}.{alias}");
+        }
+        if op.on_error.is_none() {
+            op.on_error = included.on_error.clone();
+        }
+        if let Some(origin) = &mut op.origin {
+            origin.namespace = Some(match &origin.namespace {
+                Some(inner) => format!("{namespace}.{inner}"),
+                None => namespace.clone(),
+            });
+        }
+    }
+    Ok(operations)
+}
+
+/// Whether a bound value is resolved later, by the including template.
+fn is_expression(s: &str) -> bool {
+    ["$var(", "$ref(", "$fn(", "$item("]
+        .iter()
+        .any(|prefix| s.contains(prefix))
+}
+
+/// Parse `$include(file, name=value, ...)`. `as=name` sets the namespace.
+fn parse_include_call(s: &str) -> Result<IncludeSpec, String> {
+    let trimmed = s.trim();
+    let Some(args) = trimmed
+        .strip_prefix("$include(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return Err(format!(
+            "expected an operation or `$include(file, name=value, ...)`, found '{trimmed}'"
+        ));
+    };
+
+    let mut parts = args.split(',').map(str::trim);
+    let file = parts.next().unwrap_or_default();
+    if file.is_empty() {
+        return Err("$include() requires a file".to_string());
+    }
+    let mut spec = IncludeSpec {
+        file: file.to_string(),
+        namespace: None,
+        vars: None,
+    };
+    for part in parts {
+        let Some((name, value)) = part.split_once('=') else {
+            return Err(format!(
+                "invalid $include argument '{part}': expected name=value"
+            ));
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if name == "as" {
+            spec.namespace = Some(value.to_string());
+            continue;
+        }
+        // Scalars keep their YAML type: count=3 is a number, flag=true a bool
+        let value = serde_yaml::from_str::<serde_yaml::Value>(value)
+            .ok()
+            .filter(|v| !v.is_mapping() && !v.is_sequence())
+            .unwrap_or_else(|| serde_yaml::Value::String(value.to_string()));
+        spec.vars
+            .get_or_insert_with(HashMap::new)
+            .insert(name.to_string(), value);
+    }
+    Ok(spec)
+}
+
+/// The namespace an include gets without `as`: the file name up to its
+/// first dot (`ci.shortcut.yml` → `ci`).
+fn default_namespace(file: &str) -> String {
+    let name = Path::new(file)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    name.split('.').next().unwrap_or_default().to_string()
+}
+
+fn is_valid_namespace(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Prefix `$ref()`s to the included template's own aliases with `namespace`.
+fn namespace_op_refs(op: &mut Operation, namespace: &str, aliases: &HashSet<String>) {
+    for_each_value(op, |value| namespace_refs_in(value, namespace, aliases));
+    if let Some(when) = &mut op.when {
+        *when = namespace_refs(when, namespace, aliases);
+    }
+}
+
+/// Substitute the include's vars into an operation.
+fn substitute_op_vars(
+    op: &mut Operation,
+    vars: &HashMap<String, serde_yaml::Value>,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for_each_value(op, |value| {
+        if let Err(errs) = substitute_vars(value, vars) {
+            errors.extend(errs);
+        }
+    });
+    if let Some(when) = &mut op.when {
+        match substitute_when_vars(when, vars) {
+            Ok(substituted) => *when = substituted,
+            Err(errs) => errors.extend(errs),
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Run `f` over the `id`, `fields`, `for_each` and `repeat` values of an
+/// operation.
+fn for_each_value(op: &mut Operation, mut f: impl FnMut(&mut serde_yaml::Value)) {
+    if let Some(id) = &mut op.id {
+        f(id);
+    }
+    for mapping in op
+        .fields
+        .iter_mut()
+        .chain(op.for_each.iter_mut())
+        .chain(op.repeat.iter_mut().flatten())
+    {
+        let mut value = serde_yaml::Value::Mapping(std::mem::take(mapping));
+        f(&mut value);
+        if let serde_yaml::Value::Mapping(m) = value {
+            *mapping = m;
+        }
+    }
+}
+
+fn namespace_refs_in(value: &mut serde_yaml::Value, namespace: &str, aliases: &HashSet<String>) {
+    match value {
+        serde_yaml::Value::String(s) => *s = namespace_refs(s, namespace, aliases),
+        serde_yaml::Value::Sequence(seq) => {
+            for item in seq {
+                namespace_refs_in(item, namespace, aliases);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (_, item) in map.iter_mut() {
+                namespace_refs_in(item, namespace, aliases);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn namespace_refs(s: &str, namespace: &str, aliases: &HashSet<String>) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("$ref(") {
+        let after = &rest[start + 5..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let expr = &after[..end];
+        result.push_str(&rest[..start + 5]);
+        if aliases.contains(split_ref(expr, |a| aliases.contains(a)).0) {
+            result.push_str(namespace);
+            result.push('.');
+        }
+        result.push_str(expr);
+        rest = &after[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Substitute vars into a `when:` expression as literals. Values that are
+/// themselves a single `$var()` or `$ref()` are inserted as-is.
+fn substitute_when_vars(
+    when: &str,
+    vars: &HashMap<String, serde_yaml::Value>,
+) -> Result<String, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut result = String::new();
+    let mut rest = when;
+    while let Some(start) = rest.find("$var(") {
+        let after = &rest[start + 5..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let name = &after[..end];
+        result.push_str(&rest[..start]);
+        match vars.get(name) {
+            Some(serde_yaml::Value::String(s))
+                if (s.starts_with("$var(") || s.starts_with("$ref(")) && s.ends_with(')') =>
+            {
+                result.push_str(s);
+            }
+            Some(value) => result.push_str(&yaml_to_json(value).to_string()),
+            None => {
+                errors.push(format!("undeclared variable '{name}'"));
+                result.push_str(&rest[start..start + 5 + end + 1]);
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    if errors.is_empty() {
+        Ok(result)
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,418 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::project::fnv1a_hex;
+
+use super::executor::{
+    find_repeat_entry, merge_mappings, resolve_entity_fields, seed_results,
+    substitute_template_vars,
+};
+use super::finder::fetch_entity;
+use super::reconciler::SyncAction;
+use super::resolver::{resolve_refs, yaml_mapping_to_json, yaml_to_json};
+use super::state::SyncState;
+use super::types::{Action, Operation, Template};
+
+/// Current plan file format version.
+pub const PLAN_VERSION: u32 = 1;
+
+/// A saved `template plan`: the sync actions to run and what they will
+/// change, tied to the exact template and state they were computed from.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Plan {
+    pub version: u32,
+    pub created_at: String,
+    pub template: Fingerprint,
+    pub state: Fingerprint,
+    /// Final values of the template's vars, reused when the plan is applied.
+    #[serde(default)]
+    pub vars: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub prune: bool,
+    pub actions: Vec<PlannedAction>,
+}
+
+/// A file a plan depends on, by absolute path so the plan can be applied
+/// from any directory. `hash` is `None` when the file did not exist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub path: String,
+    pub hash: Option<String>,
+}
+
+/// A sync action with the field changes it will make.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedAction {
+    #[serde(flatten)]
+    pub action: SyncAction,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+}
+
+/// A field an action sets. `from` is null for creates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+impl Plan {
+    pub fn new(
+        template_path: &str,
+        template: &Template,
+        state_path: &Path,
+        prune: bool,
+        actions: Vec<PlannedAction>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let vars = template
+            .vars
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), yaml_to_json(value)))
+            .collect();
+        // Hash against the path the template was read from, which its
+        // includes' origins are relative to
+        let template_fingerprint = Fingerprint {
+            path: absolute(Path::new(template_path))?
+                .to_string_lossy()
+                .to_string(),
+            ..template_fingerprint(template_path, template)?
+        };
+        Ok(Self {
+            version: PLAN_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            template: template_fingerprint,
+            state: state_fingerprint(&absolute(state_path)?)?,
+            vars,
+            prune,
+            actions,
+        })
+    }
+
+    /// Read a plan file written by `template plan`.
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read plan '{path}': {e}"))?;
+        let plan: Plan = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse plan '{path}': {e}"))?;
+        if plan.version != PLAN_VERSION {
+            return Err(format!(
+                "plan '{path}': unsupported version {}; only version {PLAN_VERSION} is supported",
+                plan.version
+            )
+            .into());
+        }
+        Ok(plan)
+    }
+
+    /// Set the template's vars to the values the plan was made with.
+    pub fn apply_vars(&self, template: &mut Template) -> Result<(), Box<dyn Error>> {
+        let vars = template.vars.get_or_insert_with(Default::default);
+        for (name, value) in &self.vars {
+            vars.insert(name.clone(), serde_yaml::to_value(value)?);
+        }
+        Ok(())
+    }
+
+    /// Fail if the template (or a file it includes) or the state file has
+    /// changed since the plan was made.
+    pub fn check_unchanged(&self, template: &Template) -> Result<(), Box<dyn Error>> {
+        if template_fingerprint(&self.template.path, template)? != self.template {
+            return Err(format!(
+                "template '{}' has changed since the plan was made; run `template plan` again",
+                self.template.path
+            )
+            .into());
+        }
+        if state_fingerprint(Path::new(&self.state.path))? != self.state {
+            return Err(format!(
+                "state file '{}' has changed since the plan was made; run `template plan` again",
+                self.state.path
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// `path` made absolute, for a file that may not exist yet.
+fn absolute(path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let resolve = || -> std::io::Result<PathBuf> {
+        match std::fs::canonicalize(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let dir = match path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => std::fs::canonicalize(dir)?,
+                    _ => std::env::current_dir()?,
+                };
+                Ok(dir.join(path.file_name().unwrap_or_default()))
+            }
+            result => result,
+        }
+    };
+    resolve().map_err(|e| format!("Failed to resolve path '{}': {e}", path.display()).into())
+}
+
+/// Fingerprint a template file together with every file it includes.
+///
+/// Files are named relative to the template's directory, so the hash is the
+/// same whichever directory the template was opened from.
+pub fn template_fingerprint(
+    path: &str,
+    template: &Template,
+) -> Result<Fingerprint, Box<dyn Error>> {
+    let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+    let name = |file: &str| {
+        let file = Path::new(file);
+        file.strip_prefix(dir)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .to_string()
+    };
+    let mut files: BTreeSet<(String, &str)> = template
+        .operations
+        .iter()
+        .filter_map(|op| op.origin.as_ref())
+        .map(|origin| (name(&origin.file), origin.file.as_str()))
+        .collect();
+    files.insert((name(path), path));
+
+    let mut data = Vec::new();
+    for (name, file) in files {
+        let content =
+            std::fs::read(file).map_err(|e| format!("Failed to read file '{file}': {e}"))?;
+        data.extend_from_slice(name.as_bytes());
+        data.push(0);
+        data.extend_from_slice(&content);
+        data.push(0);
+    }
+    Ok(Fingerprint {
+        path: path.to_string(),
+        hash: Some(fnv1a_hex(&data)),
+    })
+}
+
+/// Fingerprint a state file, which may not exist yet.
+pub fn state_fingerprint(path: &Path) -> Result<Fingerprint, Box<dyn Error>> {
+    let hash = match std::fs::read(path) {
+        Ok(content) => Some(fnv1a_hex(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return Err(format!("Failed to read state file '{}': {e}", path.display()).into());
+        }
+    };
+    Ok(Fingerprint {
+        path: path.to_string_lossy().to_string(),
+        hash,
+    })
+}
+
+/// Work out the field changes of each action.
+///
+/// Creates list every field they set. Updates are diffed against the live
+/// entity, so fields that already have the desired value are left out.
+pub async fn plan_actions(
+    template: &mut Template,
+    state: Option<&SyncState>,
+    actions: &[SyncAction],
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<PlannedAction>, Box<dyn Error>> {
+    substitute_template_vars(template)?;
+    let results = state.map(seed_results).unwrap_or_default();
+
+    let mut planned = Vec::new();
+    for action in actions {
+        let changes = match action {
+            SyncAction::Create { op_index, .. } => additions(
+                desired_fields(&template.operations[*op_index], None)?,
+                &results,
+            ),
+            SyncAction::CreateEntry { op_index, key, .. } => additions(
+                desired_fields(&template.operations[*op_index], Some(key))?,
+                &results,
+            ),
+            SyncAction::Update {
+                op_index,
+                existing_id,
+                ..
+            } => {
+                let op = &template.operations[*op_index];
+                let fields = desired_fields(op, None)?;
+                diff(op, fields, existing_id, &results, client, cache_dir).await?
+            }
+            SyncAction::UpdateEntry {
+                op_index,
+                key,
+                existing_id,
+                ..
+            } => {
+                let op = &template.operations[*op_index];
+                let fields = desired_fields(op, Some(key))?;
+                diff(op, fields, existing_id, &results, client, cache_dir).await?
+            }
+            SyncAction::Passthrough { op_index } => {
+                let op = &template.operations[*op_index];
+                match passthrough_target(op, &results) {
+                    Some(id) => {
+                        let fields = desired_fields(op, None)?;
+                        diff(op, fields, &id, &results, client, cache_dir).await?
+                    }
+                    None => Vec::new(),
+                }
+            }
+            SyncAction::OrphanEntry { .. }
+            | SyncAction::Orphan { .. }
+            | SyncAction::Skip { .. }
+            | SyncAction::RunSideEffect { .. } => Vec::new(),
+        };
+        planned.push(PlannedAction {
+            action: action.clone(),
+            changes,
+        });
+    }
+    Ok(planned)
+}
+
+/// The fields an operation (or one of its repeat entries) sets.
+fn desired_fields(
+    op: &Operation,
+    key: Option<&String>,
+) -> Result<serde_json::Map<String, serde_json::Value>, Box<dyn Error>> {
+    let shared = op.fields.clone().unwrap_or_default();
+    let fields = match key {
+        Some(key) => merge_mappings(&shared, &find_repeat_entry(op, key)?),
+        None => shared,
+    };
+    let mut fields = match yaml_mapping_to_json(&fields) {
+        serde_json::Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+    fields.remove("key");
+    Ok(fields)
+}
+
+/// Changes for a create: every field, from nothing.
+fn additions(
+    fields: serde_json::Map<String, serde_json::Value>,
+    results: &HashMap<String, serde_json::Value>,
+) -> Vec<FieldChange> {
+    fields
+        .into_iter()
+        .map(|(field, value)| FieldChange {
+            field,
+            from: serde_json::Value::Null,
+            to: resolved(&value, results).unwrap_or(value),
+        })
+        .collect()
+}
+
+/// Changes for an update: the fields whose live value differs from the
+/// desired one.
+async fn diff(
+    op: &Operation,
+    mut fields: serde_json::Map<String, serde_json::Value>,
+    id: &serde_json::Value,
+    results: &HashMap<String, serde_json::Value>,
+    client: &api::Client,
+    cache_dir: &Path,
+) -> Result<Vec<FieldChange>, Box<dyn Error>> {
+    // Inline tasks are synced separately, as in `sync`
+    fields.remove("tasks");
+
+    // Fields that $ref resources this plan has yet to create are only known
+    // once it is applied
+    let mut known = serde_json::Map::new();
+    let mut pending = Vec::new();
+    for (field, value) in fields {
+        match resolved(&value, results) {
+            Some(value) => {
+                known.insert(field, value);
+            }
+            None => pending.push((field, value)),
+        }
+    }
+
+    let story_id = known.get("story_id").and_then(|v| v.as_i64());
+    let mut body = serde_json::Value::Object(known);
+    resolve_entity_fields(&op.entity, &Action::Update, &mut body, client, cache_dir).await?;
+
+    let live = fetch_entity(client, &op.entity, id, story_id)
+        .await
+        .map_err(|e| format!("could not read {} {id} to plan its update: {e}", op.entity))?;
+
+    let mut changes = Vec::new();
+    for (field, to) in body.as_object().into_iter().flatten() {
+        let from = project(live.get(field).unwrap_or(&serde_json::Value::Null), to);
+        if &from != to {
+            changes.push(FieldChange {
+                field: field.clone(),
+                from,
+                to: to.clone(),
+            });
+        }
+    }
+    for (field, to) in pending {
+        changes.push(FieldChange {
+            from: live.get(&field).cloned().unwrap_or_default(),
+            field,
+            to,
+        });
+    }
+    Ok(changes)
+}
+
+/// The entity an explicit `update` targets, when it can be known without
+/// running the template.
+fn passthrough_target(
+    op: &Operation,
+    results: &HashMap<String, serde_json::Value>,
+) -> Option<serde_json::Value> {
+    if op.action != Action::Update {
+        return None;
+    }
+    let id = resolved(&yaml_to_json(op.id.as_ref()?), results)?;
+    (id.is_i64() || id.is_string()).then_some(id)
+}
+
+/// `value` with its `$ref()`s resolved, or `None` if one can't be yet.
+fn resolved(
+    value: &serde_json::Value,
+    results: &HashMap<String, serde_json::Value>,
+) -> Option<serde_json::Value> {
+    let mut value = value.clone();
+    resolve_refs(&mut value, results).ok()?;
+    Some(value)
+}
+
+/// Reduce a live value to the shape of the desired one, so keys the API
+/// returns but the template doesn't set (a label's id and color, say) don't
+/// count as changes.
+fn project(live: &serde_json::Value, desired: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match (live, desired) {
+        (Value::Object(live), Value::Object(desired)) => Value::Object(
+            desired
+                .iter()
+                .map(|(key, shape)| {
+                    let value = live.get(key).unwrap_or(&Value::Null);
+                    (key.clone(), project(value, shape))
+                })
+                .collect(),
+        ),
+        (Value::Array(live), Value::Array(desired)) => Value::Array(
+            live.iter()
+                .enumerate()
+                .map(|(i, item)| match desired.get(i).or(desired.first()) {
+                    Some(shape) => project(item, shape),
+                    None => item.clone(),
+                })
+                .collect(),
+        ),
+        _ => live.clone(),
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize};
+
 use super::state::{ResourceState, SyncState};
 use super::types::{Action, Operation};
 
 /// A planned sync action produced by reconciliation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
 pub enum SyncAction {
     /// Single create op, alias not in state -> POST
     Create { op_index: usize, alias: String },
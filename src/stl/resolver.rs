@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+use super::functions;
+use super::validator;
+
 /// Substitute all `$var(name)` references in a YAML value tree.
 ///
 /// Variables are resolved from the `vars` map. Returns errors for any
@@ -46,29 +49,31 @@ fn substitute_vars_inner(
     }
 }
 
-/// Resolve `$var()` in a string. Returns a new YAML value if substitution occurred.
+/// Resolve `$var()` and `$fn()` in a string. Returns a new YAML value if
+/// substitution occurred.
 ///
 /// If the entire string is `$var(name)` and the variable is a non-string type
 /// (integer, boolean, etc.), the raw value is returned (not stringified).
 /// For inline interpolation like `"text $var(name) text"`, the variable value
-/// is stringified and embedded.
+/// is stringified and embedded. `$fn()` calls always produce strings.
 fn resolve_var_string(
     s: &str,
     vars: &HashMap<String, serde_yaml::Value>,
     errors: &mut Vec<String>,
 ) -> Option<serde_yaml::Value> {
-    // Fast path: no $var() at all
-    if !s.contains("$var(") {
+    // Fast path: no $var() or $fn() at all
+    if !s.contains("$var(") && !s.contains("$fn(") {
         return None;
     }
 
-    // Check if the entire string is a single $var(name)
-    let trimmed = s.trim();
-    if trimmed.starts_with("$var(")
-        && trimmed.ends_with(')')
-        && trimmed.matches("$var(").count() == 1
-    {
-        let var_name = &trimmed[5..trimmed.len() - 1];
+    // Check if the entire string is a single $var(name); anything else, like
+    // `$var(a)-$fn(upper(b))`, is interpolated below
+    let whole_var = s
+        .trim()
+        .strip_prefix("$var(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .filter(|name| validator::is_valid_var_name(name));
+    if let Some(var_name) = whole_var {
         if let Some(val) = vars.get(var_name) {
             return Some(val.clone());
         } else {
@@ -77,14 +82,43 @@ fn resolve_var_string(
         }
     }
 
-    // Inline interpolation: replace all $var(name) within the string
+    // Inline interpolation: replace all $var(name) and $fn(...) within the
+    // string, left to right
     let mut result = s.to_string();
     let mut search_start = 0;
     loop {
-        let Some(start) = result[search_start..].find("$var(") else {
+        let next_var = result[search_start..].find("$var(");
+        let next_fn = result[search_start..].find("$fn(");
+        let Some(start) = next_var.into_iter().chain(next_fn).min() else {
             break;
         };
         let abs_start = search_start + start;
+
+        if next_fn == Some(start) {
+            let (call_start, call_end) = match functions::next_call(&result, abs_start) {
+                Some(Ok(span)) => span,
+                Some(Err(e)) => {
+                    errors.push(e);
+                    break;
+                }
+                None => break,
+            };
+            let call = &result[call_start..call_end];
+            match functions::parse(functions::inner(call))
+                .and_then(|expr| functions::evaluate(&expr, vars))
+            {
+                Ok(replacement) => {
+                    result.replace_range(call_start..call_end, &replacement);
+                    search_start = call_start + replacement.len();
+                }
+                Err(e) => {
+                    errors.push(format!("{call}: {e}"));
+                    search_start = call_end;
+                }
+            }
+            continue;
+        }
+
         let after = &result[abs_start + 5..];
         let Some(end) = after.find(')') else {
             break;
@@ -106,7 +140,7 @@ fn resolve_var_string(
 }
 
 /// Convert a YAML value to its string representation for interpolation.
-fn yaml_value_to_string(val: &serde_yaml::Value) -> String {
+pub fn yaml_value_to_string(val: &serde_yaml::Value) -> String {
     match val {
         serde_yaml::Value::String(s) => s.clone(),
         serde_yaml::Value::Number(n) => n.to_string(),
@@ -116,6 +150,111 @@ fn yaml_value_to_string(val: &serde_yaml::Value) -> String {
     }
 }
 
+/// Substitute all `$item(field)` references in a YAML value tree with values
+/// from the current `for_each` item.
+///
+/// `field` may be a dotted path (`$item(owner_ids.0)`). Type preservation
+/// follows `$var()`: a full-value reference keeps the item's type, inline
+/// references are stringified.
+pub fn substitute_item(
+    value: &mut serde_yaml::Value,
+    item: &serde_json::Value,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    substitute_item_inner(value, item, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn substitute_item_inner(
+    value: &mut serde_yaml::Value,
+    item: &serde_json::Value,
+    errors: &mut Vec<String>,
+) {
+    match value {
+        serde_yaml::Value::String(s) => {
+            if let Some(resolved) = resolve_item_string(s, item, errors) {
+                *value = resolved;
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for entry in seq.iter_mut() {
+                substitute_item_inner(entry, item, errors);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            let keys: Vec<serde_yaml::Value> = map.keys().cloned().collect();
+            for key in keys {
+                if let Some(val) = map.get_mut(&key) {
+                    substitute_item_inner(val, item, errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve `$item()` in a string. Returns a new YAML value if substitution occurred.
+fn resolve_item_string(
+    s: &str,
+    item: &serde_json::Value,
+    errors: &mut Vec<String>,
+) -> Option<serde_yaml::Value> {
+    if !s.contains("$item(") {
+        return None;
+    }
+
+    let trimmed = s.trim();
+    if trimmed.starts_with("$item(")
+        && trimmed.ends_with(')')
+        && trimmed.matches("$item(").count() == 1
+    {
+        let path = &trimmed[6..trimmed.len() - 1];
+        return match item_field(item, path) {
+            Some(val) => serde_yaml::to_value(val).ok(),
+            None => {
+                errors.push(format!("$item({path}): field '{path}' not found in item"));
+                None
+            }
+        };
+    }
+
+    let mut result = s.to_string();
+    let mut search_start = 0;
+    while let Some(start) = result[search_start..].find("$item(") {
+        let abs_start = search_start + start;
+        let after = &result[abs_start + 6..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let path = after[..end].to_string();
+        let abs_end = abs_start + 6 + end + 1;
+
+        if let Some(val) = item_field(item, &path) {
+            let replacement = json_value_to_string(val);
+            result.replace_range(abs_start..abs_end, &replacement);
+            search_start = abs_start + replacement.len();
+        } else {
+            errors.push(format!("$item({path}): field '{path}' not found in item"));
+            search_start = abs_end;
+        }
+    }
+
+    Some(serde_yaml::Value::String(result))
+}
+
+/// Follow a dotted path (object keys or array indexes) into an item.
+fn item_field<'a>(item: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(item, |current, part| match current {
+            serde_json::Value::Array(arr) => part.parse::<usize>().ok().and_then(|i| arr.get(i)),
+            _ => current.get(part),
+        })
+}
+
 /// Resolve all `$ref(alias)` references in a JSON value tree, replacing them
 /// with the resolved values from completed operation results.
 ///
@@ -174,10 +313,7 @@ fn resolve_ref_string(
     // Inline interpolation
     let mut result = s.to_string();
     let mut search_start = 0;
-    loop {
-        let Some(start) = result[search_start..].find("$ref(") else {
-            break;
-        };
+    while let Some(start) = result[search_start..].find("$ref(") {
         let abs_start = search_start + start;
         let after = &result[abs_start + 5..];
         let Some(end) = after.find(')') else {
@@ -200,19 +336,16 @@ fn resolve_single_ref(
     expr: &str,
     results: &HashMap<String, serde_json::Value>,
 ) -> Result<serde_json::Value, String> {
-    let parts: Vec<&str> = expr.splitn(2, '.').collect();
-    let alias = parts[0];
+    let (alias, field) = split_ref(expr, |a| results.contains_key(a));
 
     let result = results
         .get(alias)
         .ok_or_else(|| format!("$ref({expr}): alias '{alias}' not found in results"))?;
 
-    if parts.len() == 1 {
+    let Some(field) = field else {
         // $ref(alias) → return the primary ID
         return extract_primary_id(result, alias);
-    }
-
-    let field = parts[1];
+    };
 
     // Check if it's a numeric index (for repeat aliases with array results)
     if let Ok(index) = field.parse::<usize>() {
@@ -246,6 +379,24 @@ fn resolve_single_ref(
         .ok_or_else(|| format!("$ref({expr}): field '{field}' not found in alias '{alias}' result"))
 }
 
+/// Split a `$ref()` expression into its alias and the field path after it.
+///
+/// Aliases from included templates contain dots (`ci.pipeline`), so the
+/// longest dotted prefix accepted by `is_alias` wins; otherwise the alias is
+/// everything before the first dot.
+pub fn split_ref(expr: &str, is_alias: impl Fn(&str) -> bool) -> (&str, Option<&str>) {
+    let longest = expr
+        .char_indices()
+        .filter(|(_, c)| *c == '.')
+        .map(|(i, _)| i)
+        .rev()
+        .find(|&i| is_alias(&expr[..i]));
+    match longest.or_else(|| expr.find('.')) {
+        Some(i) if !is_alias(expr) => (&expr[..i], Some(&expr[i + 1..])),
+        _ => (expr, None),
+    }
+}
+
 /// Extract the primary ID from an API response.
 fn extract_primary_id(value: &serde_json::Value, alias: &str) -> Result<serde_json::Value, String> {
     if let Some(id) = value.get("id") {
@@ -0,0 +1,399 @@
+use std::error::Error;
+
+use crate::api;
+use crate::out_println;
+use crate::output::OutputConfig;
+
+use super::executor::Slot;
+use super::finder::fetch_entity;
+use super::types::{Action, Entity, RollbackResult};
+
+/// How to undo one completed operation.
+#[derive(Debug)]
+enum Undo {
+    /// Delete an entity the run created.
+    Delete {
+        story_id: Option<i64>,
+    },
+    /// Groups cannot be deleted, so a created group is archived instead.
+    Archive,
+    /// Delete a comment the run added to a story or epic.
+    DeleteComment {
+        parent: Entity,
+        parent_id: i64,
+    },
+    /// Put back the values an update overwrote. `lost` lists fields whose
+    /// previous value could not be read.
+    Restore {
+        story_id: Option<i64>,
+        fields: serde_json::Map<String, serde_json::Value>,
+        lost: Vec<String>,
+    },
+    NotSupported(&'static str),
+}
+
+#[derive(Debug)]
+struct Entry {
+    slot: Slot,
+    action: Action,
+    entity: Entity,
+    id: Option<serde_json::Value>,
+    undo: Undo,
+}
+
+/// Previous field values of an entity, read just before it is updated.
+#[derive(Debug)]
+pub struct Snapshot(Undo);
+
+/// Completed writes of a run, in order, with how to undo each one.
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    /// Read the values an update, check or uncheck is about to overwrite.
+    ///
+    /// Returns `None` for actions that don't modify an existing entity.
+    pub async fn snapshot(
+        client: &api::Client,
+        action: &Action,
+        entity: &Entity,
+        id: Option<&serde_json::Value>,
+        story_id: Option<i64>,
+        body: &serde_json::Value,
+    ) -> Result<Option<Snapshot>, Box<dyn Error>> {
+        if !matches!(action, Action::Update | Action::Check | Action::Uncheck) {
+            return Ok(None);
+        }
+        let id = id.ok_or_else(|| format!("{action} {entity} requires id"))?;
+        let current = fetch_entity(client, entity, id, story_id)
+            .await
+            .map_err(|e| {
+                format!(
+                    "could not read {entity} {} before {action}: {e}",
+                    display_id(id)
+                )
+            })?;
+
+        let mut fields = serde_json::Map::new();
+        let mut lost = Vec::new();
+        for key in body.as_object().into_iter().flat_map(|b| b.keys()) {
+            match current.get(key) {
+                Some(value) => {
+                    fields.insert(key.clone(), restorable(key, value));
+                }
+                None => lost.push(key.clone()),
+            }
+        }
+        Ok(Some(Snapshot(Undo::Restore {
+            story_id,
+            fields,
+            lost,
+        })))
+    }
+
+    /// Record a successful call so it can be undone.
+    ///
+    /// `slot` is the operation and the repeat entry or for_each item the call
+    /// was made for. `id` is the operation's target (the entity updated, or
+    /// the story or epic commented on); created ids are taken from `response`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        slot: Slot,
+        action: &Action,
+        entity: &Entity,
+        id: Option<&serde_json::Value>,
+        story_id: Option<i64>,
+        snapshot: Option<Snapshot>,
+        response: &serde_json::Value,
+    ) {
+        let created_id = response.get("id").cloned();
+        let (id, undo) = match (action, entity) {
+            (Action::Find, _) => return,
+            (Action::Create, Entity::Group) => (created_id, Undo::Archive),
+            (Action::Create, Entity::Task) => {
+                let story_id = response
+                    .get("story_id")
+                    .and_then(|v| v.as_i64())
+                    .or(story_id);
+                (created_id, Undo::Delete { story_id })
+            }
+            (Action::Create | Action::Link, _) => (created_id, Undo::Delete { story_id: None }),
+            (Action::Comment, _) => match id.and_then(|v| v.as_i64()) {
+                Some(parent_id) => (
+                    created_id,
+                    Undo::DeleteComment {
+                        parent: entity.clone(),
+                        parent_id,
+                    },
+                ),
+                None => (
+                    created_id,
+                    Undo::NotSupported("the commented entity is unknown"),
+                ),
+            },
+            (Action::Update | Action::Check | Action::Uncheck, _) => (
+                id.cloned(),
+                match snapshot {
+                    Some(Snapshot(undo)) => undo,
+                    None => Undo::NotSupported("no snapshot was taken"),
+                },
+            ),
+            (Action::Delete, _) => (
+                id.cloned(),
+                Undo::NotSupported("deleted entities cannot be restored"),
+            ),
+            (Action::Unlink, _) => (
+                id.cloned(),
+                Undo::NotSupported("removed story links are not recreated"),
+            ),
+        };
+        self.entries.push(Entry {
+            slot,
+            action: action.clone(),
+            entity: entity.clone(),
+            id,
+            undo,
+        });
+    }
+
+    /// Undo every recorded operation, last operation first, and report what
+    /// was and wasn't rolled back under the operation numbers `index` gives.
+    /// Keeps going when an undo step fails.
+    pub async fn roll_back(
+        self,
+        index: impl Fn(Slot) -> usize,
+        client: &api::Client,
+        out: &OutputConfig,
+        show_progress: bool,
+    ) -> Result<Vec<RollbackResult>, Box<dyn Error>> {
+        if show_progress && !self.entries.is_empty() {
+            out_println!(
+                out,
+                "Rolling back {} completed operation(s)...",
+                self.entries.len()
+            );
+        }
+
+        // Later operations can only depend on earlier ones, so undoing in
+        // reverse operation order is safe even when they ran concurrently
+        let mut entries = self.entries;
+        entries.sort_by_key(|e| std::cmp::Reverse(e.slot));
+
+        let mut results = Vec::new();
+        for entry in entries {
+            let index = index(entry.slot);
+            let (status, detail) = match undo(client, &entry).await {
+                Ok((status, detail)) => (status, detail),
+                Err(e) => ("failed", e.to_string()),
+            };
+            let target = match &entry.id {
+                Some(id) => format!("{} {}", entry.entity, display_id(id)),
+                None => entry.entity.to_string(),
+            };
+            if show_progress {
+                out_println!(
+                    out,
+                    "  [rollback] operation {} ({} {target}): {}",
+                    index + 1,
+                    entry.action,
+                    match status {
+                        "failed" => format!("FAILED — {detail}"),
+                        "not_supported" => format!("not rolled back — {detail}"),
+                        _ => detail.clone(),
+                    }
+                );
+            }
+            results.push(RollbackResult {
+                index,
+                action: entry.action.to_string(),
+                entity: entry.entity.to_string(),
+                id: entry.id,
+                status: status.to_string(),
+                detail,
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// Undo one entry, returning its status and a description.
+async fn undo(
+    client: &api::Client,
+    entry: &Entry,
+) -> Result<(&'static str, String), Box<dyn Error>> {
+    let id = || -> Result<&serde_json::Value, Box<dyn Error>> {
+        entry
+            .id
+            .as_ref()
+            .ok_or_else(|| "the response had no id".into())
+    };
+    let int_id = || -> Result<i64, Box<dyn Error>> {
+        id()?
+            .as_i64()
+            .ok_or_else(|| format!("expected a numeric id, got {}", id().unwrap()).into())
+    };
+
+    match &entry.undo {
+        Undo::NotSupported(reason) => Ok(("not_supported", reason.to_string())),
+        Undo::Delete { story_id } => {
+            match &entry.entity {
+                Entity::Task => {
+                    let story_id = story_id.ok_or("the task's story is unknown")?;
+                    client
+                        .delete_task()
+                        .story_public_id(story_id)
+                        .task_public_id(int_id()?)
+                        .send()
+                        .await
+                        .map_err(|e| api::format_api_error(&e))?;
+                }
+                Entity::StoryLink => {
+                    super::executor::dispatch_api_call(
+                        client,
+                        &Action::Unlink,
+                        &entry.entity,
+                        Some(id()?),
+                        None,
+                        serde_json::json!({}),
+                    )
+                    .await?;
+                }
+                entity => {
+                    super::executor::dispatch_api_call(
+                        client,
+                        &Action::Delete,
+                        entity,
+                        Some(id()?),
+                        None,
+                        serde_json::json!({}),
+                    )
+                    .await?;
+                }
+            }
+            Ok(("rolled_back", "deleted".to_string()))
+        }
+        Undo::Archive => {
+            super::executor::dispatch_api_call(
+                client,
+                &Action::Update,
+                &entry.entity,
+                Some(id()?),
+                None,
+                serde_json::json!({ "archived": true }),
+            )
+            .await?;
+            Ok((
+                "rolled_back",
+                "archived (groups cannot be deleted)".to_string(),
+            ))
+        }
+        Undo::DeleteComment { parent, parent_id } => {
+            let comment_id = int_id()?;
+            match parent {
+                Entity::Epic => {
+                    client
+                        .delete_epic_comment()
+                        .epic_public_id(*parent_id)
+                        .comment_public_id(comment_id)
+                        .send()
+                        .await
+                        .map_err(|e| api::format_api_error(&e))?;
+                }
+                _ => {
+                    client
+                        .delete_story_comment()
+                        .story_public_id(*parent_id)
+                        .comment_public_id(comment_id)
+                        .send()
+                        .await
+                        .map_err(|e| api::format_api_error(&e))?;
+                }
+            }
+            Ok(("rolled_back", format!("deleted comment {comment_id}")))
+        }
+        Undo::Restore {
+            story_id,
+            fields,
+            lost,
+        } => {
+            if !fields.is_empty() {
+                // Sent as raw JSON: the generated update types drop `None`
+                // fields, so a value that was null before could not be cleared
+                let path = update_path(&entry.entity, id()?, *story_id)?;
+                api::send_json(
+                    client,
+                    reqwest::Method::PUT,
+                    &path,
+                    &serde_json::Value::Object(fields.clone()),
+                )
+                .await?;
+            }
+            let restored: Vec<&str> = fields.keys().map(String::as_str).collect();
+            let mut detail = if restored.is_empty() {
+                "nothing to restore".to_string()
+            } else {
+                format!("restored {}", restored.join(", "))
+            };
+            if lost.is_empty() {
+                Ok(("rolled_back", detail))
+            } else {
+                detail.push_str(&format!(
+                    "; could not restore {} (no previous value)",
+                    lost.join(", ")
+                ));
+                Ok(("partial", detail))
+            }
+        }
+    }
+}
+
+/// API path of the update endpoint for an entity.
+fn update_path(
+    entity: &Entity,
+    id: &serde_json::Value,
+    story_id: Option<i64>,
+) -> Result<String, Box<dyn Error>> {
+    let id = display_id(id);
+    let collection = match entity {
+        Entity::Story => "stories",
+        Entity::Epic => "epics",
+        Entity::Iteration => "iterations",
+        Entity::Label => "labels",
+        Entity::Objective => "objectives",
+        Entity::Milestone => "milestones",
+        Entity::Category => "categories",
+        Entity::Project => "projects",
+        Entity::Group => "groups",
+        Entity::Document => "documents",
+        Entity::Task => {
+            let story_id = story_id.ok_or("the task's story is unknown")?;
+            return Ok(format!("/api/v3/stories/{story_id}/tasks/{id}"));
+        }
+        Entity::Comment | Entity::StoryLink => {
+            return Err(format!("{entity} cannot be updated").into());
+        }
+    };
+    Ok(format!("/api/v3/{collection}/{id}"))
+}
+
+/// Convert a fetched value to the shape the update endpoints accept.
+fn restorable(key: &str, value: &serde_json::Value) -> serde_json::Value {
+    match (key, value) {
+        // Labels come back as full objects but are set by name
+        ("labels", serde_json::Value::Array(labels)) => labels
+            .iter()
+            .map(|l| serde_json::json!({ "name": l.get("name").cloned().unwrap_or_default() }))
+            .collect(),
+        _ => value.clone(),
+    }
+}
+
+fn display_id(id: &serde_json::Value) -> String {
+    match id {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,393 @@
+/// A 1-based line and column (in characters) in a template file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Where a mapping key or sequence item appears in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySpan {
+    /// Dotted path, with sequence items as indexes (`operations.2.fields.name`).
+    pub path: String,
+    /// The key itself, or the `-` of a sequence item.
+    pub key: Position,
+    /// The start of an inline value on the same line, and its text.
+    pub value: Option<(Position, String)>,
+}
+
+/// Key and value locations of a YAML or JSON document.
+///
+/// serde_yaml does not expose spans, so this is a separate scan. Block-style
+/// YAML is read by indentation (block mappings, block sequences and block
+/// scalars). A document that is a single flow collection, which includes any
+/// JSON template, is read bracket by bracket instead; flow collections nested
+/// inside block YAML are only indexed at the line where they start.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    spans: Vec<KeySpan>,
+}
+
+struct Frame {
+    indent: usize,
+    path: String,
+    is_item: bool,
+    items: usize,
+}
+
+impl SourceMap {
+    pub fn new(content: &str) -> Self {
+        if content.trim_start().starts_with(['{', '[']) {
+            let mut scanner = FlowScanner::new(content);
+            scanner.value("");
+            return SourceMap {
+                spans: scanner.spans,
+            };
+        }
+
+        let mut spans = Vec::new();
+        let mut stack: Vec<Frame> = Vec::new();
+        // Lines indented deeper than this belong to a block scalar
+        let mut block_scalar: Option<usize> = None;
+
+        for (number, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let indent = line.chars().count() - trimmed.chars().count();
+            if let Some(block) = block_scalar {
+                if trimmed.is_empty() || indent > block {
+                    continue;
+                }
+                block_scalar = None;
+            }
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+                continue;
+            }
+
+            let position = |column: usize| Position {
+                line: number + 1,
+                column: column + 1,
+            };
+            let mut column = indent;
+            let mut text = trimmed;
+
+            // Sequence items, possibly nested on one line (`- - a`)
+            while text == "-" || text.starts_with("- ") {
+                while stack
+                    .last()
+                    .is_some_and(|f| f.indent > column || (f.indent == column && f.is_item))
+                {
+                    stack.pop();
+                }
+                let parent = stack.last_mut();
+                let index = parent.as_ref().map_or(0, |f| f.items);
+                let path = join(parent.as_ref().map_or("", |f| f.path.as_str()), index);
+                if let Some(parent) = parent {
+                    parent.items += 1;
+                }
+                let rest = text[1..].trim_start();
+                let content_column = column + text.chars().count() - rest.chars().count();
+                if rest.starts_with('|') || rest.starts_with('>') {
+                    block_scalar = Some(column);
+                }
+                spans.push(KeySpan {
+                    path: path.clone(),
+                    key: position(column),
+                    value: (!rest.is_empty() && split_key(rest).is_none())
+                        .then(|| value_at(position(content_column), rest)),
+                });
+                stack.push(Frame {
+                    indent: column,
+                    path,
+                    is_item: true,
+                    items: 0,
+                });
+                column = content_column;
+                text = rest;
+            }
+
+            let Some((key, value)) = split_key(text) else {
+                continue;
+            };
+            while stack.last().is_some_and(|f| f.indent >= column) {
+                stack.pop();
+            }
+            let path = join(stack.last().map_or("", |f| f.path.as_str()), key);
+            let value_column = column + text.chars().count() - value.chars().count();
+            let value = value.trim_end();
+            if value.starts_with('|') || value.starts_with('>') {
+                block_scalar = Some(column);
+            }
+            spans.push(KeySpan {
+                path: path.clone(),
+                key: position(column),
+                value: (!value.is_empty() && !value.starts_with('#'))
+                    .then(|| value_at(position(value_column), value)),
+            });
+            stack.push(Frame {
+                indent: column,
+                path,
+                is_item: false,
+                items: 0,
+            });
+        }
+        SourceMap { spans }
+    }
+
+    /// The span recorded for `path`.
+    pub fn get(&self, path: &str) -> Option<&KeySpan> {
+        self.spans.iter().find(|s| s.path == path)
+    }
+
+    /// Spans under `prefix`, with the prefix (and its dot) removed from paths.
+    pub fn under(&self, prefix: &str) -> Vec<KeySpan> {
+        let dotted = format!("{prefix}.");
+        self.spans
+            .iter()
+            .filter_map(|s| {
+                s.path.strip_prefix(&dotted).map(|rest| KeySpan {
+                    path: rest.to_string(),
+                    ..s.clone()
+                })
+            })
+            .collect()
+    }
+
+    pub fn spans(&self) -> &[KeySpan] {
+        &self.spans
+    }
+}
+
+fn join(parent: &str, child: impl std::fmt::Display) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{parent}.{child}")
+    }
+}
+
+/// Split `key: value` into the unquoted key and the rest after the colon.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    let (key, rest) = if let Some(q) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let end = text[1..].find(q)? + 1;
+        (&text[1..end], text[end + 1..].strip_prefix(':')?)
+    } else {
+        if text.starts_with(['{', '[', '$', '&', '*', '!', '|', '>']) {
+            return None;
+        }
+        let colon = text
+            .match_indices(':')
+            .map(|(i, _)| i)
+            .find(|&i| text[i + 1..].is_empty() || text[i + 1..].starts_with([' ', '\t']))?;
+        (text[..colon].trim_end(), &text[colon + 1..])
+    };
+    (rest.is_empty() || rest.starts_with([' ', '\t'])).then(|| (key, rest.trim_start()))
+}
+
+/// The value as written, without a trailing comment or surrounding quotes,
+/// and where that text starts.
+fn value_at(start: Position, value: &str) -> (Position, String) {
+    if let Some(q) = value.chars().next().filter(|c| *c == '"' || *c == '\'')
+        && let Some(end) = value[1..].find(q)
+    {
+        let column = start.column + 1;
+        return (Position { column, ..start }, value[1..1 + end].to_string());
+    }
+    let value = match value.find(" #") {
+        Some(i) => &value[..i],
+        None => value,
+    };
+    (start, value.trim_end().to_string())
+}
+
+/// Reads a flow collection (JSON, or YAML written like it) one character at
+/// a time. Malformed input just ends the scan early; parsing reports it.
+struct FlowScanner {
+    chars: Vec<char>,
+    next: usize,
+    at: Position,
+    spans: Vec<KeySpan>,
+}
+
+impl FlowScanner {
+    fn new(content: &str) -> Self {
+        FlowScanner {
+            chars: content.chars().collect(),
+            next: 0,
+            at: Position { line: 1, column: 1 },
+            spans: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.next).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.next += 1;
+        if c == '\n' {
+            self.at = Position {
+                line: self.at.line + 1,
+                column: 1,
+            };
+        } else {
+            self.at.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespace and `#` comments.
+    fn skip_blank(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Record a key or item at `key`, then read its value.
+    fn entry(&mut self, path: String, key: Position) {
+        let index = self.spans.len();
+        self.spans.push(KeySpan {
+            path: path.clone(),
+            key,
+            value: None,
+        });
+        self.spans[index].value = self.value(&path);
+    }
+
+    /// Read a value, returning a scalar's text and where it starts.
+    fn value(&mut self, path: &str) -> Option<(Position, String)> {
+        self.skip_blank();
+        match self.peek()? {
+            '{' => {
+                self.bump();
+                self.mapping(path);
+                None
+            }
+            '[' => {
+                self.bump();
+                self.sequence(path);
+                None
+            }
+            '"' | '\'' => self.quoted(),
+            _ => self.plain(),
+        }
+    }
+
+    fn mapping(&mut self, path: &str) {
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                Some('}') => {
+                    self.bump();
+                    return;
+                }
+                Some(',') => {
+                    self.bump();
+                    continue;
+                }
+                None => return,
+                _ => {}
+            }
+            let key_at = self.at;
+            let key = match self.peek() {
+                Some('"' | '\'') => self.quoted(),
+                _ => self.plain_key(),
+            };
+            let Some((_, key)) = key else {
+                return;
+            };
+            self.skip_blank();
+            if self.bump() != Some(':') {
+                return;
+            }
+            self.entry(join(path, key), key_at);
+        }
+    }
+
+    fn sequence(&mut self, path: &str) {
+        let mut index = 0;
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                Some(']') => {
+                    self.bump();
+                    return;
+                }
+                Some(',') => {
+                    self.bump();
+                    continue;
+                }
+                None => return,
+                _ => {}
+            }
+            let start = self.next;
+            self.entry(join(path, index), self.at);
+            if self.next == start {
+                return;
+            }
+            index += 1;
+        }
+    }
+
+    /// A quoted string's text as written (escapes included, so columns
+    /// within it line up with the source), starting after the quote.
+    fn quoted(&mut self) -> Option<(Position, String)> {
+        let quote = self.bump()?;
+        let start = self.at;
+        let mut text = String::new();
+        loop {
+            let c = self.bump()?;
+            if c == quote {
+                return Some((start, text));
+            }
+            text.push(c);
+            if c == '\\' && quote == '"' {
+                text.push(self.bump()?);
+            }
+        }
+    }
+
+    /// An unquoted scalar, up to the end of its collection entry.
+    fn plain(&mut self) -> Option<(Position, String)> {
+        let start = self.at;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if matches!(c, ',' | '}' | ']' | '\n') {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+        let text = text.trim_end().to_string();
+        (!text.is_empty()).then_some((start, text))
+    }
+
+    /// An unquoted mapping key, up to its colon.
+    fn plain_key(&mut self) -> Option<(Position, String)> {
+        let start = self.at;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if matches!(c, ':' | ',' | '}' | ']' | '\n') {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+        let text = text.trim_end().to_string();
+        (!text.is_empty()).then_some((start, text))
+    }
+}
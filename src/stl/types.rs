@@ -3,6 +3,8 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use super::span::KeySpan;
+
 /// A parsed STL template document.
 #[derive(Debug, Deserialize)]
 pub struct Template {
@@ -11,6 +13,49 @@ pub struct Template {
     pub vars: Option<HashMap<String, serde_yaml::Value>>,
     pub on_error: Option<ErrorHandling>,
     pub operations: Vec<Operation>,
+    /// Typed declarations from `vars`. The parser moves them here and leaves
+    /// each var's default (or null) in `vars`.
+    #[serde(skip)]
+    pub var_specs: HashMap<String, VarSpec>,
+}
+
+/// A typed variable declaration: `vars: { name: { type: int, ... } }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VarSpec {
+    #[serde(rename = "type")]
+    pub var_type: VarType,
+    #[serde(default)]
+    pub required: bool,
+    pub default: Option<serde_yaml::Value>,
+    pub description: Option<String>,
+    pub choices: Option<Vec<String>>,
+    pub pattern: Option<String>,
+}
+
+/// Declared type of a variable.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VarType {
+    String,
+    Int,
+    Bool,
+    Date,
+    Member,
+    Enum,
+}
+
+impl fmt::Display for VarType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarType::String => write!(f, "string"),
+            VarType::Int => write!(f, "int"),
+            VarType::Bool => write!(f, "bool"),
+            VarType::Date => write!(f, "date"),
+            VarType::Member => write!(f, "member"),
+            VarType::Enum => write!(f, "enum"),
+        }
+    }
 }
 
 /// Informational metadata (not sent to the API).
@@ -25,6 +70,16 @@ pub struct Meta {
 #[serde(rename_all = "snake_case")]
 pub enum ErrorHandling {
     Continue,
+    /// Stop and undo every operation that already ran.
+    Rollback,
+}
+
+/// What a `find` operation does when nothing matches.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingPolicy {
+    Fail,
+    Empty,
 }
 
 /// A single operation in the template.
@@ -35,14 +90,50 @@ pub struct Operation {
     pub alias: Option<String>,
     pub id: Option<serde_yaml::Value>,
     pub on_error: Option<ErrorHandling>,
+    pub on_missing: Option<MissingPolicy>,
     pub fields: Option<serde_yaml::Mapping>,
     pub repeat: Option<Vec<serde_yaml::Mapping>>,
+    /// Run the operation once per item of a query or earlier result; each
+    /// item is available as `$item(field)`.
+    pub for_each: Option<serde_yaml::Mapping>,
+    /// Only run the operation when this expression is true; otherwise it is
+    /// reported as skipped.
+    pub when: Option<String>,
+    /// Where the operation was written. Set by the parser for templates read
+    /// from a file, including operations pulled in by `include`.
+    #[serde(skip)]
+    pub origin: Option<Origin>,
+}
+
+/// Source location of an operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Origin {
+    pub file: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Position of the operation within its own file.
+    pub index: usize,
+    /// Alias namespace of the include that pulled the operation in.
+    pub namespace: Option<String>,
+    /// Locations of the operation's keys and values, relative to the
+    /// operation (`fields.name`).
+    pub spans: Vec<KeySpan>,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}", self.file, line),
+            None => write!(f, "{}", self.file),
+        }
+    }
 }
 
-/// Write-only action vocabulary.
+/// Action vocabulary. Everything but `find` writes to the API.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Action {
+    Find,
     Create,
     Update,
     Delete,
@@ -56,6 +147,7 @@ pub enum Action {
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Action::Find => write!(f, "find"),
             Action::Create => write!(f, "create"),
             Action::Update => write!(f, "update"),
             Action::Delete => write!(f, "delete"),
@@ -116,6 +208,9 @@ pub struct OperationResult {
     pub status: String,
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Why a skipped operation did not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Full execution result.
@@ -123,6 +218,22 @@ pub struct OperationResult {
 pub struct ExecutionResult {
     pub operations: Vec<OperationResult>,
     pub summary: ExecutionSummary,
+    /// Undo steps taken after a failure under `on_error: rollback`, most
+    /// recent operation first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<Vec<RollbackResult>>,
+}
+
+/// Outcome of undoing one completed operation.
+#[derive(Debug, Clone, Serialize)]
+pub struct RollbackResult {
+    pub index: usize,
+    pub action: String,
+    pub entity: String,
+    pub id: Option<serde_json::Value>,
+    /// "rolled_back", "partial", "failed" or "not_supported".
+    pub status: String,
+    pub detail: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -130,4 +241,5 @@ pub struct ExecutionSummary {
     pub total: usize,
     pub succeeded: usize,
     pub failed: usize,
+    pub skipped: usize,
 }
@@ -1,17 +1,47 @@
 use std::collections::{HashMap, HashSet};
 
-use super::types::{Action, Entity, Template};
+use super::condition;
+use super::finder::{FINDABLE_ENTITIES, FOR_EACH_SOURCES, SEARCHABLE_ENTITIES};
+use super::functions;
+use super::resolver::split_ref;
+use super::span::{KeySpan, Position};
+use super::types::{Action, Entity, ErrorHandling, Operation, Origin, Template};
+use super::vars;
 
 /// A validation error with context.
 #[derive(Debug, Clone)]
 pub struct ValidationError {
     pub message: String,
     pub operation_index: Option<usize>,
+    /// File and line of the operation, when the template was read from a file.
+    pub origin: Option<Origin>,
+    /// Where in `origin.file` the problem is: the offending key or value when
+    /// it can be found, otherwise the start of the operation.
+    pub position: Option<Position>,
+}
+
+impl ValidationError {
+    /// An error about the template, or about the operation at
+    /// `operation_index`; where it is in the file is filled in later.
+    pub fn new(message: impl Into<String>, operation_index: Option<usize>) -> Self {
+        ValidationError {
+            message: message.into(),
+            operation_index,
+            origin: None,
+            position: None,
+        }
+    }
 }
 
 impl std::fmt::Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(idx) = self.operation_index {
+        if let Some(origin) = &self.origin {
+            match self.position {
+                Some(position) => write!(f, "{}:{position}", origin.file)?,
+                None => write!(f, "{origin}")?,
+            }
+            write!(f, ": operation {}: {}", origin.index + 1, self.message)
+        } else if let Some(idx) = self.operation_index {
             write!(f, "operation {}: {}", idx + 1, self.message)
         } else {
             write!(f, "{}", self.message)
@@ -27,13 +57,13 @@ pub fn validate(template: &Template) -> Vec<ValidationError> {
 
     // 1. Version must be 1
     if template.version != 1 {
-        errors.push(ValidationError {
-            message: format!(
+        errors.push(ValidationError::new(
+            format!(
                 "unsupported version {}; only version 1 is supported",
                 template.version
             ),
-            operation_index: None,
-        });
+            None,
+        ));
     }
 
     // Collect declared variable names
@@ -47,18 +77,42 @@ pub fn validate(template: &Template) -> Vec<ValidationError> {
     if let Some(vars) = &template.vars {
         for name in vars.keys() {
             if !is_valid_var_name(name) {
-                errors.push(ValidationError {
-                    message: format!(
-                        "invalid variable name '{name}': must match [a-zA-Z][a-zA-Z0-9_]*"
-                    ),
-                    operation_index: None,
-                });
+                errors.push(ValidationError::new(
+                    format!("invalid variable name '{name}': must match [a-zA-Z][a-zA-Z0-9_]*"),
+                    None,
+                ));
             }
         }
     }
 
+    // Typed var declarations and their current values
+    let mut typed: Vec<_> = template.var_specs.iter().collect();
+    typed.sort_by_key(|(name, _)| name.as_str());
+    for (name, spec) in typed {
+        let value = template
+            .vars
+            .as_ref()
+            .and_then(|v| v.get(name))
+            .cloned()
+            .unwrap_or_default();
+        let problems = vars::check_spec(spec);
+        let value_problem = if problems.is_empty() {
+            vars::check_value(spec, &value).err()
+        } else {
+            None
+        };
+        for problem in problems.into_iter().chain(value_problem) {
+            errors.push(ValidationError::new(
+                format!("var '{name}': {problem}"),
+                None,
+            ));
+        }
+    }
+
     // Track aliases defined so far (for forward-reference checking)
     let mut defined_aliases: HashMap<&str, usize> = HashMap::new();
+    // Aliases whose result is a list (repeat and for_each operations)
+    let mut list_aliases: HashSet<&str> = HashSet::new();
 
     for (idx, op) in template.operations.iter().enumerate() {
         // 4. Action-entity compatibility
@@ -80,34 +134,85 @@ pub fn validate(template: &Template) -> Vec<ValidationError> {
                 | Action::Check
                 | Action::Uncheck
         ) && op.id.is_none()
+            && op.for_each.is_none()
         {
-            errors.push(ValidationError {
-                message: format!("{} action requires an 'id' field", op.action),
-                operation_index: Some(idx),
-            });
+            errors.push(ValidationError::new(
+                format!("{} action requires an 'id' field", op.action),
+                Some(idx),
+            ));
+        }
+
+        // 6b. find needs an alias and exactly one lookup criterion
+        if op.action == Action::Find {
+            validate_find(op, idx, &mut errors);
+        } else if op.on_missing.is_some() {
+            errors.push(ValidationError::new(
+                "'on_missing' is only valid for find actions".to_string(),
+                Some(idx),
+            ));
+        }
+
+        // 6c. for_each source and $item() usage
+        if let Some(for_each) = &op.for_each {
+            validate_for_each(
+                op,
+                for_each,
+                &defined_aliases,
+                &list_aliases,
+                &declared_vars,
+                idx,
+                &mut errors,
+            );
+        } else {
+            let mut item_refs = Vec::new();
+            if let Some(fields) = &op.fields {
+                collect_item_refs(&serde_yaml::Value::Mapping(fields.clone()), &mut item_refs);
+            }
+            if let Some(id) = &op.id {
+                collect_item_refs(id, &mut item_refs);
+            }
+            for field in item_refs {
+                errors.push(ValidationError::new(
+                    format!("$item({field}) can only be used in a for_each operation"),
+                    Some(idx),
+                ));
+            }
+        }
+
+        // 6d. when: expression parses and references known names
+        if let Some(when) = &op.when {
+            validate_when(when, &defined_aliases, &declared_vars, idx, &mut errors);
         }
 
         // 9. Check duplicate aliases
         if let Some(alias) = &op.alias {
-            // 10. Alias name format
-            if !is_valid_alias_name(alias) {
-                errors.push(ValidationError {
-                    message: format!(
-                        "invalid alias name '{alias}': must match [a-zA-Z][a-zA-Z0-9_-]*"
-                    ),
-                    operation_index: Some(idx),
-                });
+            // 10. Alias name format (aliases from includes carry the
+            //     include's namespace as a prefix)
+            let local_alias = op
+                .origin
+                .as_ref()
+                .and_then(|o| o.namespace.as_ref())
+                .and_then(|ns| alias.strip_prefix(ns.as_str())?.strip_prefix('.'))
+                .unwrap_or(alias);
+            if !is_valid_alias_name(local_alias) {
+                errors.push(ValidationError::new(
+                    format!("invalid alias name '{alias}': must match [a-zA-Z][a-zA-Z0-9_-]*"),
+                    Some(idx),
+                ));
             }
             if let Some(&prev_idx) = defined_aliases.get(alias.as_str()) {
-                errors.push(ValidationError {
-                    message: format!(
+                errors.push(ValidationError::new(
+                    format!(
                         "duplicate alias '{alias}' (first defined in operation {})",
                         prev_idx + 1
                     ),
-                    operation_index: Some(idx),
-                });
+                    Some(idx),
+                ));
             } else {
                 defined_aliases.insert(alias, idx);
+                if op.repeat.is_some() || op.for_each.is_some() {
+                    list_aliases.insert(alias);
+                }
             }
         }
 
@@ -115,12 +220,12 @@ pub fn validate(template: &Template) -> Vec<ValidationError> {
         if let Some(id_val) = &op.id
             && let Some(ref_name) = extract_ref_from_yaml(id_val)
         {
-            let base = ref_base(&ref_name);
+            let base = ref_base(&ref_name, &defined_aliases);
             if !defined_aliases.contains_key(base) {
-                errors.push(ValidationError {
-                    message: format!("$ref({ref_name}) references undefined alias '{base}'"),
-                    operation_index: Some(idx),
-                });
+                errors.push(ValidationError::new(
+                    format!("$ref({ref_name}) references undefined alias '{base}'"),
+                    Some(idx),
+                ));
             }
         }
 
@@ -150,20 +255,287 @@ pub fn validate(template: &Template) -> Vec<ValidationError> {
 
         // 12. Known fields per entity (warn level — we add as errors for strictness)
         // For comment/link/check/uncheck actions, use the target entity's field list
-        if let Some(fields) = &op.fields {
-            let field_entity = match op.action {
-                Action::Comment => &Entity::Comment,
-                Action::Link | Action::Unlink => &Entity::StoryLink,
-                Action::Check | Action::Uncheck => &Entity::Task,
-                _ => &op.entity,
-            };
-            check_known_fields(field_entity, fields, idx, &mut errors);
+        if let Some(fields) = &op.fields
+            && op.action != Action::Find
+        {
+            check_known_fields(
+                field_entity(&op.action, &op.entity),
+                fields,
+                idx,
+                &mut errors,
+            );
         }
     }
 
+    attach_origins(template, &mut errors);
     errors
 }
 
+/// Point errors about an operation at the file and line it was written at.
+fn attach_origins(template: &Template, errors: &mut [ValidationError]) {
+    for error in errors {
+        if let Some(idx) = error.operation_index {
+            error.origin = template
+                .operations
+                .get(idx)
+                .and_then(|op| op.origin.clone());
+            error.position = error
+                .origin
+                .as_ref()
+                .and_then(|origin| locate(origin, &error.message));
+        }
+    }
+}
+
+/// Find what an error message is about within its operation.
+///
+/// Tries, in order: a `$ref()`/`$var()`/`$fn()`/`$item()` expression from the
+/// message inside one of the operation's values, then a quoted name that is
+/// one of its keys or values. Falls back to the start of the operation.
+fn locate(origin: &Origin, message: &str) -> Option<Position> {
+    let start = origin
+        .line
+        .zip(origin.column)
+        .map(|(line, column)| Position { line, column });
+
+    for token in expressions(message) {
+        for span in &origin.spans {
+            if let Some((at, text)) = &span.value
+                && let Some(offset) = text.find(token)
+            {
+                return Some(Position {
+                    column: at.column + text[..offset].chars().count(),
+                    ..*at
+                });
+            }
+        }
+    }
+
+    let quoted = message.split('\'').skip(1).step_by(2);
+    for name in quoted {
+        let last = |span: &&KeySpan| span.path.rsplit('.').next() == Some(name);
+        if let Some(span) = origin.spans.iter().find(last) {
+            return Some(span.key);
+        }
+        let value = |span: &&KeySpan| span.value.as_ref().is_some_and(|(_, v)| v == name);
+        if let Some((at, _)) = origin
+            .spans
+            .iter()
+            .find(value)
+            .and_then(|s| s.value.as_ref())
+        {
+            return Some(*at);
+        }
+    }
+    start
+}
+
+/// `$ref(...)`-style expressions quoted in a message.
+fn expressions(message: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    for prefix in ["$fn(", "$ref(", "$var(", "$item("] {
+        let mut from = 0;
+        while let Some(start) = message[from..].find(prefix).map(|i| from + i) {
+            let end = if prefix == "$fn(" {
+                match functions::next_call(message, start) {
+                    Some(Ok((_, end))) => end,
+                    _ => break,
+                }
+            } else {
+                match message[start..].find(')') {
+                    Some(i) => start + i + 1,
+                    None => break,
+                }
+            };
+            found.push(&message[start..end]);
+            from = end;
+        }
+    }
+    found
+}
+
+/// Validate the shape of a `find` operation.
+fn validate_find(op: &Operation, idx: usize, errors: &mut Vec<ValidationError>) {
+    let mut error = |message: String| errors.push(ValidationError::new(message, Some(idx)));
+
+    if op.alias.is_none() {
+        error("find action requires an 'alias'".to_string());
+    }
+    if op.id.is_some() {
+        error("find action does not take an 'id' field".to_string());
+    }
+    if op.repeat.is_some() {
+        error("find action does not support 'repeat'".to_string());
+    }
+
+    let has = |field: &str| {
+        op.fields
+            .as_ref()
+            .is_some_and(|f| f.get(serde_yaml::Value::String(field.into())).is_some())
+    };
+    match (has("name"), has("query")) {
+        (false, false) => error("find action requires 'name' or 'query' in fields".to_string()),
+        (true, true) => error("find action takes either 'name' or 'query', not both".to_string()),
+        (false, true) if !SEARCHABLE_ENTITIES.contains(&op.entity) => error(format!(
+            "find {} does not support 'query'; use 'name'",
+            op.entity
+        )),
+        _ => {}
+    }
+
+    if let Some(fields) = &op.fields {
+        for key in fields.keys() {
+            if let serde_yaml::Value::String(name) = key
+                && name != "name"
+                && name != "query"
+            {
+                error(format!("unknown field '{name}' for find action"));
+            }
+        }
+    }
+}
+
+/// Validate a `when:` expression.
+fn validate_when(
+    when: &str,
+    defined_aliases: &HashMap<&str, usize>,
+    declared_vars: &HashSet<&str>,
+    idx: usize,
+    errors: &mut Vec<ValidationError>,
+) {
+    let mut error = |message: String| errors.push(ValidationError::new(message, Some(idx)));
+
+    let expr = match condition::parse(when) {
+        Ok(expr) => expr,
+        Err(e) => {
+            error(format!("invalid when expression: {e}"));
+            return;
+        }
+    };
+    let mut vars = Vec::new();
+    let mut refs = Vec::new();
+    condition::references(&expr, &mut vars, &mut refs);
+    for ref_name in refs {
+        let base = ref_base(ref_name, defined_aliases);
+        if !defined_aliases.contains_key(base) {
+            error(format!(
+                "$ref({ref_name}) references undefined alias '{base}'"
+            ));
+        }
+    }
+    for var_name in vars {
+        if !declared_vars.contains(var_name) {
+            error(format!(
+                "$var({var_name}) references undeclared variable '{var_name}'"
+            ));
+        }
+    }
+}
+
+/// Validate the source of a `for_each` operation.
+fn validate_for_each(
+    op: &Operation,
+    for_each: &serde_yaml::Mapping,
+    defined_aliases: &HashMap<&str, usize>,
+    list_aliases: &HashSet<&str>,
+    declared_vars: &HashSet<&str>,
+    idx: usize,
+    errors: &mut Vec<ValidationError>,
+) {
+    let mut error = |message: String| errors.push(ValidationError::new(message, Some(idx)));
+
+    if op.repeat.is_some() {
+        error("for_each cannot be combined with repeat".to_string());
+    }
+    if op.action == Action::Find {
+        error("find action does not support 'for_each'".to_string());
+    }
+
+    let mut sources = 0;
+    for (key, value) in for_each {
+        let serde_yaml::Value::String(key) = key else {
+            continue;
+        };
+        if FOR_EACH_SOURCES.contains(&key.as_str()) {
+            sources += 1;
+        } else if key == "done" {
+            if !value.is_bool() {
+                error("for_each 'done' must be true or false".to_string());
+            }
+        } else {
+            error(format!("unknown for_each key '{key}'"));
+        }
+    }
+    if sources != 1 {
+        error(format!(
+            "for_each requires exactly one of {}",
+            FOR_EACH_SOURCES
+                .iter()
+                .map(|s| format!("'{s}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    // `items` reuses a list result, so it must name a repeat/for_each alias
+    if let Some(items) = for_each.get(serde_yaml::Value::String("items".into())) {
+        match extract_ref_from_yaml(items) {
+            Some(alias) if list_aliases.contains(alias.as_str()) => {}
+            Some(alias) if defined_aliases.contains_key(alias.as_str()) => error(format!(
+                "for_each items: alias '{alias}' is not a repeat or for_each operation"
+            )),
+            Some(alias) => error(format!(
+                "$ref({alias}) references undefined alias '{alias}'"
+            )),
+            None => error("for_each items must be a $ref(alias)".to_string()),
+        }
+    }
+
+    let mut source = for_each.clone();
+    source.remove(serde_yaml::Value::String("items".into()));
+    check_refs_and_vars_in_mapping(&source, defined_aliases, declared_vars, idx, errors);
+
+    let mut item_refs = Vec::new();
+    collect_item_refs(
+        &serde_yaml::Value::Mapping(for_each.clone()),
+        &mut item_refs,
+    );
+    for field in item_refs {
+        errors.push(ValidationError::new(
+            format!("$item({field}) cannot be used in the for_each source"),
+            Some(idx),
+        ));
+    }
+}
+
+/// Collect the field names of all `$item(field)` references in a YAML value.
+fn collect_item_refs(value: &serde_yaml::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::String(s) => {
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("$item(") {
+                let after = &rest[start + 6..];
+                let Some(end) = after.find(')') else {
+                    break;
+                };
+                refs.push(after[..end].to_string());
+                rest = &after[end + 1..];
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for item in seq {
+                collect_item_refs(item, refs);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for value in map.values() {
+                collect_item_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Check if a string is a valid alias name: [a-zA-Z][a-zA-Z0-9_-]*
 fn is_valid_alias_name(name: &str) -> bool {
     let mut chars = name.chars();
@@ -175,7 +547,7 @@ fn is_valid_alias_name(name: &str) -> bool {
 }
 
 /// Check if a string is a valid variable name: [a-zA-Z][a-zA-Z0-9_]*
-fn is_valid_var_name(name: &str) -> bool {
+pub(super) fn is_valid_var_name(name: &str) -> bool {
     let mut chars = name.chars();
     match chars.next() {
         Some(c) if c.is_ascii_alphabetic() => {}
@@ -193,6 +565,81 @@ fn extract_ref_from_yaml(val: &serde_yaml::Value) -> Option<String> {
     }
 }
 
+/// The operations each operation references through `$ref()`, as indexes
+/// into `template.operations`, sorted and without duplicates.
+///
+/// References to undefined aliases are left out; `validate` reports them.
+pub fn dependencies(template: &Template) -> Vec<Vec<usize>> {
+    collect_dependencies(template, true)
+}
+
+/// Like [`dependencies`], leaving out references made only by `when`. These
+/// are the operations whose results an operation's request is built from.
+pub fn input_dependencies(template: &Template) -> Vec<Vec<usize>> {
+    collect_dependencies(template, false)
+}
+
+fn collect_dependencies(template: &Template, with_when: bool) -> Vec<Vec<usize>> {
+    let mut defined_aliases: HashMap<&str, usize> = HashMap::new();
+    let mut dependencies = Vec::new();
+
+    for (idx, op) in template.operations.iter().enumerate() {
+        let mut refs = Vec::new();
+        for value in op.id.iter() {
+            collect_ref_refs(value, &mut refs);
+        }
+        for mapping in op
+            .fields
+            .iter()
+            .chain(op.for_each.iter())
+            .chain(op.repeat.iter().flatten())
+        {
+            for value in mapping.values() {
+                collect_ref_refs(value, &mut refs);
+            }
+        }
+        if with_when
+            && let Some(when) = &op.when
+            && let Ok(expr) = condition::parse(when)
+        {
+            let mut when_refs = Vec::new();
+            condition::references(&expr, &mut Vec::new(), &mut when_refs);
+            refs.extend(when_refs.into_iter().map(String::from));
+        }
+
+        let mut deps: Vec<usize> = refs
+            .iter()
+            .filter_map(|r| defined_aliases.get(ref_base(r, &defined_aliases)).copied())
+            .collect();
+        deps.sort_unstable();
+        deps.dedup();
+        dependencies.push(deps);
+
+        if let Some(alias) = &op.alias {
+            defined_aliases.entry(alias).or_insert(idx);
+        }
+    }
+    dependencies
+}
+
+/// Recursively collect `$ref()` names from a YAML value.
+fn collect_ref_refs(value: &serde_yaml::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::String(s) => refs.extend(extract_ref_refs(s)),
+        serde_yaml::Value::Sequence(seq) => {
+            for item in seq {
+                collect_ref_refs(item, refs);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for item in map.values() {
+                collect_ref_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Extract the alias name from a `$ref(name)` string.
 fn extract_ref(s: &str) -> Option<String> {
     let trimmed = s.trim();
@@ -203,9 +650,10 @@ fn extract_ref(s: &str) -> Option<String> {
     }
 }
 
-/// Get the base alias from a ref like "alias.field" → "alias".
-fn ref_base(ref_name: &str) -> &str {
-    ref_name.split('.').next().unwrap_or(ref_name)
+/// Get the base alias from a ref like "alias.field" → "alias", allowing for
+/// namespaced aliases from includes ("ci.pipeline.id" → "ci.pipeline").
+fn ref_base<'a>(ref_name: &'a str, defined_aliases: &HashMap<&str, usize>) -> &'a str {
+    split_ref(ref_name, |a| defined_aliases.contains_key(a)).0
 }
 
 /// Extract all $var(name) references from a string.
@@ -253,7 +701,7 @@ fn check_refs_and_vars_in_mapping(
     }
 }
 
-/// Recursively check $ref() and $var() in a YAML value.
+/// Recursively check $ref(), $var() and $fn() in a YAML value.
 fn check_refs_and_vars_in_value(
     value: &serde_yaml::Value,
     defined_aliases: &HashMap<&str, usize>,
@@ -265,23 +713,40 @@ fn check_refs_and_vars_in_value(
         serde_yaml::Value::String(s) => {
             // Check $ref() references
             for ref_name in extract_ref_refs(s) {
-                let base = ref_base(&ref_name);
+                let base = ref_base(&ref_name, defined_aliases);
                 if !defined_aliases.contains_key(base) {
-                    errors.push(ValidationError {
-                        message: format!("$ref({ref_name}) references undefined alias '{base}'"),
-                        operation_index: Some(op_idx),
-                    });
+                    errors.push(ValidationError::new(
+                        format!("$ref({ref_name}) references undefined alias '{base}'"),
+                        Some(op_idx),
+                    ));
                 }
             }
+            // Check $fn() calls parse and only use known functions
+            let mut from = 0;
+            while let Some(call) = functions::next_call(s, from) {
+                let message = match call {
+                    Ok((start, end)) => {
+                        from = end;
+                        let call = &s[start..end];
+                        match functions::parse(functions::inner(call)) {
+                            Ok(_) => continue,
+                            Err(e) => format!("{call}: {e}"),
+                        }
+                    }
+                    Err(e) => {
+                        from = s.len();
+                        e
+                    }
+                };
+                errors.push(ValidationError::new(message, Some(op_idx)));
+            }
             // Check $var() references
             for var_name in extract_var_refs(s) {
                 if !declared_vars.contains(var_name.as_str()) {
-                    errors.push(ValidationError {
-                        message: format!(
-                            "$var({var_name}) references undeclared variable '{var_name}'"
-                        ),
-                        operation_index: Some(op_idx),
-                    });
+                    errors.push(ValidationError::new(
+                        format!("$var({var_name}) references undeclared variable '{var_name}'"),
+                        Some(op_idx),
+                    ));
                 }
             }
         }
@@ -305,6 +770,7 @@ fn validate_action_entity(
     errors: &mut Vec<ValidationError>,
 ) {
     let valid = match action {
+        Action::Find => FINDABLE_ENTITIES.contains(&entity),
         Action::Create => !matches!(entity, Entity::Comment | Entity::StoryLink),
         Action::Update => !matches!(entity, Entity::Comment | Entity::StoryLink | Entity::Task),
         Action::Delete => !matches!(entity, Entity::Comment | Entity::Group),
@@ -314,10 +780,10 @@ fn validate_action_entity(
         Action::Check | Action::Uncheck => matches!(entity, Entity::Task),
     };
     if !valid {
-        errors.push(ValidationError {
-            message: format!("'{action}' action is not valid for '{entity}' entity"),
-            operation_index: Some(idx),
-        });
+        errors.push(ValidationError::new(
+            format!("'{action}' action is not valid for '{entity}' entity"),
+            Some(idx),
+        ));
     }
 }
 
@@ -348,16 +814,35 @@ fn validate_required_fields_on_create(
             .map(|f| f.get(serde_yaml::Value::String(field.into())).is_some())
             .unwrap_or(false);
         if !has_field {
-            errors.push(ValidationError {
-                message: format!("create {entity} requires field '{field}'"),
-                operation_index: Some(idx),
-            });
+            errors.push(ValidationError::new(
+                format!("create {entity} requires field '{field}'"),
+                Some(idx),
+            ));
         }
     }
 }
 
 /// Known fields per entity type.
-fn known_fields(entity: &Entity) -> &[&str] {
+/// Field names allowed in an operation's `fields`.
+pub fn allowed_fields(action: &Action, entity: &Entity) -> &'static [&'static str] {
+    match action {
+        Action::Find => &["name", "query"],
+        _ => known_fields(field_entity(action, entity)),
+    }
+}
+
+/// The entity whose fields an operation takes: comment, link and
+/// check/uncheck actions describe a comment, story link or task.
+fn field_entity<'a>(action: &Action, entity: &'a Entity) -> &'a Entity {
+    match action {
+        Action::Comment => &Entity::Comment,
+        Action::Link | Action::Unlink => &Entity::StoryLink,
+        Action::Check | Action::Uncheck => &Entity::Task,
+        _ => entity,
+    }
+}
+
+fn known_fields(entity: &Entity) -> &'static [&'static str] {
     match entity {
         Entity::Story => &[
             "name",
@@ -448,10 +933,10 @@ fn check_known_fields_with_extra(
             && !known.contains(&name.as_str())
             && !extra_allowed.contains(&name.as_str())
         {
-            errors.push(ValidationError {
-                message: format!("unknown field '{name}' for {entity} entity"),
-                operation_index: Some(idx),
-            });
+            errors.push(ValidationError::new(
+                format!("unknown field '{name}' for {entity} entity"),
+                Some(idx),
+            ));
         }
     }
 }
@@ -459,18 +944,40 @@ fn check_known_fields_with_extra(
 /// Additional validation for `sync` mode.
 ///
 /// Must be called after `validate()`. Checks:
+/// - No `for_each`, `when` or `on_error: rollback`
 /// - Every `create` operation has an `alias`
 /// - Every repeat entry has a unique `key` field
 /// - Inline tasks (in `tasks` field) each have a unique `key` field
 pub fn validate_for_sync(template: &Template) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
+    let rollback = "sync does not support 'on_error: rollback'; use `template run`";
+    if template.on_error == Some(ErrorHandling::Rollback) {
+        errors.push(ValidationError::new(rollback.to_string(), None));
+    }
+
     for (idx, op) in template.operations.iter().enumerate() {
+        if op.on_error == Some(ErrorHandling::Rollback) {
+            errors.push(ValidationError::new(rollback.to_string(), Some(idx)));
+        }
+        if op.for_each.is_some() {
+            errors.push(ValidationError::new(
+                "sync does not support 'for_each'; use `template run`".to_string(),
+                Some(idx),
+            ));
+        }
+        if op.when.is_some() {
+            errors.push(ValidationError::new(
+                "sync does not support 'when'; use `template run`".to_string(),
+                Some(idx),
+            ));
+        }
+
         if op.action == Action::Create && op.alias.is_none() {
-            errors.push(ValidationError {
-                message: "sync requires an 'alias' on every create operation".to_string(),
-                operation_index: Some(idx),
-            });
+            errors.push(ValidationError::new(
+                "sync requires an 'alias' on every create operation".to_string(),
+                Some(idx),
+            ));
         }
 
         // Validate repeat entry keys
@@ -480,32 +987,26 @@ pub fn validate_for_sync(template: &Template) -> Vec<ValidationError> {
                 match entry.get(serde_yaml::Value::String("key".to_string())) {
                     Some(serde_yaml::Value::String(key)) => {
                         if !seen_keys.insert(key.clone()) {
-                            errors.push(ValidationError {
-                                message: format!(
-                                    "duplicate key '{key}' in repeat entry {}",
-                                    entry_idx + 1
-                                ),
-                                operation_index: Some(idx),
-                            });
+                            errors.push(ValidationError::new(
+                                format!("duplicate key '{key}' in repeat entry {}", entry_idx + 1),
+                                Some(idx),
+                            ));
                         }
                     }
                     Some(_) => {
-                        errors.push(ValidationError {
-                            message: format!(
-                                "repeat entry {} 'key' must be a string",
-                                entry_idx + 1
-                            ),
-                            operation_index: Some(idx),
-                        });
+                        errors.push(ValidationError::new(
+                            format!("repeat entry {} 'key' must be a string", entry_idx + 1),
+                            Some(idx),
+                        ));
                     }
                     None => {
-                        errors.push(ValidationError {
-                            message: format!(
+                        errors.push(ValidationError::new(
+                            format!(
                                 "repeat entry {} is missing required 'key' field for sync",
                                 entry_idx + 1
                             ),
-                            operation_index: Some(idx),
-                        });
+                            Some(idx),
+                        ));
                     }
                 }
 
@@ -532,29 +1033,26 @@ pub fn validate_for_sync(template: &Template) -> Vec<ValidationError> {
                     match task_map.get(serde_yaml::Value::String("key".to_string())) {
                         Some(serde_yaml::Value::String(key)) => {
                             if !seen_keys.insert(key.clone()) {
-                                errors.push(ValidationError {
-                                    message: format!(
-                                        "duplicate task key '{key}' in task {}",
-                                        task_idx + 1
-                                    ),
-                                    operation_index: Some(idx),
-                                });
+                                errors.push(ValidationError::new(
+                                    format!("duplicate task key '{key}' in task {}", task_idx + 1),
+                                    Some(idx),
+                                ));
                             }
                         }
                         Some(_) => {
-                            errors.push(ValidationError {
-                                message: format!("task {} 'key' must be a string", task_idx + 1),
-                                operation_index: Some(idx),
-                            });
+                            errors.push(ValidationError::new(
+                                format!("task {} 'key' must be a string", task_idx + 1),
+                                Some(idx),
+                            ));
                         }
                         None => {
-                            errors.push(ValidationError {
-                                message: format!(
+                            errors.push(ValidationError::new(
+                                format!(
                                     "task {} is missing required 'key' field for sync",
                                     task_idx + 1
                                 ),
-                                operation_index: Some(idx),
-                            });
+                                Some(idx),
+                            ));
                         }
                     }
                 }
@@ -562,5 +1060,6 @@ pub fn validate_for_sync(template: &Template) -> Vec<ValidationError> {
         }
     }
 
+    attach_origins(template, &mut errors);
     errors
 }
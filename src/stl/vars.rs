@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use chrono::NaiveDate;
+
+use crate::interactive::Prompter;
+
+use super::types::{Template, VarSpec, VarType};
+
+/// Move typed declarations out of a `vars` block.
+///
+/// A var whose value is a mapping with a `type` key is a declaration; its
+/// entry in `vars` is replaced by its default (or null).
+pub fn extract_specs(
+    vars: &mut HashMap<String, serde_yaml::Value>,
+) -> Result<HashMap<String, VarSpec>, String> {
+    let mut specs = HashMap::new();
+    for (name, value) in vars.iter_mut() {
+        let is_declaration = value
+            .as_mapping()
+            .is_some_and(|m| m.contains_key(serde_yaml::Value::String("type".into())));
+        if !is_declaration {
+            continue;
+        }
+        let spec: VarSpec =
+            serde_yaml::from_value(value.clone()).map_err(|e| format!("var '{name}': {e}"))?;
+        *value = spec.default.clone().unwrap_or(serde_yaml::Value::Null);
+        specs.insert(name.clone(), spec);
+    }
+    Ok(specs)
+}
+
+/// Fill in the template's vars from `--var` overrides, then from prompts for
+/// required vars that still have no value.
+///
+/// Overrides for typed vars are coerced to the declared type; untyped vars
+/// take the string as-is. Prompting only happens when a `prompter` is given
+/// (i.e. on a TTY); otherwise missing required vars are an error.
+pub fn resolve(
+    template: &mut Template,
+    overrides: &[(String, String)],
+    prompter: Option<&dyn Prompter>,
+) -> Result<(), Box<dyn Error>> {
+    if !overrides.is_empty() {
+        let vars = template.vars.get_or_insert_with(Default::default);
+        for (key, raw) in overrides {
+            if !vars.contains_key(key) {
+                return Err(
+                    format!("--var '{key}' is not declared in the template's vars block").into(),
+                );
+            }
+            let value = match template.var_specs.get(key) {
+                Some(spec) => coerce(spec, raw).map_err(|e| format!("--var '{key}': {e}"))?,
+                None => serde_yaml::Value::String(raw.clone()),
+            };
+            vars.insert(key.clone(), value);
+        }
+    }
+
+    let mut missing = missing_required(
+        template.vars.as_ref().unwrap_or(&HashMap::new()),
+        &template.var_specs,
+    );
+    if let Some(prompter) = prompter {
+        for name in &missing {
+            let spec = &template.var_specs[name];
+            let value = prompt_value(name, spec, prompter)?;
+            template
+                .vars
+                .get_or_insert_with(Default::default)
+                .insert(name.clone(), value);
+        }
+        missing.clear();
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "Missing required var(s): {} (pass --var name=value)",
+            missing.join(", ")
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Names of required vars that have no value, sorted.
+pub fn missing_required(
+    vars: &HashMap<String, serde_yaml::Value>,
+    specs: &HashMap<String, VarSpec>,
+) -> Vec<String> {
+    let mut missing: Vec<String> = specs
+        .iter()
+        .filter(|(name, spec)| spec.required && vars.get(*name).is_none_or(|v| v.is_null()))
+        .map(|(name, _)| name.clone())
+        .collect();
+    missing.sort();
+    missing
+}
+
+fn prompt_value(
+    name: &str,
+    spec: &VarSpec,
+    prompter: &dyn Prompter,
+) -> Result<serde_yaml::Value, Box<dyn Error>> {
+    let message = match &spec.description {
+        Some(description) => format!("{description} ({name})"),
+        None => name.to_string(),
+    };
+    match spec.var_type {
+        VarType::Enum => {
+            let choices: Vec<&str> = spec.choices.iter().flatten().map(String::as_str).collect();
+            Ok(serde_yaml::Value::String(
+                prompter.prompt_select(&message, &choices)?,
+            ))
+        }
+        VarType::Bool => Ok(serde_yaml::Value::Bool(prompter.confirm(&message)?)),
+        _ => {
+            let message = match spec.var_type {
+                VarType::Date => format!("{message} (YYYY-MM-DD)"),
+                _ => message,
+            };
+            loop {
+                let answer = prompter.prompt_text(&message)?;
+                match coerce(spec, &answer) {
+                    Ok(value) => return Ok(value),
+                    Err(e) => eprintln!("  {e}"),
+                }
+            }
+        }
+    }
+}
+
+/// Convert a command-line string to the declared type and check it.
+pub fn coerce(spec: &VarSpec, raw: &str) -> Result<serde_yaml::Value, String> {
+    let trimmed = raw.trim();
+    let value = match spec.var_type {
+        VarType::Int => trimmed
+            .parse::<i64>()
+            .map(|n| serde_yaml::Value::Number(n.into()))
+            .map_err(|_| format!("expected an integer, got '{raw}'"))?,
+        VarType::Bool => match trimmed.to_lowercase().as_str() {
+            "true" | "yes" | "1" => serde_yaml::Value::Bool(true),
+            "false" | "no" | "0" => serde_yaml::Value::Bool(false),
+            _ => return Err(format!("expected true or false, got '{raw}'")),
+        },
+        VarType::Date | VarType::Member | VarType::Enum => {
+            serde_yaml::Value::String(trimmed.to_string())
+        }
+        VarType::String => serde_yaml::Value::String(raw.to_string()),
+    };
+    check_value(spec, &value)?;
+    Ok(value)
+}
+
+/// Check a value against its declaration. Null (no value) is accepted.
+pub fn check_value(spec: &VarSpec, value: &serde_yaml::Value) -> Result<(), String> {
+    if value.is_null() {
+        return Ok(());
+    }
+    match spec.var_type {
+        VarType::String => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| format!("expected a string, got {}", describe(value)))?;
+            if let Some(pattern) = &spec.pattern {
+                let regex = anchored(pattern)?;
+                if regex.find(s).is_none() {
+                    return Err(format!("'{s}' does not match pattern '{pattern}'"));
+                }
+            }
+        }
+        VarType::Int => {
+            value
+                .as_i64()
+                .ok_or_else(|| format!("expected an integer, got {}", describe(value)))?;
+        }
+        VarType::Bool => {
+            value
+                .as_bool()
+                .ok_or_else(|| format!("expected true or false, got {}", describe(value)))?;
+        }
+        VarType::Date => {
+            value
+                .as_str()
+                .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                .ok_or_else(|| format!("expected a date (YYYY-MM-DD), got {}", describe(value)))?;
+        }
+        VarType::Member => {
+            value
+                .as_str()
+                .filter(|s| (s.starts_with('@') && s.len() > 1) || uuid::Uuid::parse_str(s).is_ok())
+                .ok_or_else(|| {
+                    format!(
+                        "expected an @mention_name or member UUID, got {}",
+                        describe(value)
+                    )
+                })?;
+        }
+        VarType::Enum => {
+            let choices = spec.choices.as_deref().unwrap_or_default();
+            if !value
+                .as_str()
+                .is_some_and(|s| choices.iter().any(|c| c == s))
+            {
+                return Err(format!(
+                    "expected one of {}, got {}",
+                    choices.join(", "),
+                    describe(value)
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Problems with a declaration itself.
+pub fn check_spec(spec: &VarSpec) -> Vec<String> {
+    let mut errors = Vec::new();
+    match (&spec.var_type, &spec.choices) {
+        (VarType::Enum, None) => errors.push("enum vars require 'choices'".to_string()),
+        (VarType::Enum, Some(choices)) if choices.is_empty() => {
+            errors.push("enum vars require 'choices'".to_string())
+        }
+        (VarType::Enum, Some(_)) | (_, None) => {}
+        (_, Some(_)) => errors.push("'choices' is only valid for enum vars".to_string()),
+    }
+    if let Some(pattern) = &spec.pattern {
+        if spec.var_type != VarType::String {
+            errors.push("'pattern' is only valid for string vars".to_string());
+        } else if let Err(e) = anchored(pattern) {
+            errors.push(e);
+        }
+    }
+    errors
+}
+
+/// Compile `pattern` so that it must match the whole value.
+fn anchored(pattern: &str) -> Result<regress::Regex, String> {
+    regress::Regex::new(&format!("^(?:{pattern})$"))
+        .map_err(|e| format!("invalid pattern '{pattern}': {e}"))
+}
+
+fn describe(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => format!("'{s}'"),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Sequence(_) => "a list".to_string(),
+        serde_yaml::Value::Mapping(_) => "a mapping".to_string(),
+        _ => "null".to_string(),
+    }
+}
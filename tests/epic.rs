@@ -1,5 +1,7 @@
 mod support;
 
+#[path = "epic/archive.rs"]
+mod archive;
 #[path = "epic/comment.rs"]
 mod comment;
 #[path = "epic/create.rs"]
@@ -10,8 +12,14 @@ mod delete;
 mod docs;
 #[path = "epic/get.rs"]
 mod get;
+#[path = "epic/health.rs"]
+mod health;
 #[path = "epic/list.rs"]
 mod list;
+#[path = "epic/progress.rs"]
+mod progress;
+#[path = "epic/stories.rs"]
+mod stories;
 #[path = "epic/update.rs"]
 mod update;
 #[path = "epic/wizard.rs"]
@@ -22,7 +30,10 @@ pub const UUID_BOB: &str = "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb";
 
 pub fn make_list_args(desc: bool) -> shortcut_cli::commands::epic::EpicArgs {
     shortcut_cli::commands::epic::EpicArgs {
-        action: shortcut_cli::commands::epic::EpicAction::List { desc },
+        action: shortcut_cli::commands::epic::EpicAction::List {
+            desc,
+            archived: false,
+        },
     }
 }
 
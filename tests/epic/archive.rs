@@ -0,0 +1,58 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::full_epic_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::epic};
+
+#[tokio::test]
+async fn archive_epic_sets_archived() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut archived = full_epic_json(7, "Old epic", "");
+    archived["archived"] = serde_json::Value::from(true);
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/epics/7"))
+        .and(body_partial_json(serde_json::json!({ "archived": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&archived))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = epic::EpicArgs {
+        action: epic::EpicAction::Archive { id: 7 },
+    };
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Archived epic 7 - Old epic");
+}
+
+#[tokio::test]
+async fn unarchive_epic_clears_archived() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/epics/7"))
+        .and(body_partial_json(serde_json::json!({ "archived": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_epic_json(7, "Old epic", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = epic::EpicArgs {
+        action: epic::EpicAction::Unarchive { id: 7 },
+    };
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Unarchived epic 7 - Old epic");
+}
@@ -0,0 +1,231 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{epic_json, full_epic_json, health_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::epic};
+
+fn wrap(action: epic::HealthAction) -> epic::EpicArgs {
+    epic::EpicArgs {
+        action: epic::EpicAction::Health(epic::HealthArgs { action }),
+    }
+}
+
+#[tokio::test]
+async fn health_get_prints_status_and_note() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("member_cache.json"),
+        serde_json::json!({ "alice": crate::UUID_ALICE }).to_string(),
+    )
+    .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(health_json(
+            7,
+            "At Risk",
+            "Vendor API is late",
+            "2024-03-01T10:00:00Z",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(epic::HealthAction::Get { id: 7 });
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("Epic 7: At Risk\n"));
+    assert!(output.contains("  Note:    Vendor API is late"));
+    assert!(output.contains("  By:      @alice"));
+}
+
+#[tokio::test]
+async fn health_set_posts_status_and_note() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/epics/7/health"))
+        .and(body_partial_json(serde_json::json!({
+            "status": "Off Track",
+            "text": "Blocked on legal review",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(health_json(
+            7,
+            "Off Track",
+            "Blocked on legal review",
+            "2024-03-02T10:00:00Z",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(epic::HealthAction::Set {
+        id: 7,
+        status: api::types::CreateEpicHealthStatus::OffTrack,
+        note: Some("Blocked on legal review".to_string()),
+    });
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Set epic 7 health to Off Track");
+}
+
+#[tokio::test]
+async fn health_history_lists_updates_oldest_first() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/health-history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            health_json(7, "At Risk", "Vendor API is late", "2024-03-05T10:00:00Z"),
+            health_json(7, "On Track", "", "2024-03-01T10:00:00Z"),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(epic::HealthAction::History { id: 7 });
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[0]["status"], "On Track");
+    assert_eq!(json[1]["status"], "At Risk");
+    assert_eq!(json[1]["text"], "Vendor API is late");
+}
+
+#[tokio::test]
+async fn epic_get_and_list_show_health() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut epic = full_epic_json(7, "Checkout", "");
+    epic["health"] = health_json(7, "On Track", "Shipping Friday", "2024-03-01T10:00:00Z");
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&epic))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            epic_json(7, "Checkout", None),
+            epic_json(8, "Search", None),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(health_json(
+            7,
+            "On Track",
+            "Shipping Friday",
+            "2024-03-01T10:00:00Z",
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/8/health"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let get = epic::EpicArgs {
+        action: epic::EpicAction::Get { id: 7 },
+    };
+    let result = epic::run(&get, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+    let result = epic::run(
+        &crate::make_list_args(false),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("  Health:      On Track - Shipping Friday"));
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines.iter().any(|l| l.starts_with("ID  Name      Health")));
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.trim_end() == " 7  Checkout  On Track")
+    );
+    // A failed lookup is marked rather than left blank
+    assert!(lines.iter().any(|l| l.trim_end() == " 8  Search    ?"));
+    assert!(output.contains("? = health could not be read (1 epic(s))"));
+}
+
+#[tokio::test]
+async fn epic_list_fetches_health_concurrently() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            epic_json(1, "One", None),
+            epic_json(2, "Two", None),
+            epic_json(3, "Three", None),
+        ])))
+        .mount(&server)
+        .await;
+    for id in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v3/epics/{id}/health")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(health_json(id, "At Risk", "", "2024-03-01T10:00:00Z"))
+                    .set_delay(std::time::Duration::from_millis(400)),
+            )
+            .mount(&server)
+            .await;
+    }
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let started = std::time::Instant::now();
+    let result = epic::run(
+        &crate::make_list_args(false),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+    assert!(started.elapsed() < std::time::Duration::from_millis(1000));
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let ids: Vec<i64> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert!(
+        json.as_array()
+            .unwrap()
+            .iter()
+            .all(|e| e["health"] == "At Risk")
+    );
+}
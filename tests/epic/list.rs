@@ -3,6 +3,7 @@ use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::make_list_args;
 use crate::support::epic_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
 use shortcut_cli::{api, commands::epic};
 
 #[tokio::test]
@@ -90,3 +91,46 @@ async fn list_epics_api_error() {
     let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn list_epics_hides_archived_unless_requested() {
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut archived = epic_json(2, "Old Epic", None);
+    archived["archived"] = serde_json::Value::from(true);
+    let body = serde_json::json!([epic_json(1, "Epic One", None), archived]);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let result = epic::run(
+        &make_list_args(false),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "1\n");
+
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let args = epic::EpicArgs {
+        action: epic::EpicAction::List {
+            desc: false,
+            archived: true,
+        },
+    };
+    let result = epic::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "1\n2\n");
+}
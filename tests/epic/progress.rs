@@ -0,0 +1,339 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{
+    full_epic_json, history_action_story_create_json, history_action_story_update_json,
+    history_entry_json, mount_default_workflow, story_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::epic};
+
+fn make_progress_args() -> epic::ProgressArgs {
+    epic::ProgressArgs {
+        id: 7,
+        csv: false,
+        no_burnup: false,
+        refresh: false,
+    }
+}
+
+fn wrap(args: epic::ProgressArgs) -> epic::EpicArgs {
+    epic::EpicArgs {
+        action: epic::EpicAction::Progress(Box::new(args)),
+    }
+}
+
+/// Epic 7 with the given stats overriding the fixture's zeros.
+fn epic_with_stats(stats: serde_json::Value) -> serde_json::Value {
+    let mut epic = full_epic_json(7, "Checkout", "");
+    for (key, value) in stats.as_object().unwrap() {
+        epic["stats"][key] = value.clone();
+    }
+    epic
+}
+
+fn created_in_epic(story_id: i64, name: &str, changed_at: &str) -> serde_json::Value {
+    let mut create = history_action_story_create_json(story_id, name, "feature");
+    create["epic_id"] = serde_json::Value::from(7);
+    create["workflow_state_id"] = serde_json::Value::from(500000007_i64);
+    history_entry_json(
+        &format!("00000000-0000-0000-0000-{story_id:012}"),
+        changed_at,
+        Some(UUID_ALICE),
+        vec![create],
+        vec![],
+    )
+}
+
+/// Story 1 (3 pts, @alice) is created on Jan 1 and done on Jan 3. Story 2
+/// (5 pts, @alice) is created on Jan 2 and in progress. Story 3 is unowned
+/// and unestimated, created on Jan 2.
+async fn mount_epic(server: &MockServer, tmp: &std::path::Path) {
+    std::fs::write(
+        tmp.join("member_cache.json"),
+        serde_json::json!({ "alice": UUID_ALICE }).to_string(),
+    )
+    .unwrap();
+    mount_default_workflow(server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(epic_with_stats(
+            serde_json::json!({
+                "num_stories_total": 3,
+                "num_stories_unstarted": 1,
+                "num_stories_started": 1,
+                "num_stories_done": 1,
+                "num_stories_unestimated": 1,
+                "num_points": 8,
+                "num_points_started": 5,
+                "num_points_done": 3,
+            }),
+        )))
+        .mount(server)
+        .await;
+
+    let mut done = story_json(1, "Cart", None);
+    done["estimate"] = serde_json::Value::from(3);
+    done["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    done["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    let mut started = story_json(2, "Payment", None);
+    started["estimate"] = serde_json::Value::from(5);
+    started["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    started["workflow_state_id"] = serde_json::Value::from(500000008_i64);
+    let unestimated = story_json(3, "Receipts", None);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            done,
+            started,
+            unestimated
+        ])))
+        .mount(server)
+        .await;
+
+    let histories = [
+        (
+            1,
+            serde_json::json!([
+                created_in_epic(1, "Cart", "2024-01-01T09:00:00Z"),
+                history_entry_json(
+                    "11111111-1111-1111-1111-111111111111",
+                    "2024-01-03T09:00:00Z",
+                    Some(UUID_ALICE),
+                    vec![history_action_story_update_json(
+                        1,
+                        "Cart",
+                        "feature",
+                        serde_json::json!({
+                            "workflow_state_id": { "old": 500000007, "new": 500000009 }
+                        }),
+                    )],
+                    vec![],
+                ),
+            ]),
+        ),
+        (
+            2,
+            serde_json::json!([created_in_epic(2, "Payment", "2024-01-02T09:00:00Z")]),
+        ),
+        (
+            3,
+            serde_json::json!([created_in_epic(3, "Receipts", "2024-01-02T09:00:00Z")]),
+        ),
+    ];
+    for (id, body) in histories {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v3/stories/{id}/history")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(server)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn progress_json_reports_counts_owners_and_burnup() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = epic::run(
+        &wrap(make_progress_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        json["stories"],
+        serde_json::json!({ "total": 3, "done": 1, "percent": 33, "non_archived": 3 })
+    );
+    assert_eq!(
+        json["points"],
+        serde_json::json!({ "total": 8, "done": 3, "percent": 38 })
+    );
+    assert_eq!(
+        json["by_state_type"],
+        serde_json::json!([
+            { "type": "unstarted", "stories": 1, "points": 0 },
+            { "type": "started", "stories": 1, "points": 5 },
+            { "type": "done", "stories": 1, "points": 3 },
+        ])
+    );
+    assert_eq!(
+        json["owners"][0],
+        serde_json::json!({
+            "owner": "@alice",
+            "stories": 2,
+            "points": 8,
+            "remaining_stories": 1,
+            "remaining_points": 5,
+        })
+    );
+    assert_eq!(json["owners"][1]["owner"], "(unowned)");
+    assert_eq!(
+        json["unestimated"],
+        serde_json::json!([{ "id": 3, "name": "Receipts" }])
+    );
+
+    let burnup = json["burnup"].as_array().unwrap();
+    assert_eq!(
+        burnup[..3],
+        [
+            serde_json::json!({ "date": "2024-01-01", "scope_stories": 1, "done_stories": 0, "scope_points": 3, "done_points": 0 }),
+            serde_json::json!({ "date": "2024-01-02", "scope_stories": 3, "done_stories": 0, "scope_points": 8, "done_points": 0 }),
+            serde_json::json!({ "date": "2024-01-03", "scope_stories": 3, "done_stories": 1, "scope_points": 8, "done_points": 3 }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn progress_csv_prints_burnup_series() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_progress_args();
+    args.csv = true;
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        "date,scope_stories,done_stories,scope_points,done_points"
+    );
+    assert_eq!(lines[1], "2024-01-01,1,0,3,0");
+    assert_eq!(lines[3], "2024-01-03,3,1,8,3");
+}
+
+#[tokio::test]
+async fn progress_human_shows_summary_and_chart() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = epic::run(
+        &wrap(make_progress_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("7 - Checkout\n"));
+    assert!(output.contains("  Stories: 1/3 done (33%)"));
+    assert!(output.contains("  Points:  3/8 done (38%)"));
+    assert!(output.contains(
+        "  Totals and state types are from the epic's stats and include archived stories;\n  \
+         owners and unestimated stories cover the 3 non-archived ones.\n"
+    ));
+    assert!(output.contains("Unestimated stories (1):\n  3 - Receipts"));
+    assert!(output.contains("Burnup (points):"));
+    assert!(output.contains("- Scope   * Done"));
+}
+
+#[tokio::test]
+async fn progress_without_burnup_skips_history() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(epic_with_stats(
+            serde_json::json!({
+                "num_stories_total": 1,
+                "num_stories_unstarted": 1,
+                "num_stories_unestimated": 1,
+            }),
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/stories"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([story_json(1, "Cart", None)])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/1/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_progress_args();
+    args.no_burnup = true;
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["burnup"], serde_json::json!([]));
+    assert_eq!(json["stories"]["total"], 1);
+}
+
+#[tokio::test]
+async fn progress_totals_come_from_epic_stats() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    // Stats count more than the one story listed
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(epic_with_stats(
+            serde_json::json!({
+                "num_stories_total": 4,
+                "num_stories_backlog": 1,
+                "num_stories_unstarted": 1,
+                "num_stories_done": 2,
+                "num_points": 10,
+                "num_points_done": 6,
+            }),
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/stories"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([story_json(1, "Cart", None)])),
+        )
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_progress_args();
+    args.no_burnup = true;
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["stories"]["total"], 4);
+    assert_eq!(json["stories"]["done"], 2);
+    assert_eq!(json["stories"]["non_archived"], 1);
+    assert_eq!(json["points"]["total"], 10);
+    assert_eq!(json["points"]["done"], 6);
+}
@@ -0,0 +1,142 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{label_json, mount_default_workflow, story_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::epic};
+
+fn make_stories_args() -> epic::StoriesArgs {
+    epic::StoriesArgs {
+        id: 7,
+        owner: None,
+        state: None,
+        story_type: None,
+        label: None,
+        archived: false,
+    }
+}
+
+fn wrap(args: epic::StoriesArgs) -> epic::EpicArgs {
+    epic::EpicArgs {
+        action: epic::EpicAction::Stories(Box::new(args)),
+    }
+}
+
+/// Stories 1 (done, 3 pts) and 2 (in progress, 5 pts, bug, "backend") are
+/// owned by @alice; story 3 is unstarted and unestimated; story 4 is archived.
+async fn mount_epic_stories(server: &MockServer, tmp: &std::path::Path) {
+    std::fs::write(
+        tmp.join("member_cache.json"),
+        serde_json::json!({ "alice": UUID_ALICE }).to_string(),
+    )
+    .unwrap();
+    mount_default_workflow(server).await;
+
+    let mut done = story_json(1, "Cart", None);
+    done["estimate"] = serde_json::Value::from(3);
+    done["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    done["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    let mut started = story_json(2, "Payment", None);
+    started["estimate"] = serde_json::Value::from(5);
+    started["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    started["story_type"] = serde_json::Value::from("bug");
+    started["labels"] = serde_json::json!([label_json(1, "backend")]);
+    started["workflow_state_id"] = serde_json::Value::from(500000008_i64);
+    let unstarted = story_json(3, "Receipts", None);
+    let mut archived = story_json(4, "Old checkout", None);
+    archived["archived"] = serde_json::Value::from(true);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/stories"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([done, started, unstarted, archived])),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn epic_stories_groups_by_state_with_totals() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic_stories(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = epic::run(
+        &wrap(make_stories_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let unstarted = output.find("Unstarted (1 story, 0 points)").unwrap();
+    let started = output.find("In Progress (1 story, 5 points)").unwrap();
+    let done = output.find("Done (1 story, 3 points)").unwrap();
+    assert!(unstarted < started && started < done);
+    assert!(output.contains(" 2  bug   5    @alice  Payment"));
+    assert!(!output.contains("Old checkout"));
+    assert!(output.trim_end().ends_with("Total: 3 stories, 8 points"));
+}
+
+#[tokio::test]
+async fn epic_stories_filters_by_owner_type_and_label() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic_stories(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_stories_args();
+    args.owner = Some("@alice".to_string());
+    args.story_type = Some("bug".to_string());
+    args.label = Some("Backend".to_string());
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        json["total"],
+        serde_json::json!({ "stories": 1, "points": 5 })
+    );
+    assert_eq!(json["states"][0]["state"], "In Progress");
+    assert_eq!(json["states"][0]["stories"][0]["id"], 2);
+}
+
+#[tokio::test]
+async fn epic_stories_filters_by_state_and_includes_archived() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_epic_stories(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_stories_args();
+    args.state = Some("unstarted".to_string());
+    args.archived = true;
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "3\n4\n");
+}
+
+#[tokio::test]
+async fn epic_stories_rejects_unknown_type() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_stories_args();
+    args.story_type = Some("epic".to_string());
+    let result = epic::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Invalid story type: epic"));
+}
@@ -1,5 +1,17 @@
 mod support;
 
+#[path = "iteration/burndown.rs"]
+mod burndown;
+#[path = "iteration/calendar.rs"]
+mod calendar;
+#[path = "iteration/plan.rs"]
+mod plan;
+#[path = "iteration/resolve.rs"]
+mod resolve;
+#[path = "iteration/rollover.rs"]
+mod rollover;
+#[path = "iteration/velocity.rs"]
+mod velocity;
 #[path = "iteration/wizard.rs"]
 mod wizard;
 
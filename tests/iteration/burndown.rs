@@ -0,0 +1,195 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{
+    history_action_story_create_json, history_action_story_update_json, history_entry_json,
+    iteration_json, mount_default_workflow, story_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+fn make_burndown_args() -> iteration::BurndownArgs {
+    iteration::BurndownArgs {
+        id: 24.into(),
+        csv: false,
+        refresh: false,
+    }
+}
+
+fn wrap(args: iteration::BurndownArgs) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Burndown(Box::new(args)),
+        group: None,
+    }
+}
+
+fn story(id: i64, name: &str, state: i64, estimate: i64) -> serde_json::Value {
+    let mut story = story_json(id, name, None);
+    story["workflow_state_id"] = serde_json::json!(state);
+    story["estimate"] = serde_json::json!(estimate);
+    story["iteration_id"] = serde_json::json!(24);
+    story
+}
+
+fn update(
+    story_id: i64,
+    name: &str,
+    changed_at: &str,
+    changes: serde_json::Value,
+) -> serde_json::Value {
+    history_entry_json(
+        &format!("11111111-1111-1111-1111-{story_id:012}"),
+        changed_at,
+        None,
+        vec![history_action_story_update_json(
+            story_id, name, "feature", changes,
+        )],
+        vec![],
+    )
+}
+
+/// A five-day iteration. Cart (3 pts) is planned and done on day two,
+/// Payment (5 pts) is pulled in on day three, Receipts (2 pts) has no
+/// history and is still unstarted.
+async fn mount_burndown(server: &MockServer) {
+    mount_default_workflow(server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(iteration_json(
+            24,
+            "Sprint 24",
+            "2024-03-04",
+            "2024-03-08",
+        )))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            story(1, "Cart", 500000009, 3),
+            story(2, "Payment", 500000008, 5),
+            story(3, "Receipts", 500000007, 2),
+        ])))
+        .mount(server)
+        .await;
+
+    let mut create = history_action_story_create_json(1, "Cart", "feature");
+    create["iteration_id"] = serde_json::json!(24);
+    create["workflow_state_id"] = serde_json::json!(500000007);
+    let histories = [
+        (
+            1,
+            serde_json::json!([
+                history_entry_json(
+                    "00000000-0000-0000-0000-000000000001",
+                    "2024-03-01T09:00:00Z",
+                    None,
+                    vec![create],
+                    vec![],
+                ),
+                update(
+                    1,
+                    "Cart",
+                    "2024-03-05T15:00:00Z",
+                    serde_json::json!({ "workflow_state_id": { "old": 500000007, "new": 500000009 } }),
+                ),
+            ]),
+        ),
+        (
+            2,
+            serde_json::json!([update(
+                2,
+                "Payment",
+                "2024-03-06T10:00:00Z",
+                serde_json::json!({ "iteration_id": { "old": null, "new": 24 } }),
+            )]),
+        ),
+        (3, serde_json::json!([])),
+    ];
+    for (id, body) in histories {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v3/stories/{id}/history")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(server)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn burndown_json_reconstructs_remaining_points() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_burndown(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_burndown_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["scope_points"], 10);
+    assert_eq!(json["remaining_points"], 7);
+    assert_eq!(
+        json["burndown"],
+        serde_json::json!([
+            { "date": "2024-03-04", "remaining_points": 5, "ideal_points": 5.0 },
+            { "date": "2024-03-05", "remaining_points": 2, "ideal_points": 3.8 },
+            { "date": "2024-03-06", "remaining_points": 7, "ideal_points": 2.5 },
+            { "date": "2024-03-07", "remaining_points": 7, "ideal_points": 1.3 },
+            { "date": "2024-03-08", "remaining_points": 7, "ideal_points": 0.0 },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn burndown_csv_prints_daily_series() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_burndown(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_burndown_args();
+    args.csv = true;
+    let result = iteration::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "date,remaining_points,ideal_points");
+    assert_eq!(lines[2], "2024-03-05,2,3.8");
+    assert_eq!(lines[5], "2024-03-08,7,0");
+}
+
+#[tokio::test]
+async fn burndown_human_shows_summary_and_chart() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_burndown(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_burndown_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("24 - Sprint 24 (2024-03-04 to 2024-03-08)\n"));
+    assert!(output.contains("  Scope:     10 points"));
+    assert!(output.contains("  Remaining: 7 points (as of 2024-03-08)"));
+    assert!(output.contains("Burndown (points):"));
+    assert!(output.contains(". Ideal   * Remaining"));
+}
@@ -0,0 +1,113 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::iteration_slim_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+fn wrap(quarter: bool, date: &str) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Calendar(iteration::CalendarArgs {
+            quarter,
+            date: Some(date.to_string()),
+        }),
+        group: None,
+    }
+}
+
+/// Monday-to-Friday sprints: 23 and 24 are back to back (only a weekend
+/// between them), 25 starts before 24 ends, and 26 starts a week after 25.
+async fn mount_iterations(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            iteration_slim_json(26, "Sprint 26", "2024-04-08", "2024-04-19"),
+            iteration_slim_json(23, "Sprint 23", "2024-02-26", "2024-03-08"),
+            iteration_slim_json(25, "Sprint 25", "2024-03-20", "2024-03-29"),
+            iteration_slim_json(24, "Sprint 24", "2024-03-11", "2024-03-22"),
+            iteration_slim_json(20, "Sprint 20", "2023-11-06", "2023-11-17"),
+        ])))
+        .mount(server)
+        .await;
+}
+
+async fn run_calendar(args: iteration::IterationArgs, mode: OutputMode) -> String {
+    let (out, buf) = OutputConfig::with_buffer(mode, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_iterations(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+    String::from_utf8(buf.lock().unwrap().clone()).unwrap()
+}
+
+#[tokio::test]
+async fn calendar_month_json_reports_overlaps_and_ignores_weekend_gaps() {
+    let output = run_calendar(wrap(false, "2024-03-10"), OutputMode::Json).await;
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["from"], "2024-03-01");
+    assert_eq!(json["to"], "2024-03-31");
+    let ids: Vec<i64> = json["iterations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![23, 24, 25]);
+    assert_eq!(
+        json["overlaps"],
+        serde_json::json!([{
+            "iterations": [24, 25],
+            "start_date": "2024-03-20",
+            "end_date": "2024-03-22",
+            "days": 3,
+        }])
+    );
+    // Only the gap after Sprint 25 is reported, not the weekend before 24.
+    assert_eq!(json["gaps"].as_array().unwrap().len(), 1);
+    assert_eq!(json["gaps"][0]["after"], 25);
+}
+
+#[tokio::test]
+async fn calendar_quarter_json_reports_gaps_running_into_the_period() {
+    let output = run_calendar(wrap(true, "2024-05"), OutputMode::Json).await;
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["from"], "2024-04-01");
+    assert_eq!(json["to"], "2024-06-30");
+    assert_eq!(json["iterations"][0]["id"], 26);
+    assert_eq!(json["iterations"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        json["gaps"],
+        serde_json::json!([{
+            "after": 25,
+            "before": 26,
+            "start_date": "2024-03-30",
+            "end_date": "2024-04-07",
+            "days": 9,
+        }])
+    );
+}
+
+#[tokio::test]
+async fn calendar_human_draws_timeline() {
+    let output = run_calendar(wrap(false, "2024-03"), OutputMode::Human).await;
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "March 2024 (2024-03-01 to 2024-03-31)");
+    // Ruler marks each Monday: 4, 11, 18 and 25 March.
+    assert_eq!(lines[2], format!("{:14}   4      11     18     25", ""));
+    assert_eq!(lines[3], "23 Sprint 23  ████████");
+    assert_eq!(lines[4], "24 Sprint 24            ████████████");
+    assert!(lines[6].starts_with("    Coverage  ────────  ─────────!!!"));
+    assert!(
+        output.contains("Overlaps:\n  Sprint 24 and Sprint 25: 2024-03-20 to 2024-03-22 (3 days)")
+    );
+    assert!(
+        output
+            .contains("Gaps:\n  2024-03-30 to 2024-04-07 (9 days) between Sprint 25 and Sprint 26")
+    );
+}
@@ -0,0 +1,206 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{iteration_json, mount_default_workflow, story_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+const UUID_ALICE: &str = "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa";
+const UUID_BOB: &str = "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb";
+
+fn make_plan_args() -> iteration::PlanArgs {
+    iteration::PlanArgs {
+        id: 24.into(),
+        carry_over_from: None,
+        capacity: None,
+    }
+}
+
+fn wrap(args: iteration::PlanArgs) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Plan(Box::new(args)),
+        group: None,
+    }
+}
+
+fn story(
+    id: i64,
+    name: &str,
+    state: i64,
+    estimate: Option<i64>,
+    owner: Option<&str>,
+) -> serde_json::Value {
+    let mut story = story_json(id, name, None);
+    story["workflow_state_id"] = serde_json::json!(state);
+    story["estimate"] = serde_json::json!(estimate);
+    story["owner_ids"] = serde_json::json!(owner.into_iter().collect::<Vec<_>>());
+    story
+}
+
+/// A two-week iteration (10 weekdays). @alice has one day off and owns 11
+/// points; @bob owns 3; one story is unowned and unestimated.
+async fn mount_plan(server: &MockServer, project_dir: &std::path::Path) -> std::path::PathBuf {
+    let cache_dir = project_dir.join("cache");
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    std::fs::write(
+        cache_dir.join("member_cache.json"),
+        serde_json::json!({ "alice": UUID_ALICE, "bob": UUID_BOB }).to_string(),
+    )
+    .unwrap();
+    std::fs::write(
+        project_dir.join("capacity.yml"),
+        "default_points_per_day: 1\nmembers:\n  \"@alice\":\n    days_off: [2024-03-04, 2024-03-09]\n",
+    )
+    .unwrap();
+    mount_default_workflow(server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(iteration_json(
+            24,
+            "Sprint 24",
+            "2024-03-04",
+            "2024-03-15",
+        )))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            story(1, "Cart", 500000007, Some(5), Some(UUID_ALICE)),
+            story(2, "Payment", 500000008, Some(6), Some(UUID_ALICE)),
+            story(3, "Receipts", 500000007, None, None),
+            story(4, "Search", 500000009, Some(3), Some(UUID_BOB)),
+        ])))
+        .mount(server)
+        .await;
+    cache_dir
+}
+
+#[tokio::test]
+async fn plan_json_compares_points_with_capacity() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let cache_dir = mount_plan(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(&wrap(make_plan_args()), &client, cache_dir, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        json["total"],
+        serde_json::json!({ "stories": 4, "points": 14 })
+    );
+    assert_eq!(
+        json["states"],
+        serde_json::json!([
+            { "workflow_state_id": 500000007, "state": "Unstarted", "stories": 2, "points": 5 },
+            { "workflow_state_id": 500000008, "state": "In Progress", "stories": 1, "points": 6 },
+            { "workflow_state_id": 500000009, "state": "Done", "stories": 1, "points": 3 },
+        ])
+    );
+    assert_eq!(
+        json["owners"],
+        serde_json::json!([
+            {
+                "owner": "@alice", "owner_id": UUID_ALICE,
+                "stories": 2, "points": 11, "available_days": 9, "capacity": 9.0,
+            },
+            {
+                "owner": "@bob", "owner_id": UUID_BOB,
+                "stories": 1, "points": 3, "available_days": 10, "capacity": 10.0,
+            },
+        ])
+    );
+    assert_eq!(
+        json["unestimated"],
+        serde_json::json!([{ "id": 3, "name": "Receipts" }])
+    );
+    assert_eq!(
+        json["unowned"],
+        serde_json::json!([{ "id": 3, "name": "Receipts" }])
+    );
+}
+
+#[tokio::test]
+async fn plan_human_flags_over_capacity_and_unowned() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let cache_dir = mount_plan(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(&wrap(make_plan_args()), &client, cache_dir, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("24 - Sprint 24 (2024-03-04 to 2024-03-15)\n"));
+    assert!(output.contains("  Committed: 4 stories, 14 points"));
+    assert!(output.contains("@alice  over by 2"));
+    assert!(output.contains("@bob    30% of capacity"));
+    assert!(output.contains("Unestimated stories (1):\n  3 - Receipts"));
+    assert!(output.contains("Unowned stories (1):\n  3 - Receipts"));
+}
+
+#[tokio::test]
+async fn plan_carries_over_unfinished_stories() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let cache_dir = mount_plan(&server, tmp.path()).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/23/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            story(10, "Left over", 500000008, Some(2), None),
+            story(11, "Finished", 500000009, Some(1), None),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/bulk"))
+        .and(body_partial_json(serde_json::json!({
+            "story_ids": [10],
+            "iteration_id": 24,
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([story(
+                10,
+                "Left over",
+                500000008,
+                Some(2),
+                None
+            )])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_plan_args();
+    args.carry_over_from = Some(23.into());
+    let result = iteration::run(&wrap(args), &client, cache_dir, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["carried_over"], serde_json::json!([10]));
+}
+
+#[tokio::test]
+async fn plan_with_missing_capacity_file_errors() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let cache_dir = mount_plan(&server, tmp.path()).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_plan_args();
+    args.capacity = Some(tmp.path().join("missing.yml"));
+    let result = iteration::run(&wrap(args), &client, cache_dir, &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Failed to read"));
+}
@@ -0,0 +1,110 @@
+use chrono::{Duration, Utc};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{iteration_json, iteration_slim_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+const GROUP_WEB: &str = "cccccccc-cccc-cccc-cccc-cccccccccccc";
+const GROUP_MOBILE: &str = "dddddddd-dddd-dddd-dddd-dddddddddddd";
+
+fn get(id: iteration::IterationRef, group: Option<&str>) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Get { id },
+        group: group.map(String::from),
+    }
+}
+
+fn days_from_today(days: i64) -> String {
+    (Utc::now().date_naive() + Duration::days(days)).to_string()
+}
+
+fn slim(id: i64, start: i64, end: i64, group: &str) -> serde_json::Value {
+    let mut iteration = iteration_slim_json(
+        id,
+        &format!("Sprint {id}"),
+        &days_from_today(start),
+        &days_from_today(end),
+    );
+    iteration["group_ids"] = serde_json::json!([group]);
+    iteration
+}
+
+/// Web runs 23 (finished), 24 (running) and 25 (upcoming). Mobile's 90 started
+/// more recently than 24 and is still running.
+async fn mount_iterations(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            slim(23, -21, -8, GROUP_WEB),
+            slim(24, -7, 6, GROUP_WEB),
+            slim(25, 7, 20, GROUP_WEB),
+            slim(90, -2, 11, GROUP_MOBILE),
+        ])))
+        .mount(server)
+        .await;
+    for id in [23, 24, 25, 90] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v3/iterations/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(iteration_json(
+                id,
+                &format!("Sprint {id}"),
+                "2024-01-01",
+                "2024-01-14",
+            )))
+            .mount(server)
+            .await;
+    }
+}
+
+async fn resolved_id(args: iteration::IterationArgs) -> Result<i64, String> {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_iterations(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    iteration::run(&args, &client, tmp.path().to_path_buf(), &out)
+        .await
+        .map_err(|e| e.to_string())?;
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    Ok(output.trim().parse().unwrap())
+}
+
+#[test]
+fn iteration_ref_parses_ids_and_keywords() {
+    use iteration::IterationRef;
+
+    assert_eq!("24".parse::<IterationRef>(), Ok(IterationRef::Id(24)));
+    assert_eq!("Current".parse::<IterationRef>(), Ok(IterationRef::Current));
+    assert_eq!("next".parse::<IterationRef>(), Ok(IterationRef::Next));
+    assert_eq!("prev".parse::<IterationRef>(), Ok(IterationRef::Previous));
+    assert!("last".parse::<IterationRef>().is_err());
+}
+
+#[tokio::test]
+async fn current_prefers_most_recently_started_iteration() {
+    let id = resolved_id(get(iteration::IterationRef::Current, None)).await;
+    assert_eq!(id, Ok(90));
+}
+
+#[tokio::test]
+async fn relative_iterations_respect_group() {
+    use iteration::IterationRef::*;
+
+    assert_eq!(resolved_id(get(Current, Some(GROUP_WEB))).await, Ok(24));
+    assert_eq!(resolved_id(get(Next, Some(GROUP_WEB))).await, Ok(25));
+    assert_eq!(resolved_id(get(Previous, Some(GROUP_WEB))).await, Ok(23));
+}
+
+#[tokio::test]
+async fn missing_relative_iteration_errors() {
+    let err = resolved_id(get(iteration::IterationRef::Next, Some(GROUP_MOBILE)))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        format!("No next iteration found for group {GROUP_MOBILE}")
+    );
+}
@@ -0,0 +1,175 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{
+    iteration_json, label_json, make_dry_run_output, mount_default_workflow, story_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+const GROUP_WEB: &str = "cccccccc-cccc-cccc-cccc-cccccccccccc";
+
+fn make_rollover_args() -> iteration::RolloverArgs {
+    iteration::RolloverArgs {
+        id: 24.into(),
+        name: None,
+    }
+}
+
+fn wrap(args: iteration::RolloverArgs) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Rollover(Box::new(args)),
+        group: None,
+    }
+}
+
+fn story(id: i64, name: &str, state: i64, estimate: i64) -> serde_json::Value {
+    let mut story = story_json(id, name, None);
+    story["workflow_state_id"] = serde_json::json!(state);
+    story["estimate"] = serde_json::json!(estimate);
+    story
+}
+
+/// Sprint 24 runs Monday to Friday two weeks later, belongs to one group
+/// and has one label. Cart is done; Payment and Receipts are not.
+async fn mount_current(server: &MockServer) {
+    mount_default_workflow(server).await;
+
+    let mut current = iteration_json(24, "Sprint 24", "2024-03-04", "2024-03-15");
+    current["group_ids"] = serde_json::json!([GROUP_WEB]);
+    current["labels"] = serde_json::json!([label_json(1, "web")]);
+    current["label_ids"] = serde_json::json!([1]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(current))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            story(1, "Cart", 500000009, 3),
+            story(2, "Payment", 500000008, 5),
+            story(3, "Receipts", 500000007, 2),
+        ])))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn rollover_creates_next_iteration_and_moves_unfinished_stories() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    mount_current(&server).await;
+
+    let mut next = iteration_json(25, "Sprint 25", "2024-03-18", "2024-03-29");
+    next["group_ids"] = serde_json::json!([GROUP_WEB]);
+    Mock::given(method("POST"))
+        .and(path("/api/v3/iterations"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Sprint 25",
+            "start_date": "2024-03-18",
+            "end_date": "2024-03-29",
+            "group_ids": [GROUP_WEB],
+            "labels": [{ "name": "web" }],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(next))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/bulk"))
+        .and(body_partial_json(serde_json::json!({
+            "story_ids": [2, 3],
+            "iteration_id": 25,
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_rollover_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.starts_with("Created iteration 25 - Sprint 25 (2024-03-18 \u{2192} 2024-03-29)\n")
+    );
+    assert!(output.contains("  Labels: web"));
+    assert!(output.contains(
+        "Moved 2 unfinished stories (7 points) from 24 - Sprint 24\n  2 - Payment\n  3 - Receipts\n"
+    ));
+}
+
+#[tokio::test]
+async fn rollover_dry_run_prints_requests_without_writing() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    mount_current(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/bulk"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_rollover_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[dry-run] POST /api/v3/iterations"));
+    assert!(output.contains("\"name\": \"Sprint 25\""));
+    assert!(output.contains("\"start_date\": \"2024-03-18\""));
+    assert!(output.contains("[dry-run] PUT /api/v3/stories/bulk"));
+}
+
+#[tokio::test]
+async fn rollover_without_number_in_name_requires_name() {
+    let (out, _buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations/24"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(iteration_json(
+            24,
+            "Spring cleanup",
+            "2024-03-04",
+            "2024-03-15",
+        )))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_rollover_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("pass --name"));
+}
@@ -0,0 +1,138 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::iteration_slim_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::iteration};
+
+const GROUP_WEB: &str = "cccccccc-cccc-cccc-cccc-cccccccccccc";
+
+fn make_velocity_args() -> iteration::VelocityArgs {
+    iteration::VelocityArgs {
+        last: 6,
+        csv: false,
+    }
+}
+
+fn wrap(args: iteration::VelocityArgs) -> iteration::IterationArgs {
+    iteration::IterationArgs {
+        action: iteration::IterationAction::Velocity(Box::new(args)),
+        group: None,
+    }
+}
+
+fn finished(id: i64, start: &str, end: &str, planned: i64, done: i64) -> serde_json::Value {
+    let mut iteration = iteration_slim_json(id, &format!("Sprint {id}"), start, end);
+    iteration["status"] = serde_json::json!("done");
+    iteration["stats"]["num_points"] = serde_json::json!(planned);
+    iteration["stats"]["num_points_done"] = serde_json::json!(done);
+    iteration["group_ids"] = serde_json::json!([GROUP_WEB]);
+    iteration
+}
+
+/// Four finished sprints (listed out of order) plus one in progress. Sprint
+/// 20 belongs to no group.
+async fn mount_iterations(server: &MockServer) {
+    let mut other = finished(20, "2024-01-01", "2024-01-12", 8, 8);
+    other["group_ids"] = serde_json::json!([]);
+    let mut current = iteration_slim_json(24, "Sprint 24", "2024-02-26", "2024-03-08");
+    current["status"] = serde_json::json!("started");
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            finished(22, "2024-01-29", "2024-02-09", 12, 9),
+            other,
+            current,
+            finished(23, "2024-02-12", "2024-02-23", 10, 12),
+            finished(21, "2024-01-15", "2024-01-26", 10, 6),
+        ])))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn velocity_json_orders_finished_iterations_with_rolling_average() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_iterations(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_velocity_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let rows: Vec<(i64, i64, f64)> = json["iterations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["id"].as_i64().unwrap(),
+                r["completed_points"].as_i64().unwrap(),
+                r["rolling_average"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![(20, 8, 8.0), (21, 6, 7.0), (22, 9, 7.7), (23, 12, 9.0)]
+    );
+    assert_eq!(json["average"], 8.8);
+}
+
+#[tokio::test]
+async fn velocity_filters_by_group_and_limits_to_last() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_iterations(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_velocity_args();
+    args.last = 2;
+    args.csv = true;
+    let mut args = wrap(args);
+    args.group = Some(GROUP_WEB.to_string());
+    let result = iteration::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        "id,name,end_date,completed_points,planned_points,rolling_average\n\
+         22,Sprint 22,2024-02-09,9,12,9\n\
+         23,Sprint 23,2024-02-23,12,10,10.5\n"
+    );
+}
+
+#[tokio::test]
+async fn velocity_human_shows_table_and_average() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_iterations(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = iteration::run(
+        &wrap(make_velocity_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("Avg (3)"));
+    assert!(output.contains("Sprint 23"));
+    assert!(!output.contains("Sprint 24"));
+    assert!(output.contains("Average velocity: 8.8 points over 4 iterations"));
+}
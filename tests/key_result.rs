@@ -0,0 +1,17 @@
+mod support;
+
+#[path = "key_result/get.rs"]
+mod get;
+#[path = "key_result/update.rs"]
+mod update;
+
+pub const KR_ID: &str = "11111111-1111-1111-1111-111111111111";
+
+pub fn make_update_args() -> shortcut_cli::commands::key_result::UpdateArgs {
+    shortcut_cli::commands::key_result::UpdateArgs {
+        id: KR_ID.parse().unwrap(),
+        current: None,
+        target: None,
+        name: None,
+    }
+}
@@ -0,0 +1,72 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::KR_ID;
+use crate::support::key_result_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::key_result};
+
+#[tokio::test]
+async fn get_key_result_prints_values_and_progress() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{KR_ID}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(key_result_json(
+            KR_ID,
+            42,
+            "Weekly signups",
+            "0",
+            "50",
+            "200",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = key_result::KeyResultArgs {
+        action: key_result::KeyResultAction::Get {
+            id: KR_ID.parse().unwrap(),
+        },
+    };
+    let result = key_result::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with(&format!("{KR_ID} - Weekly signups\n")));
+    assert!(output.contains("  Current:     50"));
+    assert!(output.contains("  Target:      200"));
+    assert!(output.contains("  Progress:    █████░░░░░░░░░░░░░░░ 25%"));
+}
+
+#[tokio::test]
+async fn get_key_result_boolean_is_all_or_nothing() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    let mut body = key_result_json(KR_ID, 42, "Launch beta", "0", "0", "0");
+    body["type"] = serde_json::json!("boolean");
+    body["initial_observed_value"] = serde_json::json!({ "boolean_value": false });
+    body["current_observed_value"] = serde_json::json!({ "boolean_value": true });
+    body["current_target_value"] = serde_json::json!({ "boolean_value": true });
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{KR_ID}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = key_result::KeyResultArgs {
+        action: key_result::KeyResultAction::Get {
+            id: KR_ID.parse().unwrap(),
+        },
+    };
+    let result = key_result::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "100\n");
+}
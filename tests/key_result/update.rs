@@ -0,0 +1,116 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::key_result_json;
+use crate::{KR_ID, make_update_args};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::key_result};
+
+#[tokio::test]
+async fn update_key_result_records_current_value() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path(format!("/api/v3/key-results/{KR_ID}")))
+        .and(body_partial_json(serde_json::json!({
+            "observed_value": { "numeric_value": "42" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(key_result_json(
+            KR_ID,
+            42,
+            "Weekly signups",
+            "0",
+            "42",
+            "100",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut update_args = make_update_args();
+    update_args.current = Some("42".to_string());
+    let args = key_result::KeyResultArgs {
+        action: key_result::KeyResultAction::Update(Box::new(update_args)),
+    };
+    let result = key_result::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["current"], 42.0);
+    assert_eq!(json["percent"], 42);
+}
+
+#[tokio::test]
+async fn update_key_result_rejects_non_numeric_value() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut update_args = make_update_args();
+    update_args.current = Some("lots".to_string());
+    let args = key_result::KeyResultArgs {
+        action: key_result::KeyResultAction::Update(Box::new(update_args)),
+    };
+    let result = key_result::run(&args, &client, &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Invalid value: lots"));
+}
+
+#[tokio::test]
+async fn update_key_result_rejects_non_finite_value() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    for value in ["NaN", "inf", "-infinity"] {
+        let mut update_args = make_update_args();
+        update_args.current = Some(value.to_string());
+        let args = key_result::KeyResultArgs {
+            action: key_result::KeyResultAction::Update(Box::new(update_args)),
+        };
+        let result = key_result::run(&args, &client, &out).await;
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains(&format!("Invalid value: {value}")),
+            "got: {err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn update_key_result_rejects_numbers_the_api_does_not_accept() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    for value in ["1.234", "1e3", "+5", ".5", "5.", "1,000"] {
+        let mut update_args = make_update_args();
+        update_args.current = Some(value.to_string());
+        let args = key_result::KeyResultArgs {
+            action: key_result::KeyResultAction::Update(Box::new(update_args)),
+        };
+        let result = key_result::run(&args, &client, &out).await;
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains(&format!("Invalid value: {value}")),
+            "got: {err}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn update_key_result_requires_a_field() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = key_result::KeyResultArgs {
+        action: key_result::KeyResultAction::Update(Box::new(make_update_args())),
+    };
+    let result = key_result::run(&args, &client, &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Nothing to update"));
+}
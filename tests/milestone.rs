@@ -0,0 +1,25 @@
+mod support;
+
+#[path = "milestone/create.rs"]
+mod create;
+#[path = "milestone/delete.rs"]
+mod delete;
+#[path = "milestone/epics.rs"]
+mod epics;
+#[path = "milestone/get.rs"]
+mod get;
+#[path = "milestone/list.rs"]
+mod list;
+#[path = "milestone/update.rs"]
+mod update;
+
+pub fn make_update_args(id: i64) -> shortcut_cli::commands::milestone::UpdateArgs {
+    shortcut_cli::commands::milestone::UpdateArgs {
+        id,
+        name: None,
+        description: None,
+        state: None,
+        archived: None,
+        categories: vec![],
+    }
+}
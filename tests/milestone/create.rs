@@ -0,0 +1,100 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{category_json, milestone_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn create_milestone_minimal() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/milestones"))
+        .and(body_partial_json(
+            serde_json::json!({ "name": "V2 Launch" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(milestone_json(
+            300,
+            "V2 Launch",
+            "to do",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Create(Box::new(milestone::CreateArgs {
+            name: "V2 Launch".to_string(),
+            description: None,
+            state: None,
+            categories: vec![],
+        })),
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Created milestone 300 - V2 Launch (to do)");
+}
+
+#[tokio::test]
+async fn create_milestone_resolves_state_and_category_ids() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/categories/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(category_json(10, "Product", None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/milestones"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "V2 Launch",
+            "state": "in progress",
+            "categories": [{ "name": "Product" }, { "name": "Marketing" }],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(milestone_json(
+            300,
+            "V2 Launch",
+            "in progress",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Create(Box::new(milestone::CreateArgs {
+            name: "V2 Launch".to_string(),
+            description: None,
+            state: Some("In-Progress".to_string()),
+            categories: vec!["10".to_string(), "Marketing".to_string()],
+        })),
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn create_milestone_rejects_unknown_state() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Create(Box::new(milestone::CreateArgs {
+            name: "V2 Launch".to_string(),
+            description: None,
+            state: Some("blocked".to_string()),
+            categories: vec![],
+        })),
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Invalid state: blocked"));
+}
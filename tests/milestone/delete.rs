@@ -0,0 +1,55 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::milestone_json;
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn delete_milestone_with_confirm() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/300"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(milestone_json(
+            300,
+            "V2 Launch",
+            "to do",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/milestones/300"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Delete {
+            id: 300,
+            confirm: true,
+        },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn delete_milestone_without_confirm_errors() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Delete {
+            id: 300,
+            confirm: false,
+        },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("--confirm"));
+}
@@ -0,0 +1,64 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::epic_json;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn list_milestone_epics() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/300/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            epic_json(42, "Q3 Platform Rewrite", Some("Rebuild the core")),
+            epic_json(43, "V2 Migration Guide", None),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Epics {
+            id: 300,
+            desc: true,
+        },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        "42 - Q3 Platform Rewrite\n  Rebuild the core\n43 - V2 Migration Guide\n"
+    );
+}
+
+#[tokio::test]
+async fn list_milestone_epics_empty() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/300/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Epics {
+            id: 300,
+            desc: false,
+        },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "No epics for this milestone\n");
+}
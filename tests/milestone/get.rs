@@ -0,0 +1,69 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{category_json, epic_json, milestone_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn get_milestone_with_categories_and_epics() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    let mut body = milestone_json(300, "V2 Launch", "in progress");
+    body["description"] = serde_json::json!("Public launch of V2");
+    body["categories"] = serde_json::json!([category_json(10, "Product", None)]);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/300"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/300/epics"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([epic_json(
+                42,
+                "Q3 Platform Rewrite",
+                None
+            )])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Get { id: 300 },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("300 - V2 Launch\n"));
+    assert!(output.contains("  State:       in progress"));
+    assert!(output.contains("  Categories:  10 - Product"));
+    assert!(output.contains("  Description: Public launch of V2"));
+    assert!(output.contains("  Epics:\n    42 - Q3 Platform Rewrite"));
+}
+
+#[tokio::test]
+async fn get_milestone_not_found() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones/999"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Get { id: 999 },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,64 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{category_json, milestone_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn list_milestones_shows_state_and_categories() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    let mut launch = milestone_json(1, "V2 Launch", "in progress");
+    launch["categories"] = serde_json::json!([category_json(10, "Product", None)]);
+    let mut old = milestone_json(2, "V1 Launch", "done");
+    old["archived"] = serde_json::json!(true);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([launch, old])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::List { archived: false },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("1  in progress  Product     V2 Launch"));
+    assert!(!output.contains("V1 Launch"));
+}
+
+#[tokio::test]
+async fn list_milestones_includes_archived_when_requested() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    let mut old = milestone_json(2, "V1 Launch", "done");
+    old["archived"] = serde_json::json!(true);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/milestones"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            milestone_json(1, "V2 Launch", "to do"),
+            old
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::List { archived: true },
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "1\n2\n");
+}
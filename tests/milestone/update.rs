@@ -0,0 +1,63 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::make_update_args;
+use crate::support::milestone_json;
+use shortcut_cli::{api, commands::milestone};
+
+#[tokio::test]
+async fn update_milestone_name_and_state() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/milestones/300"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "V2 Launch (Delayed)",
+            "state": "done",
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(milestone_json(
+            300,
+            "V2 Launch (Delayed)",
+            "done",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut update_args = make_update_args(300);
+    update_args.name = Some("V2 Launch (Delayed)".to_string());
+    update_args.state = Some("Done".to_string());
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Update(Box::new(update_args)),
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn update_milestone_dry_run_skips_api() {
+    let (out, buf) = crate::support::make_dry_run_output();
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/milestones/300"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut update_args = make_update_args(300);
+    update_args.categories = vec!["10".to_string()];
+    let args = milestone::MilestoneArgs {
+        action: milestone::MilestoneAction::Update(Box::new(update_args)),
+    };
+    let result = milestone::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("/api/v3/milestones/300"));
+    assert!(output.contains("\"id\": 10"));
+}
@@ -8,6 +8,8 @@ mod delete;
 mod epics;
 #[path = "objective/get.rs"]
 mod get;
+#[path = "objective/key_results.rs"]
+mod key_results;
 #[path = "objective/list.rs"]
 mod list;
 #[path = "objective/update.rs"]
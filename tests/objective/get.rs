@@ -59,3 +59,89 @@ async fn get_objective_not_found() {
     let result = objective::run(&args, &client, &out).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn get_objective_lists_key_results() {
+    let (out, buf) = shortcut_cli::output::OutputConfig::with_buffer(
+        shortcut_cli::output::OutputMode::Human,
+        shortcut_cli::output::ColorMode::Never,
+    );
+    let server = MockServer::start().await;
+    let kr_id = "11111111-1111-1111-1111-111111111111";
+
+    let mut body = objective_json(42, "My Objective", "in progress", "");
+    body["key_result_ids"] = serde_json::json!([kr_id]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{kr_id}")))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(crate::support::key_result_json(
+                kr_id,
+                42,
+                "Weekly signups",
+                "0",
+                "30",
+                "60",
+            )),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = objective::ObjectiveArgs {
+        action: objective::ObjectiveAction::Get { id: 42 },
+    };
+    let result = objective::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("  Key Results:\n     50% Weekly signups"));
+}
+
+#[tokio::test]
+async fn get_objective_survives_key_result_failure() {
+    let (out, buf) = shortcut_cli::output::OutputConfig::with_buffer(
+        shortcut_cli::output::OutputMode::Human,
+        shortcut_cli::output::ColorMode::Never,
+    );
+    let server = MockServer::start().await;
+    let kr_id = "11111111-1111-1111-1111-111111111111";
+
+    let mut body = objective_json(42, "My Objective", "in progress", "");
+    body["key_result_ids"] = serde_json::json!([kr_id]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{kr_id}")))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = objective::ObjectiveArgs {
+        action: objective::ObjectiveAction::Get { id: 42 },
+    };
+    let result = objective::run(&args, &client, &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("42 - My Objective\n"));
+    assert!(output.contains("  Key Results: unavailable"));
+}
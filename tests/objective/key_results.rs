@@ -0,0 +1,108 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{key_result_json, objective_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::objective};
+
+const KR_SIGNUPS: &str = "11111111-1111-1111-1111-111111111111";
+const KR_CHURN: &str = "22222222-2222-2222-2222-222222222222";
+
+async fn mount_objective_with_key_results(server: &MockServer) {
+    let mut body = objective_json(42, "Grow the product", "in progress", "");
+    body["key_result_ids"] = serde_json::json!([KR_SIGNUPS, KR_CHURN]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{KR_SIGNUPS}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(key_result_json(
+            KR_SIGNUPS,
+            42,
+            "Weekly signups",
+            "100",
+            "142",
+            "200",
+        )))
+        .mount(server)
+        .await;
+    // Decreasing target: churn from 10 down to 5, currently 9.
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/key-results/{KR_CHURN}")))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(key_result_json(KR_CHURN, 42, "Churn", "10", "9", "5")),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn key_results_json_includes_percent() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    mount_objective_with_key_results(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = objective::ObjectiveArgs {
+        action: objective::ObjectiveAction::KeyResults { id: 42 },
+    };
+    let result = objective::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[0]["name"], "Weekly signups");
+    assert_eq!(json[0]["current"], 142.0);
+    assert_eq!(json[0]["target"], 200.0);
+    assert_eq!(json[0]["percent"], 42);
+    assert_eq!(json[1]["percent"], 20);
+}
+
+#[tokio::test]
+async fn key_results_human_shows_progress_bars() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    mount_objective_with_key_results(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = objective::ObjectiveArgs {
+        action: objective::ObjectiveAction::KeyResults { id: 42 },
+    };
+    let result = objective::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("42 - Grow the product\n"));
+    assert!(output.contains("42%  ████████░░░░░░░░░░░░"));
+    assert!(output.contains("142      200     Weekly signups"));
+}
+
+#[tokio::test]
+async fn key_results_empty() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/objectives/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(objective_json(
+            42,
+            "Grow the product",
+            "to do",
+            "",
+        )))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = objective::ObjectiveArgs {
+        action: objective::ObjectiveAction::KeyResults { id: 42 },
+    };
+    let result = objective::run(&args, &client, &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "No key results for this objective\n");
+}
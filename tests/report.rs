@@ -0,0 +1,4 @@
+mod support;
+
+#[path = "report/cycle_time.rs"]
+mod cycle_time;
@@ -0,0 +1,261 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{
+    completed_story_json, history_action_story_create_json, history_action_story_update_json,
+    history_entry_json, history_reference_workflow_state_json,
+};
+use shortcut_cli::api;
+use shortcut_cli::commands::report::{self, CycleTimeArgs, ReportAction, ReportArgs};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+
+fn make_cycle_time_args() -> CycleTimeArgs {
+    CycleTimeArgs {
+        epic_id: None,
+        iteration_id: None,
+        label: None,
+        group_id: None,
+        story_type: None,
+        since: None,
+        until: None,
+        csv: false,
+        refresh: false,
+    }
+}
+
+fn wrap(args: CycleTimeArgs) -> ReportArgs {
+    ReportArgs {
+        action: ReportAction::CycleTime(Box::new(args)),
+    }
+}
+
+/// Story 42: created Jan 1, started Jan 2, done Jan 4.
+async fn mount_completed_story(server: &MockServer, history_calls: u64) {
+    let stories = serde_json::json!([completed_story_json(
+        42,
+        "Fix login bug",
+        "bug",
+        "2024-01-01T00:00:00Z",
+        Some("2024-01-02T00:00:00Z"),
+        "2024-01-04T00:00:00Z",
+    )]);
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/search"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&stories))
+        .mount(server)
+        .await;
+
+    let mut create = history_action_story_create_json(42, "Fix login bug", "bug");
+    create["workflow_state_id"] = serde_json::Value::from(500000007_i64);
+    let history = serde_json::json!([
+        history_entry_json(
+            "11111111-1111-1111-1111-111111111111",
+            "2024-01-01T00:00:00Z",
+            None,
+            vec![create],
+            vec![history_reference_workflow_state_json(
+                500000007,
+                "Unstarted",
+                "unstarted"
+            )],
+        ),
+        history_entry_json(
+            "22222222-2222-2222-2222-222222222222",
+            "2024-01-02T00:00:00Z",
+            None,
+            vec![history_action_story_update_json(
+                42,
+                "Fix login bug",
+                "bug",
+                serde_json::json!({
+                    "workflow_state_id": { "old": 500000007, "new": 500000008 }
+                }),
+            )],
+            vec![
+                history_reference_workflow_state_json(500000007, "Unstarted", "unstarted"),
+                history_reference_workflow_state_json(500000008, "In Progress", "started"),
+            ],
+        ),
+        history_entry_json(
+            "33333333-3333-3333-3333-333333333333",
+            "2024-01-04T00:00:00Z",
+            None,
+            vec![history_action_story_update_json(
+                42,
+                "Fix login bug",
+                "bug",
+                serde_json::json!({
+                    "workflow_state_id": { "old": 500000008, "new": 500000009 }
+                }),
+            )],
+            vec![
+                history_reference_workflow_state_json(500000008, "In Progress", "started"),
+                history_reference_workflow_state_json(500000009, "Done", "done"),
+            ],
+        ),
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&history))
+        .expect(history_calls)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn cycle_time_human_summary() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_completed_story(&server, 1).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = report::run(
+        &wrap(make_cycle_time_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("Completed stories: 1"));
+    assert!(output.contains("Lead time"));
+    assert!(output.contains("3.0d"));
+    assert!(output.contains("Cycle time"));
+    assert!(output.contains("2.0d"));
+    assert!(output.contains("bug"));
+    assert!(output.contains("Unstarted"));
+    assert!(output.contains("In Progress"));
+}
+
+#[tokio::test]
+async fn cycle_time_json_output() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_completed_story(&server, 1).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = report::run(
+        &wrap(make_cycle_time_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let story = &json["stories"][0];
+    assert_eq!(story["id"], 42);
+    assert_eq!(story["lead_time_days"], 3.0);
+    assert_eq!(story["cycle_time_days"], 2.0);
+    assert_eq!(story["time_in_state_days"]["Unstarted"], 1.0);
+    assert_eq!(story["time_in_state_days"]["In Progress"], 2.0);
+    assert!(story["time_in_state_days"].get("Done").is_none());
+    assert_eq!(json["summary"]["lead_time"]["p50_days"], 3.0);
+    assert_eq!(json["summary"]["by_type"]["bug"]["cycle_time"]["count"], 1);
+}
+
+#[tokio::test]
+async fn cycle_time_csv_output() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_completed_story(&server, 1).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_cycle_time_args();
+    args.csv = true;
+    let result = report::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("id,name,story_type,"));
+    assert!(lines[0].ends_with("In Progress (days),Unstarted (days)"));
+    assert!(lines[1].starts_with("42,Fix login bug,bug,"));
+    assert!(lines[1].ends_with("3.00,2.00,2.00,1.00"));
+}
+
+#[tokio::test]
+async fn cycle_time_reuses_cached_history() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_completed_story(&server, 1).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    for _ in 0..2 {
+        let result = report::run(
+            &wrap(make_cycle_time_args()),
+            &client,
+            tmp.path().to_path_buf(),
+            &out,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+    assert!(tmp.path().join("story_history").join("42.json").exists());
+}
+
+#[tokio::test]
+async fn cycle_time_refresh_bypasses_cache() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_completed_story(&server, 2).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    for _ in 0..2 {
+        let mut args = make_cycle_time_args();
+        args.refresh = true;
+        let result = report::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+        assert!(result.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn cycle_time_no_completed_stories() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/search"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = report::run(
+        &wrap(make_cycle_time_args()),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "No completed stories found");
+}
+
+#[tokio::test]
+async fn cycle_time_invalid_date_errors() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_cycle_time_args();
+    args.since = Some("last week".to_string());
+    let result = report::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Invalid --since date"));
+}
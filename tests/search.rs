@@ -21,5 +21,6 @@ pub fn make_query(query: &str) -> shortcut_cli::commands::search::SearchQueryArg
         page_size: 25,
         next: None,
         desc: false,
+        archived: false,
     }
 }
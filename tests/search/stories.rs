@@ -1,4 +1,4 @@
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::make_query;
@@ -85,3 +85,39 @@ async fn search_stories_with_pagination() {
     let result = search::run(&args, &client, &out).await;
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn search_stories_excludes_archived_unless_requested() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+
+    let body = serde_json::json!({ "data": [], "next": null, "total": 0 });
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", "login !is:archived"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", "login"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = search::SearchArgs {
+        action: search::SearchAction::Stories(make_query("login")),
+    };
+    assert!(search::run(&args, &client, &out).await.is_ok());
+
+    let mut query = make_query("login");
+    query.archived = true;
+    let args = search::SearchArgs {
+        action: search::SearchAction::Stories(query),
+    };
+    assert!(search::run(&args, &client, &out).await.is_ok());
+}
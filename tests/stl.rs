@@ -1,10 +1,20 @@
+#[path = "stl/condition.rs"]
+mod condition;
+#[path = "stl/functions.rs"]
+mod functions;
+#[path = "stl/lsp.rs"]
+mod lsp;
 #[path = "stl/parser.rs"]
 mod parser;
 #[path = "stl/reconciler.rs"]
 mod reconciler;
 #[path = "stl/resolver.rs"]
 mod resolver;
+#[path = "stl/span.rs"]
+mod span;
 #[path = "stl/state.rs"]
 mod state;
 #[path = "stl/validator.rs"]
 mod validator;
+#[path = "stl/vars.rs"]
+mod vars;
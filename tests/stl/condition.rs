@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use shortcut_cli::stl::condition::{evaluate, parse};
+
+fn eval(expr: &str) -> Result<bool, String> {
+    let mut vars = HashMap::new();
+    vars.insert("include_docs".to_string(), serde_yaml::Value::Bool(false));
+    vars.insert("team".to_string(), serde_yaml::Value::String("web".into()));
+    vars.insert("count".to_string(), serde_yaml::Value::Number(3.into()));
+    vars.insert(
+        "flag".to_string(),
+        serde_yaml::Value::String("false".into()),
+    );
+
+    let mut results = HashMap::new();
+    results.insert(
+        "story".to_string(),
+        serde_json::json!({"id": 42, "completed": false, "owner_ids": [], "estimate": 5}),
+    );
+    results.insert("missing".to_string(), serde_json::json!({"id": null}));
+    results.insert(
+        "tasks".to_string(),
+        serde_json::json!([{"id": 1}, {"id": 2}]),
+    );
+
+    evaluate(&parse(expr)?, &vars, &results)
+}
+
+#[test]
+fn evaluates_vars_and_comparisons() {
+    assert_eq!(eval("$var(include_docs)"), Ok(false));
+    assert_eq!(eval("$var(team) == 'web'"), Ok(true));
+    assert_eq!(eval("$var(count) >= 3 && $var(count) < 10"), Ok(true));
+    assert_eq!(eval("$var(team) != \"web\""), Ok(false));
+    assert_eq!(eval("$var(count) == '3'"), Ok(true));
+}
+
+#[test]
+fn evaluates_refs_and_existence() {
+    assert_eq!(eval("$ref(story) == 42"), Ok(true));
+    assert_eq!(eval("not $ref(story.completed)"), Ok(true));
+    assert_eq!(eval("$ref(story.estimate) > 3"), Ok(true));
+    assert_eq!(eval("$ref(story.owner_ids)"), Ok(false));
+    assert_eq!(eval("$ref(tasks.1.id) == 2"), Ok(true));
+    assert_eq!(eval("exists($ref(story.completed))"), Ok(true));
+    assert_eq!(eval("exists($ref(missing))"), Ok(false));
+    assert_eq!(eval("exists($ref(skipped.id))"), Ok(false));
+}
+
+#[test]
+fn boolean_logic_and_precedence() {
+    assert_eq!(eval("true || false && false"), Ok(true));
+    assert_eq!(eval("(true || false) && false"), Ok(false));
+    assert_eq!(
+        eval("!$var(include_docs) and $var(team) == 'web'"),
+        Ok(true)
+    );
+    assert_eq!(eval("$var(flag) or null"), Ok(false));
+}
+
+#[test]
+fn reports_parse_and_type_errors() {
+    assert_eq!(parse(""), Err("empty expression".to_string()));
+    assert_eq!(
+        parse("$var(team) == web"),
+        Err("unknown word 'web' (quote strings, e.g. 'web')".to_string())
+    );
+    assert_eq!(
+        parse("(true"),
+        Err("unexpected end of expression".to_string())
+    );
+    assert_eq!(
+        parse("exists(true)"),
+        Err("exists() takes a $var() or $ref()".to_string())
+    );
+    assert_eq!(parse("true false"), Err("unexpected 'false'".to_string()));
+    assert_eq!(
+        eval("$var(team) > 3"),
+        Err("cannot compare string with number using '>'".to_string())
+    );
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use shortcut_cli::stl::functions::{evaluate, parse};
+use shortcut_cli::stl::resolver::substitute_vars;
+
+fn vars() -> HashMap<String, serde_yaml::Value> {
+    let mut vars = HashMap::new();
+    vars.insert("start".to_string(), "2024-06-01".into());
+    vars.insert("service".to_string(), "Billing API v2".into());
+    vars.insert("points".to_string(), 3.into());
+    vars
+}
+
+fn eval(expr: &str) -> Result<String, String> {
+    evaluate(&parse(expr)?, &vars())
+}
+
+#[test]
+fn date_add_supports_days_weeks_and_timestamps() {
+    assert_eq!(eval("date_add($var(start), 14d)").unwrap(), "2024-06-15");
+    assert_eq!(eval("date_add($var(start), -1w)").unwrap(), "2024-05-25");
+    assert_eq!(
+        eval("date_add('2024-06-01T09:30:00Z', 2d)").unwrap(),
+        "2024-06-03T09:30:00Z"
+    );
+    assert_eq!(
+        eval("date_add($var(start), 3m)").unwrap_err(),
+        "date_add(): expected a duration like 14d or 2w, got '3m'"
+    );
+    assert_eq!(
+        eval("date_add($var(service), 1d)").unwrap_err(),
+        "date_add(): expected a date (YYYY-MM-DD) or RFC 3339 timestamp, got 'Billing API v2'"
+    );
+}
+
+#[test]
+fn format_date_uses_strftime_and_rejects_bad_formats() {
+    assert_eq!(
+        eval("format_date($var(start), '%B %d, %Y')").unwrap(),
+        "June 01, 2024"
+    );
+    assert_eq!(
+        eval("format_date(date_add($var(start), 1w), %m/%d)").unwrap(),
+        "06/08"
+    );
+    assert_eq!(
+        eval("format_date($var(start), '%Q')").unwrap_err(),
+        "format_date(): invalid format '%Q'"
+    );
+}
+
+#[test]
+fn string_functions() {
+    assert_eq!(eval("upper($var(service))").unwrap(), "BILLING API V2");
+    assert_eq!(eval("lower('MiXeD')").unwrap(), "mixed");
+    assert_eq!(eval("slug($var(service))").unwrap(), "billing-api-v2");
+    assert_eq!(
+        eval("slug('  --Hello, World!--  ')").unwrap(),
+        "hello-world"
+    );
+    assert_eq!(
+        eval("upper(slug($var(service)))").unwrap(),
+        "BILLING-API-V2"
+    );
+}
+
+#[test]
+fn now_and_today_produce_dates() {
+    let today = chrono::Utc::now().date_naive().to_string();
+    assert_eq!(eval("today()").unwrap(), today);
+    assert!(eval("now()").unwrap().starts_with(&today[..4]));
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(&eval("now()").unwrap()).is_ok(),
+        "now() should be an RFC 3339 timestamp"
+    );
+}
+
+#[test]
+fn env_reads_variables_with_optional_default() {
+    let path = std::env::var("PATH").unwrap();
+    assert_eq!(eval("env(PATH)").unwrap(), path);
+    assert_eq!(
+        eval("env(SHORTCUT_STL_TEST_UNSET_VAR, fallback)").unwrap(),
+        "fallback"
+    );
+    assert_eq!(
+        eval("env(SHORTCUT_STL_TEST_UNSET_VAR)").unwrap_err(),
+        "env(): environment variable 'SHORTCUT_STL_TEST_UNSET_VAR' is not set"
+    );
+}
+
+#[test]
+fn env_refuses_api_token() {
+    assert_eq!(
+        eval("env(SHORTCUT_API_TOKEN)").unwrap_err(),
+        "env(): environment variable 'SHORTCUT_API_TOKEN' cannot be read from templates"
+    );
+    assert_eq!(
+        eval("env(SHORTCUT_API_TOKEN, fallback)").unwrap_err(),
+        "env(): environment variable 'SHORTCUT_API_TOKEN' cannot be read from templates"
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse("shout(x)").unwrap_err(), "unknown function 'shout'");
+    assert_eq!(
+        parse("upper(a, b)").unwrap_err(),
+        "upper() takes 1 argument, got 2"
+    );
+    assert_eq!(
+        parse("now(1)").unwrap_err(),
+        "now() takes 0 arguments, got 1"
+    );
+    assert_eq!(
+        parse("env()").unwrap_err(),
+        "env() takes 1 to 2 arguments, got 0"
+    );
+    assert_eq!(
+        parse("plain text").unwrap_err(),
+        "expected a function call, got 'plain text'"
+    );
+    assert_eq!(
+        parse("upper($ref(epic))").unwrap_err(),
+        "$ref() cannot be used inside $fn(); only $var() values are available"
+    );
+    let deep = format!("{}x{}", "upper(".repeat(20), ")".repeat(20));
+    assert_eq!(parse(&deep).unwrap_err(), "expression is nested too deeply");
+}
+
+#[test]
+fn substitution_interpolates_fn_calls() {
+    let mut val = serde_yaml::Value::String(
+        "$fn(upper($var(service))) due $fn(date_add($var(start), 1w)) ($var(points) pts)".into(),
+    );
+    substitute_vars(&mut val, &vars()).unwrap();
+    assert_eq!(val, "BILLING API V2 due 2024-06-08 (3 pts)");
+
+    let mut val = serde_yaml::Value::String("$fn(format_date($var(start), 'Q%m (%Y)'))".into());
+    substitute_vars(&mut val, &vars()).unwrap();
+    assert_eq!(val, "Q06 (2024)");
+}
+
+#[test]
+fn substitution_reports_fn_errors() {
+    let mut val = serde_yaml::Value::String("$fn(upper($var(missing))) and $fn(slug(x)".into());
+    let errors = substitute_vars(&mut val, &vars()).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "$fn(upper($var(missing))): undeclared variable 'missing'".to_string(),
+            "unterminated $fn(slug(x)".to_string(),
+        ]
+    );
+}
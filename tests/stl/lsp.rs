@@ -0,0 +1,188 @@
+use serde_json::{Value, json};
+
+use shortcut_cli::stl::lsp::{completions, definition, diagnostics, serve};
+use shortcut_cli::stl::span::Position;
+
+const DOC: &str = r#"version: 1
+vars:
+  team: "@backend"
+operations:
+  - action: create
+    entity: epic
+    alias: launch
+    fields:
+      name: "Launch"
+  - action: create
+    entity: story
+    fields:
+      name: "Checklist $var(team)"
+      epic_id: $ref(launch)
+      colour: red
+  - action: 
+"#;
+
+fn at(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+fn labels(items: Vec<Value>) -> Vec<String> {
+    items
+        .iter()
+        .map(|i| i["label"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+fn unframe(output: &str) -> Vec<Value> {
+    output
+        .split("Content-Length: ")
+        .skip(1)
+        .map(|part| serde_json::from_str(part.split_once("\r\n\r\n").unwrap().1).unwrap())
+        .collect()
+}
+
+#[test]
+fn diagnostics_point_at_the_offending_text() {
+    let diagnostics = diagnostics(&DOC.replace("  - action: \n", ""), "/tmp/doc.shortcut.yml");
+    let summary: Vec<(&str, &Value)> = diagnostics
+        .iter()
+        .map(|d| (d["message"].as_str().unwrap(), &d["range"]))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(
+            "unknown field 'colour' for story entity",
+            &json!({"start": {"line": 14, "character": 6}, "end": {"line": 14, "character": 12}})
+        )]
+    );
+}
+
+#[test]
+fn parse_errors_use_the_yaml_location() {
+    let diagnostics = diagnostics(DOC, "/tmp/doc.shortcut.yml");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(
+        diagnostics[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("operations[2].action"),
+        "{diagnostics:?}"
+    );
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 15);
+}
+
+#[test]
+fn completes_operation_keys_values_and_fields() {
+    let path = "/tmp/doc.shortcut.yml";
+    assert_eq!(labels(completions(DOC, path, at(16, 13))).len(), 9);
+    assert!(labels(completions(DOC, path, at(11, 13))).contains(&"story_link".to_string()));
+
+    let doc = DOC.replace("      colour: red\n", "      colour: red\n      \n");
+    let fields = labels(completions(&doc, path, at(16, 7)));
+    assert!(fields.contains(&"estimate".to_string()), "{fields:?}");
+
+    let doc = DOC.replace("  - action: \n", "  - \n");
+    let keys = labels(completions(&doc, path, at(16, 5)));
+    assert_eq!(keys[..3], ["action", "entity", "alias"]);
+}
+
+#[test]
+fn completes_aliases_and_vars() {
+    let path = "/tmp/doc.shortcut.yml";
+    let doc = DOC.replace("$ref(launch)", "$ref(");
+    assert_eq!(labels(completions(&doc, path, at(14, 21))), ["launch"]);
+    let doc = DOC.replace("$var(team)", "$var(");
+    assert_eq!(labels(completions(&doc, path, at(13, 29))), ["team"]);
+}
+
+#[test]
+fn goes_to_alias_and_var_definitions() {
+    let path = "/tmp/doc.shortcut.yml";
+    assert_eq!(
+        definition(DOC, path, at(14, 20)),
+        Some(json!({
+            "uri": "file:///tmp/doc.shortcut.yml",
+            "range": {"start": {"line": 6, "character": 11}, "end": {"line": 6, "character": 11}},
+        }))
+    );
+    assert_eq!(
+        definition(DOC, path, at(13, 28)),
+        Some(json!({
+            "uri": "file:///tmp/doc.shortcut.yml",
+            "range": {"start": {"line": 2, "character": 2}, "end": {"line": 2, "character": 2}},
+        }))
+    );
+    assert_eq!(definition(DOC, path, at(13, 9)), None);
+}
+
+#[test]
+fn replies_to_malformed_json_and_keeps_serving() {
+    let garbage = "{\"jsonrpc\": \"2.0\", \"id\": 1,";
+    let input = [
+        format!("Content-Length: {}\r\n\r\n{garbage}", garbage.len()),
+        frame(json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"})),
+        frame(json!({"jsonrpc": "2.0", "method": "exit"})),
+    ]
+    .concat();
+
+    let mut output = Vec::new();
+    serve(input.as_bytes(), &mut output).unwrap();
+    let messages = unframe(&String::from_utf8(output).unwrap());
+
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["id"], Value::Null);
+    assert_eq!(messages[0]["error"]["code"], -32700);
+    assert_eq!(
+        messages[1],
+        json!({"jsonrpc": "2.0", "id": 2, "result": null})
+    );
+}
+
+#[test]
+fn serves_a_session_over_framed_json_rpc() {
+    let uri = "file:///tmp/session%20test.shortcut.yml";
+    let input = [
+        frame(json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})),
+        frame(json!({"jsonrpc": "2.0", "method": "initialized", "params": {}})),
+        frame(json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": {"uri": uri, "languageId": "yaml", "version": 1, "text": DOC.replace("  - action: \n", "")},
+        }})),
+        frame(json!({"jsonrpc": "2.0", "id": 2, "method": "textDocument/completion", "params": {
+            "textDocument": {"uri": uri}, "position": {"line": 9, "character": 12},
+        }})),
+        frame(json!({"jsonrpc": "2.0", "id": 3, "method": "workspace/symbol", "params": {}})),
+        frame(json!({"jsonrpc": "2.0", "id": 4, "method": "shutdown"})),
+        frame(json!({"jsonrpc": "2.0", "method": "exit"})),
+    ]
+    .concat();
+
+    let mut output = Vec::new();
+    serve(input.as_bytes(), &mut output).unwrap();
+    let messages = unframe(&String::from_utf8(output).unwrap());
+
+    assert_eq!(messages.len(), 5);
+    assert_eq!(
+        messages[0]["result"]["capabilities"]["definitionProvider"],
+        true
+    );
+    assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(messages[1]["params"]["uri"], uri);
+    assert_eq!(
+        messages[1]["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(messages[2]["id"], 2);
+    assert_eq!(messages[2]["result"].as_array().unwrap().len(), 9);
+    assert_eq!(messages[3]["error"]["code"], -32601);
+    assert_eq!(
+        messages[4],
+        json!({"jsonrpc": "2.0", "id": 4, "result": null})
+    );
+}
@@ -1,4 +1,4 @@
-use shortcut_cli::stl::parser::parse;
+use shortcut_cli::stl::parser::{parse, parse_from_path};
 use shortcut_cli::stl::types::{Action, Entity, ErrorHandling};
 
 #[test]
@@ -387,3 +387,172 @@ fn parse_block_scalar_description() {
         "Expected blank line between paragraphs, got: {text:?}"
     );
 }
+
+fn write(dir: &tempfile::TempDir, name: &str, yaml: &str) -> String {
+    let path = dir.path().join(name);
+    std::fs::write(&path, yaml).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+const CI_TEMPLATE: &str = r#"version: 1
+vars:
+  service: web
+  epic: null
+operations:
+  - action: create
+    entity: story
+    alias: pipeline
+    fields:
+      name: "Set up CI for $var(service)"
+      epic_id: $var(epic)
+  - action: create
+    entity: task
+    when: "$var(service) != 'docs'"
+    fields:
+      story_id: $ref(pipeline)
+      description: "Add $var(service) to deploy"
+"#;
+
+#[test]
+fn parse_includes_with_namespaced_aliases_and_bound_vars() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(&tmp, "ci.shortcut.yml", CI_TEMPLATE);
+    let main = write(
+        &tmp,
+        "main.shortcut.yml",
+        r#"version: 1
+include:
+  - file: ci.shortcut.yml
+    vars:
+      service: api
+      epic: 5
+operations:
+  - action: create
+    entity: epic
+    alias: auth
+    fields:
+      name: "Auth"
+  - $include(ci.shortcut.yml, as=mobile, service=mobile, epic=$ref(auth))
+  - action: comment
+    entity: story
+    id: $ref(mobile.pipeline)
+    fields:
+      text: "Done"
+"#,
+    );
+
+    let t = parse_from_path(&main).unwrap();
+    let field = |i: usize, name: &str| {
+        t.operations[i].fields.as_ref().unwrap()[serde_yaml::Value::String(name.into())].clone()
+    };
+    assert_eq!(t.operations.len(), 6);
+    assert_eq!(t.operations[0].alias.as_deref(), Some("ci.pipeline"));
+    assert_eq!(field(0, "name"), "Set up CI for api");
+    assert_eq!(field(0, "epic_id"), 5);
+    assert_eq!(field(1, "story_id"), "$ref(ci.pipeline)");
+    assert_eq!(t.operations[1].when.as_deref(), Some(r#""api" != 'docs'"#));
+    assert_eq!(t.operations[2].alias.as_deref(), Some("auth"));
+    assert_eq!(t.operations[3].alias.as_deref(), Some("mobile.pipeline"));
+    assert_eq!(field(3, "epic_id"), "$ref(auth)");
+    assert_eq!(field(4, "story_id"), "$ref(mobile.pipeline)");
+
+    let origin = t.operations[4].origin.as_ref().unwrap();
+    assert!(origin.file.ends_with("ci.shortcut.yml"));
+    assert_eq!(origin.line, Some(12));
+    assert_eq!(origin.index, 1);
+    assert_eq!(origin.namespace.as_deref(), Some("mobile"));
+    let origin = t.operations[5].origin.as_ref().unwrap();
+    assert_eq!((origin.line, origin.index), (Some(14), 2));
+    assert!(t.operations[5].origin.as_ref().unwrap().namespace.is_none());
+
+    assert!(shortcut_cli::stl::validator::validate(&t).is_empty());
+}
+
+#[test]
+fn parse_include_cycle_is_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(
+        &tmp,
+        "a.shortcut.yml",
+        "version: 1\noperations:\n  - $include(b.shortcut.yml)\n",
+    );
+    write(
+        &tmp,
+        "b.shortcut.yml",
+        "version: 1\noperations:\n  - $include(a.shortcut.yml)\n",
+    );
+    let main = tmp.path().join("a.shortcut.yml");
+
+    let err = parse_from_path(main.to_str().unwrap())
+        .err()
+        .unwrap()
+        .to_string();
+    let dir = tmp.path().to_str().unwrap();
+    assert_eq!(
+        err,
+        format!(
+            "include cycle: {dir}/a.shortcut.yml -> {dir}/b.shortcut.yml -> {dir}/a.shortcut.yml"
+        )
+    );
+}
+
+#[test]
+fn parse_include_rejects_undeclared_var() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(&tmp, "ci.shortcut.yml", CI_TEMPLATE);
+    let main = write(
+        &tmp,
+        "main.shortcut.yml",
+        "version: 1\noperations:\n  - $include(ci.shortcut.yml, team=web)\n",
+    );
+
+    let err = parse_from_path(&main).err().unwrap().to_string();
+    assert!(
+        err.starts_with("include 'ci': variable 'team' is not declared in "),
+        "{err}"
+    );
+}
+
+#[test]
+fn parse_include_checks_bound_vars_against_declarations() {
+    let tmp = tempfile::tempdir().unwrap();
+    write(
+        &tmp,
+        "inc.shortcut.yml",
+        r#"version: 1
+vars:
+  count: { type: int, default: 1 }
+  tier: { type: enum, choices: [gold, silver], default: gold }
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: "$var(tier) x$var(count)"
+      estimate: $var(count)
+"#,
+    );
+    let parse_main = |args: &str| {
+        let main = write(
+            &tmp,
+            "main.shortcut.yml",
+            &format!("version: 1\noperations:\n  - $include(inc.shortcut.yml, {args})\n"),
+        );
+        parse_from_path(&main)
+    };
+
+    let err = parse_main("count=abc").err().unwrap().to_string();
+    assert_eq!(
+        err,
+        "include 'inc': variable 'count': expected an integer, got 'abc'"
+    );
+    let err = parse_main("tier=platinum").err().unwrap().to_string();
+    assert!(
+        err.starts_with("include 'inc': variable 'tier': expected one of "),
+        "{err}"
+    );
+
+    let t = parse_main("count=3, tier=silver").unwrap();
+    let fields = t.operations[0].fields.as_ref().unwrap();
+    assert_eq!(fields["name"], "silver x3");
+    assert_eq!(fields["estimate"], 3);
+}
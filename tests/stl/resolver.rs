@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use shortcut_cli::stl::resolver::{resolve_refs, substitute_vars, yaml_to_json};
+use shortcut_cli::stl::resolver::{resolve_refs, substitute_item, substitute_vars, yaml_to_json};
 
 // --- Variable substitution tests ---
 
@@ -135,6 +135,21 @@ fn substitute_multiple_vars_in_one_string() {
     );
 }
 
+#[test]
+fn substitute_var_followed_by_fn() {
+    let mut vars = HashMap::new();
+    vars.insert("svc".to_string(), serde_yaml::Value::String("api".into()));
+    vars.insert("env".to_string(), serde_yaml::Value::String("prod".into()));
+
+    let mut val = serde_yaml::Value::String("$var(svc)-$fn(upper($var(env)))".into());
+    substitute_vars(&mut val, &vars).unwrap();
+    assert_eq!(val, serde_yaml::Value::String("api-PROD".into()));
+
+    let mut val = serde_yaml::Value::String("$var(svc)-$fn(upper(x))".into());
+    substitute_vars(&mut val, &vars).unwrap();
+    assert_eq!(val, serde_yaml::Value::String("api-X".into()));
+}
+
 // --- Reference resolution tests ---
 
 #[test]
@@ -278,3 +293,32 @@ fn yaml_to_json_nested() {
         serde_json::json!({"name": "Test", "labels": ["a", "b"]})
     );
 }
+
+// --- for_each item substitution tests ---
+
+#[test]
+fn substitute_item_keeps_type_and_interpolates() {
+    let item = serde_json::json!({"id": 12, "name": "Login", "owner_ids": ["u1"]});
+
+    let mut val: serde_yaml::Value = serde_yaml::from_str(
+        r#"
+story_id: $item(id)
+text: "Check $item(name) with $item(owner_ids.0)"
+"#,
+    )
+    .unwrap();
+    substitute_item(&mut val, &item).unwrap();
+    assert_eq!(val["story_id"], serde_yaml::Value::from(12));
+    assert_eq!(val["text"], serde_yaml::Value::from("Check Login with u1"));
+}
+
+#[test]
+fn substitute_item_missing_field() {
+    let item = serde_json::json!({"id": 12});
+    let mut val = serde_yaml::Value::String("$item(estimate)".into());
+    let errs = substitute_item(&mut val, &item).unwrap_err();
+    assert_eq!(
+        errs,
+        vec!["$item(estimate): field 'estimate' not found in item".to_string()]
+    );
+}
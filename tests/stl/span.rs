@@ -0,0 +1,91 @@
+use shortcut_cli::stl::span::{Position, SourceMap};
+
+const YAML: &str = r#"version: 1
+vars:
+  team: "@backend"   # owners
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: 'Launch'
+      description: |
+        name: not a key
+        - not an item
+      labels:
+      - name: one
+      - two
+  - $include(ci.shortcut.yml)
+"#;
+
+fn at(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+#[test]
+fn indexes_keys_and_items_by_path() {
+    let map = SourceMap::new(YAML);
+    let paths: Vec<&str> = map.spans().iter().map(|s| s.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "version",
+            "vars",
+            "vars.team",
+            "operations",
+            "operations.0",
+            "operations.0.action",
+            "operations.0.entity",
+            "operations.0.fields",
+            "operations.0.fields.name",
+            "operations.0.fields.description",
+            "operations.0.fields.labels",
+            "operations.0.fields.labels.0",
+            "operations.0.fields.labels.0.name",
+            "operations.0.fields.labels.1",
+            "operations.1",
+        ]
+    );
+}
+
+#[test]
+fn records_key_and_value_positions() {
+    let map = SourceMap::new(YAML);
+    let team = map.get("vars.team").unwrap();
+    assert_eq!(team.key, at(3, 3));
+    assert_eq!(team.value, Some((at(3, 10), "@backend".to_string())));
+
+    let item = map.get("operations.0").unwrap();
+    assert_eq!((item.key, item.value.clone()), (at(5, 3), None));
+    assert_eq!(map.get("operations.0.action").unwrap().key, at(5, 5));
+
+    let include = map.get("operations.1").unwrap();
+    assert_eq!(
+        include.value,
+        Some((at(15, 5), "$include(ci.shortcut.yml)".to_string()))
+    );
+    assert_eq!(
+        map.get("operations.0.fields.labels.1").unwrap().value,
+        Some((at(14, 9), "two".to_string()))
+    );
+}
+
+#[test]
+fn under_strips_the_prefix() {
+    let map = SourceMap::new(YAML);
+    let op: Vec<String> = map
+        .under("operations.0.fields")
+        .into_iter()
+        .map(|s| s.path)
+        .collect();
+    assert_eq!(
+        op,
+        vec![
+            "name",
+            "description",
+            "labels",
+            "labels.0",
+            "labels.0.name",
+            "labels.1"
+        ]
+    );
+}
@@ -1,5 +1,5 @@
 use shortcut_cli::stl::parser;
-use shortcut_cli::stl::validator::{ValidationError, validate};
+use shortcut_cli::stl::validator::{ValidationError, dependencies, validate};
 
 fn parse_and_validate(yaml: &str) -> Vec<ValidationError> {
     let template = parser::parse(yaml).unwrap();
@@ -403,3 +403,362 @@ operations:
     );
     assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
 }
+
+#[test]
+fn find_with_name_is_valid() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: find
+    entity: epic
+    alias: auth
+    on_missing: empty
+    fields:
+      name: "Auth Hardening"
+  - action: create
+    entity: story
+    fields:
+      name: "Test"
+      epic_id: $ref(auth)
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
+}
+
+#[test]
+fn find_requires_alias_and_one_criterion() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: find
+    entity: story
+    fields:
+      name: "Login"
+      query: "is:blocked"
+"#,
+    );
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "find action requires an 'alias'",
+            "find action takes either 'name' or 'query', not both",
+        ]
+    );
+}
+
+#[test]
+fn find_rejects_query_for_unsearchable_entity_and_unknown_fields() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: find
+    entity: label
+    alias: bug
+    fields:
+      query: "bug"
+      color: red
+  - action: find
+    entity: milestone
+    alias: q3
+    fields:
+      name: "Q3"
+"#,
+    );
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "find label does not support 'query'; use 'name'",
+            "unknown field 'color' for find action",
+            "'find' action is not valid for 'milestone' entity",
+        ]
+    );
+}
+
+#[test]
+fn on_missing_only_valid_for_find() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: create
+    entity: story
+    on_missing: empty
+    fields:
+      name: "Test"
+"#,
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0]
+            .message
+            .contains("'on_missing' is only valid for find actions")
+    );
+}
+
+#[test]
+fn for_each_with_item_refs_is_valid() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: find
+    entity: epic
+    alias: auth
+    fields:
+      name: "Auth Hardening"
+  - action: update
+    entity: story
+    for_each:
+      epic: $ref(auth)
+      done: false
+    fields:
+      name: "[Auth] $item(name)"
+"#,
+    );
+    assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
+}
+
+#[test]
+fn item_ref_outside_for_each() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: update
+    entity: story
+    id: 12
+    fields:
+      name: "$item(name)"
+"#,
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0]
+            .message
+            .contains("$item(name) can only be used in a for_each operation")
+    );
+}
+
+#[test]
+fn for_each_source_errors() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+operations:
+  - action: create
+    entity: epic
+    alias: auth
+    fields:
+      name: "Auth"
+  - action: comment
+    entity: story
+    for_each:
+      query: "is:blocked"
+      epic: 55
+      done: "no"
+    fields:
+      text: "Ping"
+  - action: comment
+    entity: story
+    for_each:
+      items: $ref(auth)
+    fields:
+      text: "Ping"
+"#,
+    );
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "for_each 'done' must be true or false",
+            "for_each requires exactly one of 'query', 'epic', 'iteration', 'items'",
+            "for_each items: alias 'auth' is not a repeat or for_each operation",
+        ]
+    );
+}
+
+#[test]
+fn when_expressions_are_checked() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+vars:
+  include_docs: true
+operations:
+  - action: create
+    entity: epic
+    alias: docs
+    when: "$var(include_docs) && !exists($ref(docs))"
+    fields:
+      name: "Docs"
+  - action: create
+    entity: story
+    when: "exists($ref(docs)) and $var(team) == 'web'"
+    fields:
+      name: "Write docs"
+  - action: create
+    entity: story
+    when: "$ref(docs.completed) = false"
+    fields:
+      name: "Review docs"
+"#,
+    );
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "operation 1: $ref(docs) references undefined alias 'docs'",
+            "operation 2: $var(team) references undeclared variable 'team'",
+            "operation 3: invalid when expression: unexpected character '='",
+        ]
+    );
+}
+
+#[test]
+fn errors_in_included_operations_point_to_their_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let write = |name: &str, yaml: &str| {
+        let path = tmp.path().join(name);
+        std::fs::write(&path, yaml).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let docs = write(
+        "docs.shortcut.yml",
+        "version: 1\noperations:\n  - action: create\n    entity: epic\n    alias: docs\n    fields:\n      name: \"Docs\"\n  - action: update\n    entity: story\n    fields:\n      name: \"Write docs\"\n",
+    );
+    let main = write(
+        "main.shortcut.yml",
+        "version: 1\noperations:\n  - $include(docs.shortcut.yml)\n  - action: delete\n    entity: epic\n  - action: comment\n    entity: epic\n    id: $ref(docs.docs)\n    fields:\n      text: \"Ready\"\n",
+    );
+
+    let template = parser::parse_from_path(&main).unwrap();
+    let messages: Vec<String> = validate(&template).iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            format!("{docs}:8:3: operation 2: update action requires an 'id' field"),
+            format!("{main}:4:3: operation 2: delete action requires an 'id' field"),
+        ]
+    );
+}
+
+#[test]
+fn typed_var_declarations_are_checked() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+vars:
+  priority:
+    type: enum
+  points:
+    type: int
+    pattern: "[0-9]+"
+  owner:
+    type: member
+    default: alice
+  size:
+    type: string
+    choices: [s, m]
+  start:
+    type: date
+    default: "2024-02-30"
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: "Story"
+"#,
+    );
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "var 'owner': expected an @mention_name or member UUID, got 'alice'",
+            "var 'points': 'pattern' is only valid for string vars",
+            "var 'priority': enum vars require 'choices'",
+            "var 'size': 'choices' is only valid for enum vars",
+            "var 'start': expected a date (YYYY-MM-DD), got '2024-02-30'",
+        ]
+    );
+}
+
+#[test]
+fn fn_calls_are_checked() {
+    let errors = parse_and_validate(
+        r#"
+version: 1
+vars:
+  start: "2024-06-01"
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: "$fn(upper($var(start))) ok"
+  - action: create
+    entity: story
+    fields:
+      name: "$fn(shout($var(start)))"
+      description: "$fn(date_add($var(launch), 1d)) $fn(upper(a, b))"
+"#,
+    );
+    let messages: Vec<(Option<usize>, &str)> = errors
+        .iter()
+        .map(|e| (e.operation_index, e.message.as_str()))
+        .collect();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    assert!(messages.contains(&(Some(1), "$fn(shout($var(start))): unknown function 'shout'")));
+    assert!(messages.contains(&(
+        Some(1),
+        "$var(launch) references undeclared variable 'launch'"
+    )));
+    assert!(messages.contains(&(Some(1), "$fn(upper(a, b)): upper() takes 1 argument, got 2")));
+}
+
+#[test]
+fn dependencies_follow_refs() {
+    let template = parser::parse(
+        r#"
+version: 1
+operations:
+  - action: create
+    entity: epic
+    alias: epic
+    fields:
+      name: "Epic"
+  - action: create
+    entity: label
+    alias: label
+    fields:
+      name: "Label"
+  - action: create
+    entity: story
+    alias: stories
+    repeat:
+      - name: "A"
+        epic_id: $ref(epic)
+      - name: "B"
+        labels:
+          - name: "$ref(label.name)"
+  - action: comment
+    entity: story
+    id: $ref(stories.0.id)
+    when: exists($ref(epic.id))
+    fields:
+      text: "Done"
+  - action: create
+    entity: label
+    fields:
+      name: "$ref(missing)"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        dependencies(&template),
+        vec![vec![], vec![], vec![0, 1], vec![0, 2], vec![]]
+    );
+}
@@ -0,0 +1,136 @@
+use shortcut_cli::interactive::{MockAnswer, MockPrompter};
+use shortcut_cli::stl::parser::parse;
+use shortcut_cli::stl::types::{Template, VarType};
+use shortcut_cli::stl::vars::resolve;
+
+const TYPED: &str = r#"
+version: 1
+vars:
+  team: "@backend"
+  points:
+    type: int
+    default: 2
+  urgent:
+    type: bool
+    required: true
+  priority:
+    type: enum
+    choices: [low, high]
+    required: true
+  due:
+    type: date
+    required: true
+    description: "Launch date"
+  slug:
+    type: string
+    pattern: "[a-z-]+"
+    default: launch
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: "Launch"
+      estimate: $var(points)
+"#;
+
+fn var(template: &Template, name: &str) -> serde_yaml::Value {
+    template.vars.as_ref().unwrap()[name].clone()
+}
+
+fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn typed_vars_are_extracted_with_defaults() {
+    let t = parse(TYPED).unwrap();
+    assert_eq!(t.var_specs.len(), 5);
+    assert_eq!(t.var_specs["points"].var_type, VarType::Int);
+    assert!(t.var_specs["urgent"].required);
+    assert_eq!(var(&t, "team"), "@backend");
+    assert_eq!(var(&t, "points"), 2);
+    assert!(var(&t, "urgent").is_null());
+}
+
+#[test]
+fn overrides_are_coerced_to_declared_types() {
+    let mut t = parse(TYPED).unwrap();
+    resolve(
+        &mut t,
+        &overrides(&[
+            ("points", "5"),
+            ("urgent", "yes"),
+            ("priority", "high"),
+            ("due", "2024-06-01"),
+            ("team", "7"),
+        ]),
+        None,
+    )
+    .unwrap();
+    assert_eq!(var(&t, "points"), 5);
+    assert_eq!(var(&t, "urgent"), true);
+    assert_eq!(var(&t, "priority"), "high");
+    assert_eq!(var(&t, "due"), "2024-06-01");
+    // Untyped vars keep the string
+    assert_eq!(var(&t, "team"), "7");
+}
+
+#[test]
+fn invalid_overrides_are_rejected() {
+    let cases = [
+        (
+            "points",
+            "many",
+            "--var 'points': expected an integer, got 'many'",
+        ),
+        (
+            "priority",
+            "urgent",
+            "--var 'priority': expected one of low, high, got 'urgent'",
+        ),
+        (
+            "due",
+            "June",
+            "--var 'due': expected a date (YYYY-MM-DD), got 'June'",
+        ),
+        (
+            "slug",
+            "Launch!",
+            "--var 'slug': 'Launch!' does not match pattern '[a-z-]+'",
+        ),
+    ];
+    for (name, value, expected) in cases {
+        let mut t = parse(TYPED).unwrap();
+        let err = resolve(&mut t, &overrides(&[(name, value)]), None).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn missing_required_vars_error_without_a_terminal() {
+    let mut t = parse(TYPED).unwrap();
+    let err = resolve(&mut t, &overrides(&[("urgent", "no")]), None).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Missing required var(s): due, priority (pass --var name=value)"
+    );
+}
+
+#[test]
+fn missing_required_vars_are_prompted_for() {
+    let mut t = parse(TYPED).unwrap();
+    // Sorted by name: due (re-asked after an invalid date), priority, urgent
+    let prompter = MockPrompter::new(vec![
+        MockAnswer::Text("next week".into()),
+        MockAnswer::Text("2024-07-15".into()),
+        MockAnswer::Select("low".into()),
+        MockAnswer::Confirm(true),
+    ]);
+    resolve(&mut t, &[], Some(&prompter)).unwrap();
+    assert_eq!(var(&t, "due"), "2024-07-15");
+    assert_eq!(var(&t, "priority"), "low");
+    assert_eq!(var(&t, "urgent"), true);
+}
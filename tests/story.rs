@@ -1,7 +1,11 @@
 mod support;
 
+#[path = "story/archive.rs"]
+mod archive;
 #[path = "story/branch.rs"]
 mod branch;
+#[path = "story/clone.rs"]
+mod clone;
 #[path = "story/comment.rs"]
 mod comment;
 #[path = "story/commit.rs"]
@@ -14,16 +18,24 @@ mod custom_field;
 mod delete;
 #[path = "story/get.rs"]
 mod get;
+#[path = "story/graph.rs"]
+mod graph;
 #[path = "story/history.rs"]
 mod history;
 #[path = "story/link.rs"]
 mod link;
 #[path = "story/list.rs"]
 mod list;
+#[path = "story/subtask.rs"]
+mod subtask;
 #[path = "story/task.rs"]
 mod task;
+#[path = "story/trash.rs"]
+mod trash;
 #[path = "story/update.rs"]
 mod update;
+#[path = "story/watch.rs"]
+mod watch;
 #[path = "story/wizard.rs"]
 mod wizard;
 
@@ -65,6 +77,7 @@ pub fn make_update_args(id: i64) -> shortcut_cli::commands::story::UpdateArgs {
         estimate: None,
         labels: vec![],
         iteration_id: None,
+        group: None,
         custom_fields: vec![],
         parent_story_id: None,
         unless_state: vec![],
@@ -81,5 +94,6 @@ pub fn make_list_args() -> shortcut_cli::commands::story::ListArgs {
         project_id: None,
         limit: 25,
         desc: false,
+        archived: false,
     }
 }
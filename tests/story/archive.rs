@@ -0,0 +1,80 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{full_story_json, make_dry_run_output};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+#[tokio::test]
+async fn archive_story_sets_archived() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut archived = full_story_json(42, "Old story", "");
+    archived["archived"] = serde_json::Value::from(true);
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/42"))
+        .and(body_partial_json(serde_json::json!({ "archived": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&archived))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Archive { id: 42 },
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Archived story 42 - Old story");
+}
+
+#[tokio::test]
+async fn unarchive_story_clears_archived() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/42"))
+        .and(body_partial_json(serde_json::json!({ "archived": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            42,
+            "Old story",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Unarchive { id: 42 },
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Unarchived story 42 - Old story");
+}
+
+#[tokio::test]
+async fn dry_run_archive_story_shows_request() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Archive { id: 42 },
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[dry-run] PUT /api/v3/stories/42"));
+    assert!(output.contains("\"archived\": true"));
+}
@@ -0,0 +1,229 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{
+    full_story_json, label_json, make_dry_run_output, mount_default_workflow, story_comment_json,
+    story_custom_field_json, task_json, typed_story_link_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+const FIELD_ID: &str = "dddddddd-dddd-dddd-dddd-dddddddddddd";
+const VALUE_ID: &str = "eeeeeeee-eeee-eeee-eeee-eeeeeeeeeeee";
+
+fn make_clone_args(id: i64) -> story::CloneArgs {
+    story::CloneArgs {
+        id,
+        name: None,
+        epic_id: None,
+        iteration_id: None,
+        project_id: None,
+        group_id: None,
+        owner: vec![],
+        with_comments: false,
+        with_links: false,
+        count: 1,
+    }
+}
+
+fn wrap(args: story::CloneArgs) -> story::StoryArgs {
+    story::StoryArgs {
+        action: story::StoryAction::Clone(Box::new(args)),
+    }
+}
+
+fn source_story_json() -> serde_json::Value {
+    let mut source = full_story_json(42, "Fix login bug", "Steps to reproduce");
+    source["story_type"] = serde_json::Value::from("bug");
+    source["estimate"] = serde_json::Value::from(3);
+    source["epic_id"] = serde_json::Value::from(7);
+    source["iteration_id"] = serde_json::Value::from(3);
+    source["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    source["labels"] = serde_json::json!([label_json(1, "backend")]);
+    source["custom_fields"] =
+        serde_json::json!([story_custom_field_json(FIELD_ID, VALUE_ID, "High")]);
+    source["tasks"] = serde_json::json!([task_json(20, 42, "Write regression test", true)]);
+    source["comments"] = serde_json::json!([story_comment_json(
+        30,
+        42,
+        "Seen on Safari only",
+        UUID_ALICE
+    )]);
+    source["story_links"] = serde_json::json!([
+        typed_story_link_json(10, 42, 50, "blocks", "subject"),
+        typed_story_link_json(11, 60, 42, "duplicates", "object"),
+    ]);
+    source
+}
+
+async fn mount_source(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(source_story_json()))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn clone_copies_fields_and_tasks() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_source(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Fix login bug",
+            "description": "Steps to reproduce",
+            "story_type": "bug",
+            "estimate": 3,
+            "epic_id": 7,
+            "iteration_id": 3,
+            "owner_ids": [UUID_ALICE],
+            "labels": [{ "name": "backend" }],
+            "custom_fields": [{ "field_id": FIELD_ID, "value_id": VALUE_ID }],
+            "tasks": [{ "description": "Write regression test", "complete": true }],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            43,
+            "Fix login bug",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_clone_args(42)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let requests = server.received_requests().await.unwrap();
+    let post = requests
+        .iter()
+        .find(|r| r.method.as_str() == "POST")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&post.body).unwrap();
+    assert!(body.get("comments").is_none());
+    assert!(body.get("story_links").is_none());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Created story 43 - Fix login bug");
+}
+
+#[tokio::test]
+async fn clone_with_overrides_comments_and_links() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_source(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Fix login bug (Android)",
+            "epic_id": 9,
+            "iteration_id": 4,
+            "project_id": 5,
+            "comments": [{ "text": "Seen on Safari only", "author_id": UUID_ALICE }],
+            "story_links": [
+                { "verb": "blocks", "object_id": 50 },
+                { "verb": "duplicates", "subject_id": 60 },
+            ],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            43,
+            "Fix login bug (Android)",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_clone_args(42);
+    args.name = Some("Fix login bug (Android)".to_string());
+    args.epic_id = Some(9);
+    args.iteration_id = Some(4.into());
+    args.project_id = Some(5);
+    args.with_comments = true;
+    args.with_links = true;
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn clone_count_creates_multiple_copies() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_source(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            43,
+            "Fix login bug",
+            "",
+        )))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_clone_args(42);
+    args.count = 3;
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn dry_run_clone_shows_request() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_source(&server).await;
+
+    // No POST mock — if a POST is sent, wiremock returns 404 and the test fails
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_clone_args(42)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[dry-run] POST /api/v3/stories"));
+    assert!(output.contains("\"name\": \"Fix login bug\""));
+    assert!(output.contains("\"description\": \"Write regression test\""));
+}
+
+#[tokio::test]
+async fn clone_rejects_zero_count() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_clone_args(42);
+    args.count = 0;
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("--count must be at least 1"));
+}
@@ -31,6 +31,7 @@ async fn delete_story_with_confirm() {
         action: story::StoryAction::Delete {
             id: 42,
             confirm: true,
+            trash: false,
         },
     };
     let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
@@ -48,6 +49,7 @@ async fn delete_story_without_confirm_errors() {
         action: story::StoryAction::Delete {
             id: 42,
             confirm: false,
+            trash: false,
         },
     };
     let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
@@ -74,6 +76,7 @@ async fn delete_story_not_found() {
         action: story::StoryAction::Delete {
             id: 999,
             confirm: true,
+            trash: false,
         },
     };
     let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
@@ -93,6 +96,7 @@ async fn dry_run_story_delete_bypasses_confirm() {
         action: story::StoryAction::Delete {
             id: 42,
             confirm: false, // Would normally error, but dry-run bypasses
+            trash: false,
         },
     };
     let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
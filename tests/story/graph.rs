@@ -0,0 +1,363 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{
+    full_story_json_with_links, mount_default_workflow, story_json, typed_story_link_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+fn make_graph_args(id: Option<i64>) -> story::GraphArgs {
+    story::GraphArgs {
+        id,
+        epic_id: None,
+        iteration_id: None,
+        group: None,
+        depth: None,
+        dot: false,
+        mermaid: false,
+    }
+}
+
+fn wrap(args: story::GraphArgs) -> story::StoryArgs {
+    story::StoryArgs {
+        action: story::StoryAction::Graph(Box::new(args)),
+    }
+}
+
+async fn mount_story(server: &MockServer, story: serde_json::Value) {
+    let id = story["id"].as_i64().unwrap();
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/stories/{id}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&story))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+/// 1 blocks 2, 2 blocks 3, 3 relates to 4.
+async fn mount_chain(server: &MockServer) {
+    mount_story(
+        server,
+        full_story_json_with_links(
+            1,
+            "Design API",
+            "",
+            vec![typed_story_link_json(10, 1, 2, "blocks", "subject")],
+        ),
+    )
+    .await;
+    mount_story(
+        server,
+        full_story_json_with_links(
+            2,
+            "Build API",
+            "",
+            vec![
+                typed_story_link_json(10, 1, 2, "blocks", "object"),
+                typed_story_link_json(11, 2, 3, "blocks", "subject"),
+            ],
+        ),
+    )
+    .await;
+    mount_story(
+        server,
+        full_story_json_with_links(
+            3,
+            "Ship API",
+            "",
+            vec![
+                typed_story_link_json(11, 2, 3, "blocks", "object"),
+                typed_story_link_json(12, 3, 4, "relates to", "subject"),
+            ],
+        ),
+    )
+    .await;
+    let mut done = full_story_json_with_links(
+        4,
+        "Write docs",
+        "",
+        vec![typed_story_link_json(12, 3, 4, "relates to", "object")],
+    );
+    done["completed"] = serde_json::Value::from(true);
+    done["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    mount_story(server, done).await;
+}
+
+#[tokio::test]
+async fn graph_walks_links_transitively() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_chain(&server).await;
+    mount_default_workflow(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_graph_args(Some(1))),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("1 Design API [Unstarted]\n"));
+    assert!(output.contains("└── blocks 2 Build API [Unstarted]"));
+    assert!(output.contains("    └── blocks 3 Ship API [Unstarted]"));
+    assert!(output.contains("        └── relates to 4 Write docs [Done]"));
+    assert!(output.contains("Blocked stories:"));
+    assert!(output.contains("  2 - Build API (blocked by 1)"));
+    assert!(output.contains("  3 - Ship API (blocked by 2)"));
+    assert!(!output.contains("Dependency cycles:"));
+}
+
+#[tokio::test]
+async fn graph_respects_depth() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            1,
+            "Design API",
+            "",
+            vec![typed_story_link_json(10, 1, 2, "blocks", "subject")],
+        ),
+    )
+    .await;
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            2,
+            "Build API",
+            "",
+            vec![
+                typed_story_link_json(10, 1, 2, "blocks", "object"),
+                typed_story_link_json(11, 2, 3, "blocks", "subject"),
+            ],
+        ),
+    )
+    .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_graph_args(Some(1));
+    args.depth = Some(1);
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["nodes"].as_array().unwrap().len(), 2);
+    assert_eq!(json["edges"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn graph_detects_cycles() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            1,
+            "A",
+            "",
+            vec![
+                typed_story_link_json(10, 1, 2, "blocks", "subject"),
+                typed_story_link_json(11, 2, 1, "blocks", "object"),
+            ],
+        ),
+    )
+    .await;
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            2,
+            "B",
+            "",
+            vec![
+                typed_story_link_json(10, 1, 2, "blocks", "object"),
+                typed_story_link_json(11, 2, 1, "blocks", "subject"),
+            ],
+        ),
+    )
+    .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_graph_args(Some(1))),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["cycles"], serde_json::json!([[1, 2]]));
+}
+
+#[tokio::test]
+async fn graph_renders_dot() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_chain(&server).await;
+    mount_default_workflow(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_graph_args(Some(1));
+    args.dot = true;
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("digraph stories {"));
+    assert!(output.contains("\"2\" [label=\"2: Build API\\n(Unstarted)\", color=red];"));
+    assert!(output.contains("\"4\" [label=\"4: Write docs\\n(Done)\", style=dashed];"));
+    assert!(output.contains("\"1\" -> \"2\" [label=\"blocks\"];"));
+    assert!(output.contains("\"3\" -> \"4\" [label=\"relates to\", dir=none, style=dashed];"));
+    assert!(output.trim_end().ends_with('}'));
+}
+
+#[tokio::test]
+async fn graph_renders_mermaid() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_chain(&server).await;
+    mount_default_workflow(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_graph_args(Some(1));
+    args.mermaid = true;
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("graph LR\n"));
+    assert!(output.contains("  s1[\"1: Design API (Unstarted)\"]"));
+    assert!(output.contains("  s1 -->|blocks| s2"));
+    assert!(output.contains("  s3 -.-|relates to| s4"));
+    assert!(output.contains("  class s2,s3 blocked"));
+}
+
+#[tokio::test]
+async fn graph_epic_includes_sub_tasks() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut parent = story_json(5, "Checkout flow", None);
+    parent["sub_task_story_ids"] = serde_json::json!([6]);
+    let mut child = story_json(6, "Payment form", None);
+    child["parent_story_id"] = serde_json::Value::from(5);
+    let body = serde_json::json!([parent, child]);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/7/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_default_workflow(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut args = make_graph_args(None);
+    args.epic_id = Some(7);
+    let result = story::run(&wrap(args), &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        "5 Checkout flow [Unstarted]\n└── parent of 6 Payment form [Unstarted]\n"
+    );
+}
+
+#[tokio::test]
+async fn graph_keeps_deleted_stories_as_bare_ids() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            1,
+            "Design API",
+            "",
+            vec![typed_story_link_json(10, 2, 1, "blocks", "object")],
+        ),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/2"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(serde_json::json!({"message": "Resource not found."})),
+        )
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_graph_args(Some(1))),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok(), "{result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["nodes"].as_array().unwrap().len(), 1);
+    assert_eq!(json["edges"].as_array().unwrap().len(), 1);
+    assert_eq!(json["blocked"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn graph_fails_when_a_linked_story_cannot_be_fetched() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_story(
+        &server,
+        full_story_json_with_links(
+            1,
+            "Design API",
+            "",
+            vec![typed_story_link_json(10, 1, 2, "blocks", "subject")],
+        ),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/2"))
+        .respond_with(
+            ResponseTemplate::new(500).set_body_json(serde_json::json!({"message": "Oops"})),
+        )
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_graph_args(Some(1))),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.starts_with("Failed to get story 2: ") && err.contains("500"),
+        "{err}"
+    );
+}
@@ -1,4 +1,4 @@
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{body_partial_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::support::{default_icon, member_json, story_json, workflow_json, workflow_state_json};
@@ -234,3 +234,58 @@ async fn list_stories_api_error() {
     let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn list_stories_hides_archived_by_default() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    crate::support::mount_default_workflow(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/search"))
+        .and(body_partial_json(serde_json::json!({ "archived": false })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::List(Box::new(make_list_args())),
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn list_stories_archived_flag_includes_archived() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    crate::support::mount_default_workflow(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/search"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut list_args = make_list_args();
+    list_args.archived = true;
+    let args = story::StoryArgs {
+        action: story::StoryAction::List(Box::new(list_args)),
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let requests = server.received_requests().await.unwrap();
+    let search = requests
+        .iter()
+        .find(|r| r.url.path() == "/api/v3/stories/search")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&search.body).unwrap();
+    assert!(body.get("archived").is_none());
+}
@@ -0,0 +1,333 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{
+    full_story_json, full_story_json_with_tasks, mount_default_workflow, story_json, task_json,
+};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+const GROUP_ID: &str = "cccccccc-cccc-cccc-cccc-cccccccccccc";
+
+fn wrap(action: story::subtask::SubtaskAction) -> story::StoryArgs {
+    story::StoryArgs {
+        action: story::StoryAction::Subtask(story::subtask::SubtaskArgs { action }),
+    }
+}
+
+fn parent_story_json() -> serde_json::Value {
+    let mut parent = full_story_json(10, "Checkout flow", "");
+    parent["epic_id"] = serde_json::Value::from(7);
+    parent["iteration_id"] = serde_json::Value::from(3);
+    parent["group_id"] = serde_json::Value::from(GROUP_ID);
+    parent
+}
+
+#[tokio::test]
+async fn list_subtasks_shows_state_and_owner() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    std::fs::write(
+        tmp.path().join("member_cache.json"),
+        serde_json::json!({ "alice": UUID_ALICE }).to_string(),
+    )
+    .unwrap();
+
+    let mut sub = story_json(11, "Payment form", None);
+    sub["owner_ids"] = serde_json::json!([UUID_ALICE]);
+    sub["workflow_state_id"] = serde_json::Value::from(500000008_i64);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10/sub-tasks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([sub])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::List { story_id: 10 });
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("In Progress"));
+    assert!(output.contains("@alice"));
+    assert!(output.contains("Payment form"));
+}
+
+#[tokio::test]
+async fn list_subtasks_empty() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10/sub-tasks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::List { story_id: 10 });
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "No sub-tasks on story 10");
+}
+
+#[tokio::test]
+async fn add_subtask_inherits_parent_fields() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(parent_story_json()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Payment form",
+            "epic_id": 7,
+            "iteration_id": 3,
+            "group_id": GROUP_ID,
+            "parent_story_id": 10,
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            11,
+            "Payment form",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::Add(
+        story::subtask::AddSubtaskArgs {
+            story_id: 10,
+            name: "Payment form".to_string(),
+            description: None,
+            story_type: None,
+            owner: vec![],
+            state: None,
+            estimate: None,
+        },
+    ));
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn detach_subtask_clears_parent() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut sub = full_story_json(11, "Payment form", "");
+    sub["parent_story_id"] = serde_json::Value::from(10);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/11"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&sub))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/11"))
+        .and(body_partial_json(
+            serde_json::json!({ "parent_story_id": null }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            11,
+            "Payment form",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::Detach {
+        story_id: 10,
+        id: 11,
+    });
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output.trim(),
+        "Detached story 11 - Payment form from story 10"
+    );
+}
+
+#[tokio::test]
+async fn detach_rejects_unrelated_story() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/11"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            11,
+            "Payment form",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::Detach {
+        story_id: 10,
+        id: 11,
+    });
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Story 11 is not a sub-task of story 10"));
+}
+
+#[tokio::test]
+async fn promote_completed_task_to_done_subtask() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(full_story_json_with_tasks(
+                10,
+                "Checkout flow",
+                "",
+                vec![task_json(20, 10, "Wire up Stripe\nUse the test keys", true)],
+            )),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10/tasks/20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json(
+            20,
+            10,
+            "Wire up Stripe\nUse the test keys",
+            true,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Wire up Stripe",
+            "description": "Wire up Stripe\nUse the test keys",
+            "workflow_state_id": 500000009,
+            "parent_story_id": 10,
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            21,
+            "Wire up Stripe",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/stories/10/tasks/20"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::Promote(
+        story::subtask::PromoteArgs {
+            story_id: 10,
+            task_id: 20,
+            keep_task: false,
+        },
+    ));
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn promote_keep_task_skips_delete() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            10,
+            "Checkout flow",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/10/tasks/20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json(
+            20,
+            10,
+            "Add receipts",
+            false,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Add receipts",
+            "workflow_state_id": 100,
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            21,
+            "Add receipts",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/stories/10/tasks/20"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = wrap(story::subtask::SubtaskAction::Promote(
+        story::subtask::PromoteArgs {
+            story_id: 10,
+            task_id: 20,
+            keep_task: true,
+        },
+    ));
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
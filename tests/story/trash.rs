@@ -0,0 +1,121 @@
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{full_story_json, mount_default_workflow, story_comment_json, task_json};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+fn deleted_story_json() -> serde_json::Value {
+    let mut story = full_story_json(42, "Fix login bug", "Steps to reproduce");
+    story["workflow_state_id"] = serde_json::Value::from(500000008_i64);
+    story["tasks"] = serde_json::json!([task_json(20, 42, "Write regression test", false)]);
+    story["comments"] = serde_json::json!([story_comment_json(
+        30,
+        42,
+        "Seen on Safari only",
+        UUID_ALICE
+    )]);
+    story
+}
+
+async fn delete_with_trash(server: &MockServer, cache_dir: &std::path::Path) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(deleted_story_json()))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(server)
+        .await;
+
+    let out = crate::support::make_output();
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Delete {
+            id: 42,
+            confirm: true,
+            trash: true,
+        },
+    };
+    let result = story::run(&args, &client, cache_dir.to_path_buf(), &out).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn delete_with_trash_writes_snapshot() {
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    delete_with_trash(&server, tmp.path()).await;
+
+    let path = tmp.path().join("trash").join("story_42.json");
+    let snapshot: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(snapshot["story"]["name"], "Fix login bug");
+    assert_eq!(
+        snapshot["story"]["tasks"][0]["description"],
+        "Write regression test"
+    );
+    assert_eq!(
+        snapshot["story"]["comments"][0]["text"],
+        "Seen on Safari only"
+    );
+    assert!(snapshot["deleted_at"].is_string());
+}
+
+#[tokio::test]
+async fn restore_from_trash_recreates_story() {
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    delete_with_trash(&server, tmp.path()).await;
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Fix login bug",
+            "description": "Steps to reproduce",
+            "workflow_state_id": 500000008,
+            "tasks": [{ "description": "Write regression test", "complete": false }],
+            "comments": [{ "text": "Seen on Safari only", "author_id": UUID_ALICE }],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            57,
+            "Fix login bug",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Restore { from_trash: 42 },
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output.trim(), "Restored story 42 as 57 - Fix login bug");
+    assert!(!tmp.path().join("trash").join("story_42.json").exists());
+}
+
+#[tokio::test]
+async fn restore_without_snapshot_errors() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = story::StoryArgs {
+        action: story::StoryAction::Restore { from_trash: 42 },
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("No trashed story 42 found"));
+}
@@ -1,11 +1,11 @@
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{body_partial_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
 
 use crate::support::{
-    default_icon, full_story_json, make_dry_run_output, member_json, workflow_json,
-    workflow_state_json,
+    default_icon, full_story_json, iteration_slim_json, make_dry_run_output, member_json,
+    workflow_json, workflow_state_json,
 };
 use crate::{UUID_ALICE, make_update_args};
 use shortcut_cli::{api, commands::story};
@@ -581,3 +581,51 @@ async fn add_owner_appends_to_existing() {
     assert!(sent_ids.contains(&serde_json::json!(UUID_ALICE)));
     assert!(sent_ids.contains(&serde_json::json!(crate::UUID_BOB)));
 }
+
+#[tokio::test]
+async fn relative_iteration_uses_group() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let web = "cccccccc-cccc-cccc-cccc-cccccccccccc";
+    let mobile = "dddddddd-dddd-dddd-dddd-dddddddddddd";
+
+    // Both teams have an iteration running today
+    let today = chrono::Utc::now().date_naive();
+    let iteration = |id: i64, group: &str| {
+        let mut iteration = iteration_slim_json(
+            id,
+            &format!("Sprint {id}"),
+            &(today - chrono::Duration::days(3)).to_string(),
+            &(today + chrono::Duration::days(3)).to_string(),
+        );
+        iteration["group_ids"] = serde_json::json!([group]);
+        iteration
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            iteration(24, web),
+            iteration(90, mobile)
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/42"))
+        .and(body_partial_json(serde_json::json!({"iteration_id": 90})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(42, "My Story", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let mut update_args = make_update_args(42);
+    update_args.iteration_id = Some(shortcut_cli::commands::iteration::IterationRef::Current);
+    update_args.group = Some(mobile.to_string());
+    let args = story::StoryArgs {
+        action: story::StoryAction::Update(Box::new(update_args)),
+    };
+    let result = story::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
@@ -0,0 +1,402 @@
+use std::time::Duration;
+
+use clap::Parser;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::UUID_ALICE;
+use crate::support::{
+    default_icon, full_story_json, history_action_story_update_json, history_entry_json,
+    history_reference_workflow_state_json, member_json, mount_default_workflow, story_comment_json,
+};
+use shortcut_cli::cli::{Cli, Command};
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
+use shortcut_cli::{api, commands::story};
+
+fn make_watch_args(until_state: Option<&str>, exec: Option<&str>) -> story::WatchArgs {
+    story::WatchArgs {
+        id: 42,
+        interval: Duration::from_millis(10),
+        until_state: until_state.map(String::from),
+        exec: exec.map(String::from),
+    }
+}
+
+fn wrap(args: story::WatchArgs) -> story::StoryArgs {
+    story::StoryArgs {
+        action: story::StoryAction::Watch(args),
+    }
+}
+
+async fn mount_members(server: &MockServer) {
+    let members = serde_json::json!([member_json(
+        UUID_ALICE,
+        "alice",
+        "Alice Smith",
+        "admin",
+        false,
+        Some(default_icon())
+    )]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/members"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&members))
+        .mount(server)
+        .await;
+}
+
+/// First poll: story is Unstarted with no history. Later polls: story is Done
+/// with a state-change event and a new comment.
+async fn mount_story_progression(server: &MockServer) {
+    let before = full_story_json(42, "Fix login bug", "");
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&before))
+        .up_to_n_times(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .up_to_n_times(1)
+        .mount(server)
+        .await;
+
+    let mut after = full_story_json(42, "Fix login bug", "");
+    after["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    after["comments"] =
+        serde_json::json!([story_comment_json(7, 42, "Verified on staging", UUID_ALICE)]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&after))
+        .mount(server)
+        .await;
+
+    let history = serde_json::json!([history_entry_json(
+        "22222222-2222-2222-2222-222222222222",
+        "2024-01-02T00:00:00Z",
+        Some(UUID_ALICE),
+        vec![history_action_story_update_json(
+            42,
+            "Fix login bug",
+            "bug",
+            serde_json::json!({
+                "workflow_state_id": { "old": 500000007, "new": 500000009 }
+            }),
+        )],
+        vec![
+            history_reference_workflow_state_json(500000007, "Unstarted", "unstarted"),
+            history_reference_workflow_state_json(500000009, "Done", "done"),
+        ],
+    )]);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&history))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn watch_prints_changes_until_state() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+    mount_story_progression(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Watching story 42 - Fix login bug (Unstarted), polling every 10ms",
+            "[2024-01-02T00:00:00Z] @alice: updated story 42 \"Fix login bug\": state: Unstarted -> Done",
+            "[2024-01-01T00:00:00+00:00] @alice commented: Verified on staging",
+            "Story 42 reached Done",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn watch_exits_when_already_in_target_state() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            42,
+            "Fix login bug",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Unstarted"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("Story 42 is already in Unstarted"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn watch_runs_exec_on_change() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+    mount_story_progression(&server).await;
+
+    let log = tmp.path().join("exec.log");
+    let cmd = format!(
+        "echo \"changed $SHORTCUT_STORY_ID $SHORTCUT_STORY_STATE\" >> {}",
+        log.display()
+    );
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), Some(&cmd))),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "changed 42 Done\n");
+}
+
+#[tokio::test]
+async fn watch_prints_state_change_without_history_entry() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(
+            42,
+            "Fix login bug",
+            "",
+        )))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    let mut after = full_story_json(42, "Fix login bug", "");
+    after["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&after))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("\nState: Unstarted -> Done\nStory 42 reached Done\n"));
+}
+
+#[tokio::test]
+async fn watch_retries_after_a_failed_poll() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+
+    // Mocks are tried in mount order: the first poll fails, the next succeeds
+    let before = full_story_json(42, "Fix login bug", "");
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&before))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(
+            ResponseTemplate::new(503).set_body_json(serde_json::json!({"message": "Busy"})),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    let mut after = full_story_json(42, "Fix login bug", "");
+    after["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&after))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/42/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok(), "{result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[1].starts_with("Failed to get story: "), "{output}");
+    assert!(lines[1].ends_with("; retrying in 10ms"), "{output}");
+    assert_eq!(
+        lines[2..],
+        ["State: Unstarted -> Done", "Story 42 reached Done"]
+    );
+}
+
+#[tokio::test]
+async fn watch_prints_json_events() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+    mount_story_progression(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let events: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let kinds: Vec<&str> = events
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        ["watching", "history", "comment", "state", "reached"]
+    );
+    assert_eq!(events[1]["member"], "alice");
+    assert_eq!(
+        events[1]["change"],
+        "updated story 42 \"Fix login bug\": state: Unstarted -> Done"
+    );
+    assert_eq!(events[2]["text"], "Verified on staging");
+    assert_eq!(
+        events[3],
+        serde_json::json!({"event": "state", "from": "Unstarted", "to": "Done"})
+    );
+    assert_eq!(events[4]["state"], "Done");
+}
+
+#[tokio::test]
+async fn watch_quiet_prints_only_new_states() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Quiet, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_members(&server).await;
+    mount_story_progression(&server).await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = story::run(
+        &wrap(make_watch_args(Some("Done"), None)),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "Done\n");
+}
+
+#[test]
+fn watch_parses_interval_units() {
+    for (arg, expected) in [
+        ("45", Duration::from_secs(45)),
+        ("30s", Duration::from_secs(30)),
+        ("5m", Duration::from_secs(300)),
+        ("1h", Duration::from_secs(3600)),
+        ("250ms", Duration::from_millis(250)),
+    ] {
+        let cli =
+            Cli::try_parse_from(["shortcut", "story", "watch", "--id", "1", "--interval", arg])
+                .unwrap();
+        let Command::Story(args) = cli.command else {
+            panic!("expected story command");
+        };
+        let story::StoryAction::Watch(watch) = args.action else {
+            panic!("expected watch action");
+        };
+        assert_eq!(watch.interval, expected);
+    }
+}
+
+#[test]
+fn watch_rejects_invalid_interval() {
+    for arg in [
+        "soon",
+        "10d",
+        "0s",
+        "999999999999999999h",
+        "999999999999999999m",
+    ] {
+        let result =
+            Cli::try_parse_from(["shortcut", "story", "watch", "--id", "1", "--interval", arg]);
+        assert!(result.is_err(), "expected '{arg}' to be rejected");
+    }
+}
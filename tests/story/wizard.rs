@@ -177,7 +177,7 @@ fn wizard_all_fields() {
     );
     assert_eq!(result.estimate, Some(5));
     assert_eq!(result.labels, vec!["backend", "urgent"]);
-    assert_eq!(result.iteration_id, Some(10));
+    assert_eq!(result.iteration_id, Some(10.into()));
     assert_eq!(result.custom_fields, vec!["Priority=High"]);
 }
 
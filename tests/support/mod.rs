@@ -160,6 +160,20 @@ pub fn full_epic_json(id: i64, name: &str, description: &str) -> serde_json::Val
     epic
 }
 
+/// Build a JSON value representing a valid `Health` response object for an epic.
+pub fn health_json(epic_id: i64, status: &str, text: &str, created_at: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": "cccccccc-cccc-cccc-cccc-cccccccccccc",
+        "entity_type": "health",
+        "epic_id": epic_id,
+        "status": status,
+        "text": text,
+        "author_id": "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa",
+        "created_at": created_at,
+        "updated_at": created_at
+    })
+}
+
 /// Build a JSON value representing a valid `Workflow` response object.
 pub fn workflow_json(id: i64, name: &str, states: Vec<serde_json::Value>) -> serde_json::Value {
     serde_json::json!({
@@ -391,6 +405,27 @@ pub fn story_json(id: i64, name: &str, description: Option<&str>) -> serde_json:
     serde_json::Value::Object(m)
 }
 
+/// Build a completed `StorySlim` with explicit lifecycle timestamps.
+pub fn completed_story_json(
+    id: i64,
+    name: &str,
+    story_type: &str,
+    created_at: &str,
+    started_at: Option<&str>,
+    completed_at: &str,
+) -> serde_json::Value {
+    let mut story = story_json(id, name, None);
+    story["story_type"] = serde_json::Value::from(story_type);
+    story["created_at"] = serde_json::Value::from(created_at);
+    story["started"] = serde_json::Value::from(started_at.is_some());
+    story["started_at"] = serde_json::json!(started_at);
+    story["completed"] = serde_json::Value::from(true);
+    story["completed_at"] = serde_json::Value::from(completed_at);
+    story["updated_at"] = serde_json::Value::from(completed_at);
+    story["workflow_state_id"] = serde_json::Value::from(500000009_i64);
+    story
+}
+
 /// Build a JSON value representing a valid full `Story` response object.
 ///
 /// The full `Story` type (returned by create/update/get) requires `description`
@@ -577,6 +612,27 @@ pub fn objective_json(id: i64, name: &str, state: &str, description: &str) -> se
     })
 }
 
+/// Build a JSON value representing a valid numeric `KeyResult` response object.
+pub fn key_result_json(
+    id: &str,
+    objective_id: i64,
+    name: &str,
+    initial: &str,
+    current: &str,
+    target: &str,
+) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "objective_id": objective_id,
+        "name": name,
+        "type": "numeric",
+        "progress": 0,
+        "initial_observed_value": { "numeric_value": initial },
+        "current_observed_value": { "numeric_value": current },
+        "current_target_value": { "numeric_value": target }
+    })
+}
+
 /// Build a JSON value representing a valid `Category` response object.
 pub fn category_json(id: i64, name: &str, color: Option<&str>) -> serde_json::Value {
     serde_json::json!({
@@ -653,6 +709,19 @@ pub fn iteration_json(id: i64, name: &str, start_date: &str, end_date: &str) ->
     })
 }
 
+/// Build a JSON value representing a valid `IterationSlim` (as returned by
+/// `list_iterations`), which has no `description`.
+pub fn iteration_slim_json(
+    id: i64,
+    name: &str,
+    start_date: &str,
+    end_date: &str,
+) -> serde_json::Value {
+    let mut iteration = iteration_json(id, name, start_date, end_date);
+    iteration.as_object_mut().unwrap().remove("description");
+    iteration
+}
+
 /// Build a JSON value representing a valid `Milestone` response object.
 pub fn milestone_json(id: i64, name: &str, state: &str) -> serde_json::Value {
     serde_json::json!({
@@ -10,6 +10,8 @@ mod get;
 mod init;
 #[path = "template/list.rs"]
 mod list;
+#[path = "template/plan_stl.rs"]
+mod plan_stl;
 #[path = "template/run_stl.rs"]
 mod run_stl;
 #[path = "template/sync_stl.rs"]
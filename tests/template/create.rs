@@ -1,8 +1,8 @@
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{body_partial_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::TEMPLATE_UUID;
-use crate::support::entity_template_json;
+use crate::support::{entity_template_json, iteration_slim_json};
 use shortcut_cli::{api, commands::template};
 
 #[tokio::test]
@@ -34,6 +34,7 @@ async fn create_template_minimal() {
             estimate: None,
             labels: vec![],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
@@ -70,9 +71,62 @@ async fn create_template_with_story_contents() {
             estimate: Some(1),
             labels: vec!["bug".to_string()],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
     let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn create_template_with_relative_iteration() {
+    let out = crate::support::make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let today = chrono::Utc::now().date_naive();
+    let days = |n: i64| (today + chrono::Duration::days(n)).to_string();
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            iteration_slim_json(23, "Sprint 23", &days(-10), &days(-4)),
+            iteration_slim_json(24, "Sprint 24", &days(-3), &days(3)),
+        ])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/entity-templates"))
+        .and(body_partial_json(
+            serde_json::json!({"story_contents": {"iteration_id": 24}}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(entity_template_json(TEMPLATE_UUID, "Sprint Work")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = template::TemplateArgs {
+        action: template::TemplateAction::Create(Box::new(template::CreateArgs {
+            name: "Sprint Work".to_string(),
+            story_name: None,
+            description: None,
+            description_file: None,
+            story_type: None,
+            owner: vec![],
+            state: None,
+            epic_id: None,
+            estimate: None,
+            labels: vec![],
+            iteration_id: Some(shortcut_cli::commands::iteration::IterationRef::Current),
+            group: None,
+            custom_fields: vec![],
+        })),
+    };
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
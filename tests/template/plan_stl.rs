@@ -0,0 +1,337 @@
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::support::{full_epic_json, full_story_json, make_output, mount_default_workflow};
+use shortcut_cli::stl::state;
+use shortcut_cli::{api, commands::template};
+
+fn plan_args(file: &str, plan_file: &str) -> template::TemplateArgs {
+    template::TemplateArgs {
+        action: template::TemplateAction::Plan(Box::new(template::plan_stl::PlanArgs {
+            file: file.to_string(),
+            plan_file: Some(plan_file.to_string()),
+            state: None,
+            prune: false,
+            vars: vec![],
+        })),
+    }
+}
+
+fn apply_args(plan_file: &str) -> template::TemplateArgs {
+    template::TemplateArgs {
+        action: template::TemplateAction::Apply(Box::new(template::apply_stl::ApplyArgs {
+            plan: plan_file.to_string(),
+            confirm: true,
+        })),
+    }
+}
+
+const TEMPLATE: &str = r#"
+version: 1
+operations:
+  - action: create
+    entity: epic
+    alias: my-epic
+    fields:
+      name: "New Name"
+      description: "Unchanged"
+  - action: create
+    entity: story
+    alias: my-story
+    fields:
+      name: "My Story"
+      epic_id: $ref(my-epic)
+"#;
+
+/// Write `TEMPLATE` and a state in which `my-epic` is already synced as epic 55.
+fn setup(dir: &tempfile::TempDir) -> (String, String) {
+    let file = dir.path().join("test.shortcut.yml");
+    std::fs::write(&file, TEMPLATE).unwrap();
+    let file = file.to_str().unwrap().to_string();
+
+    let mut existing = state::SyncState::new();
+    existing.resources.insert(
+        "my-epic".to_string(),
+        state::ResourceState::Single {
+            entity: "epic".to_string(),
+            id: serde_json::json!(55),
+            tasks: None,
+        },
+    );
+    let state_path = state::default_state_path(&file);
+    state::save_state(&existing, &state_path).unwrap();
+
+    let plan_file = dir.path().join("plan.json");
+    (file, plan_file.to_str().unwrap().to_string())
+}
+
+async fn mount_live_epic(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/55"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_epic_json(
+            55,
+            "Old Name",
+            "Unchanged",
+        )))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn plan_writes_actions_and_field_diffs() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_live_epic(&server).await;
+
+    // Planning must not write anything
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let (file, plan_file) = setup(&tmp);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let result = template::run(
+        &plan_args(&file, &plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&plan_file).unwrap()).unwrap();
+    assert_eq!(plan["version"], 1);
+    let canonical = std::fs::canonicalize(&file).unwrap();
+    assert_eq!(plan["template"]["path"], canonical.to_str().unwrap());
+    let actions = plan["actions"].as_array().unwrap();
+    assert_eq!(actions.len(), 2);
+
+    // The unchanged description is left out of the diff
+    assert_eq!(actions[0]["action"], "update");
+    assert_eq!(actions[0]["existing_id"], 55);
+    assert_eq!(
+        actions[0]["changes"],
+        serde_json::json!([{"field": "name", "from": "Old Name", "to": "New Name"}])
+    );
+
+    assert_eq!(actions[1]["action"], "create");
+    assert_eq!(actions[1]["alias"], "my-story");
+    let changes = actions[1]["changes"].as_array().unwrap();
+    assert!(changes.contains(&serde_json::json!({"field": "epic_id", "from": null, "to": 55})));
+}
+
+#[tokio::test]
+async fn apply_runs_saved_plan() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_live_epic(&server).await;
+
+    let (file, plan_file) = setup(&tmp);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    template::run(
+        &plan_args(&file, &plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/epics/55"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_epic_json(
+            55,
+            "New Name",
+            "Unchanged",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(full_story_json(200, "My Story", "")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = template::run(
+        &apply_args(&plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let loaded = state::load_state(&state::default_state_path(&file))
+        .unwrap()
+        .unwrap();
+    assert!(loaded.resources.contains_key("my-story"));
+}
+
+#[tokio::test]
+async fn apply_refuses_when_template_changed() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_live_epic(&server).await;
+
+    let (file, plan_file) = setup(&tmp);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    template::run(
+        &plan_args(&file, &plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap();
+
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    std::fs::write(&file, TEMPLATE.replace("New Name", "Other Name")).unwrap();
+    let err = template::run(
+        &apply_args(&plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("has changed since the plan was made"),
+        "got: {err}"
+    );
+}
+
+#[tokio::test]
+async fn apply_refuses_when_state_changed() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_live_epic(&server).await;
+
+    let (file, plan_file) = setup(&tmp);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    template::run(
+        &plan_args(&file, &plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap();
+
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // Another sync ran in between
+    let state_path = state::default_state_path(&file);
+    let mut current = state::load_state(&state_path).unwrap().unwrap();
+    current.resources.remove("my-epic");
+    state::save_state(&current, &state_path).unwrap();
+
+    let err = template::run(
+        &apply_args(&plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().starts_with("state file"), "got: {err}");
+}
+
+#[tokio::test]
+async fn plan_stores_absolute_paths() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    mount_default_workflow(&server).await;
+    mount_live_epic(&server).await;
+
+    let (file, plan_file) = setup(&tmp);
+    std::fs::create_dir(tmp.path().join("sub")).unwrap();
+    // An indirect path, as a relative one typed elsewhere would be
+    let indirect = tmp
+        .path()
+        .join("sub/../test.shortcut.yml")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    template::run(
+        &plan_args(&indirect, &plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await
+    .unwrap();
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&plan_file).unwrap()).unwrap();
+    let canonical = |path: std::path::PathBuf| std::fs::canonicalize(path).unwrap();
+    assert_eq!(
+        plan["template"]["path"],
+        canonical(file.clone().into()).to_str().unwrap()
+    );
+    assert_eq!(
+        plan["state"]["path"],
+        canonical(state::default_state_path(&file))
+            .to_str()
+            .unwrap()
+    );
+
+    // The plan still matches the template it was made from
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/epics/55"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_epic_json(
+            55,
+            "New Name",
+            "Unchanged",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(full_story_json(200, "My Story", "")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let result = template::run(
+        &apply_args(&plan_file),
+        &client,
+        tmp.path().to_path_buf(),
+        &out,
+    )
+    .await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
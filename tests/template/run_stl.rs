@@ -1,9 +1,10 @@
-use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::matchers::{body_partial_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::support::{
-    full_epic_json, full_story_json, group_json, iteration_json, label_json, make_dry_run_output,
-    make_output, member_json, mount_default_workflow, story_comment_json, story_link_json,
+    epic_json, full_epic_json, full_story_json, group_json, iteration_json, iteration_slim_json,
+    label_json, make_dry_run_output, make_output, member_json, mount_default_workflow,
+    search_story_result_json, story_comment_json, story_json, story_link_json, task_json,
 };
 use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
 use shortcut_cli::{api, commands::template};
@@ -14,6 +15,7 @@ fn run_args(file: &str) -> template::TemplateArgs {
             file: file.to_string(),
             confirm: true,
             vars: vec![],
+            concurrency: 1,
         })),
     }
 }
@@ -24,6 +26,18 @@ fn run_args_with_vars(file: &str, vars: Vec<(String, String)>) -> template::Temp
             file: file.to_string(),
             confirm: true,
             vars,
+            concurrency: 1,
+        })),
+    }
+}
+
+fn run_args_with_concurrency(file: &str, concurrency: usize) -> template::TemplateArgs {
+    template::TemplateArgs {
+        action: template::TemplateAction::Run(Box::new(template::run_stl::RunArgs {
+            file: file.to_string(),
+            confirm: true,
+            vars: vec![],
+            concurrency,
         })),
     }
 }
@@ -1038,3 +1052,1262 @@ operations:
     let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
     assert!(result.is_ok(), "Expected ok, got: {result:?}");
 }
+
+#[tokio::test]
+async fn run_find_epic_by_name_then_create_story() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+
+    // An archived epic with the same name is ignored
+    let mut archived = epic_json(40, "Auth Hardening", None);
+    archived["archived"] = serde_json::json!(true);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            archived,
+            epic_json(55, "auth hardening", None),
+            epic_json(56, "Billing", None),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({"epic_id": 55})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            200,
+            "JWT Story",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: find
+    entity: epic
+    alias: auth
+    fields:
+      name: "Auth Hardening"
+  - action: create
+    entity: story
+    fields:
+      name: "JWT Story"
+      epic_id: $ref(auth)
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
+
+#[tokio::test]
+async fn run_find_missing_fails_by_default() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/iterations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            iteration_slim_json(24, "Sprint 24", "2024-03-04", "2024-03-15"),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: find
+    entity: iteration
+    alias: sprint
+    fields:
+      name: "Sprint 99"
+  - action: create
+    entity: story
+    fields:
+      name: "Planned work"
+      iteration_id: $ref(sprint)
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let _ = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed["summary"]["failed"], 1);
+    assert_eq!(parsed["operations"][0]["action"], "find");
+    assert_eq!(
+        parsed["operations"][0]["error"],
+        "no iteration found with name 'Sprint 99'"
+    );
+}
+
+#[tokio::test]
+async fn run_find_missing_with_empty_policy_resolves_to_null() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/labels"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([label_json(1, "backend"),])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(200, "Cleanup", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: find
+    entity: label
+    alias: tech-debt
+    on_missing: empty
+    fields:
+      name: "tech-debt"
+  - action: create
+    entity: story
+    fields:
+      name: "Cleanup"
+      description: "Label: $ref(tech-debt)"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed["summary"]["succeeded"], 2);
+    assert_eq!(
+        parsed["operations"][0]["result"],
+        serde_json::json!({ "id": null })
+    );
+}
+
+#[tokio::test]
+async fn run_find_story_by_query_in_dry_run() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    // Lookups still hit the API in dry-run mode; writes don't
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", "is:blocked epic:55"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [search_story_result_json(7, "Flaky login", "bug")],
+            "next": null,
+            "total": 1,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/7/comments"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: find
+    entity: story
+    alias: blocked
+    fields:
+      query: "is:blocked epic:55"
+  - action: comment
+    entity: story
+    id: $ref(blocked)
+    fields:
+      text: "Still blocked?"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[1/2] Found story 7 - Flaky login"));
+    assert!(output.contains("[2/2] comment story"));
+}
+
+#[tokio::test]
+async fn run_find_story_by_punctuated_name_reads_every_page() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let query = "name:\"Fix: login (v2)\" !is:archived";
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", query))
+        .and(query_param("next", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [search_story_result_json(9, "Fix: login (v2)", "bug")],
+            "next": null,
+            "total": 2,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", query))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [search_story_result_json(8, "Fix: login (v2) follow-up", "bug")],
+            "next": "page-2",
+            "total": 2,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/9/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(story_comment_json(
+            1,
+            9,
+            "Shipped",
+            "00000000-0000-0000-0000-000000000001",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: find
+    entity: story
+    alias: fix
+    fields:
+      name: "Fix: login (v2)"
+  - action: comment
+    entity: story
+    id: $ref(fix)
+    fields:
+      text: "Shipped"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
+
+#[tokio::test]
+async fn run_for_each_updates_unfinished_epic_stories() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut done = story_json(1, "Cart", None);
+    done["completed"] = serde_json::json!(true);
+    Mock::given(method("GET"))
+        .and(path("/api/v3/epics/55/stories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            done,
+            story_json(2, "Payment", None),
+            story_json(3, "Receipts", None),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    for (id, name) in [(2, "Payment"), (3, "Receipts")] {
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/v3/stories/{id}")))
+            .and(body_partial_json(serde_json::json!({
+                "name": format!("[Q3] {name}"),
+                "labels": [{ "name": "q3" }],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(id, name, "")))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+vars:
+  epic: 55
+operations:
+  - action: update
+    entity: story
+    for_each:
+      epic: $var(epic)
+      done: false
+    fields:
+      name: "[Q3] $item(name)"
+      labels: [q3]
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
+
+#[tokio::test]
+async fn run_for_each_query_dry_run_reports_progress() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .and(query_param("query", "is:blocked !is:archived"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                search_story_result_json(7, "Flaky login", "bug"),
+                search_story_result_json(8, "Slow export", "bug"),
+            ],
+            "next": null,
+            "total": 2,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: comment
+    entity: story
+    for_each:
+      query: "is:blocked"
+    fields:
+      text: "Is $item(name) still blocked?"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("comment story for each of 2 stories matching 'is:blocked'"));
+    assert!(output.contains("[1/2] comment story"));
+    assert!(output.contains("\"text\": \"Is Flaky login still blocked?\""));
+    assert!(output.contains("[2/2] comment story"));
+}
+
+#[tokio::test]
+async fn run_dry_run_placeholders_follow_progress_numbers() {
+    let (out, buf) = make_dry_run_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "data": [
+                        search_story_result_json(7, "Flaky login", "bug"),
+                        search_story_result_json(8, "Slow export", "bug"),
+                    ],
+                    "next": null,
+                    "total": 2,
+                }))
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: comment
+    entity: story
+    for_each:
+      query: "is:blocked"
+    fields:
+      text: "Still blocked?"
+  - action: create
+    entity: epic
+    alias: cleanup
+    fields:
+      name: "Cleanup"
+  - action: create
+    entity: story
+    fields:
+      name: "Triage"
+      epic_id: $ref(cleanup)
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args_with_concurrency(&file, 4);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[3/4] create epic"), "{output}");
+    assert!(output.contains("[4/4] create story"), "{output}");
+    assert!(output.contains("\"epic_id\": 3"), "{output}");
+}
+
+#[tokio::test]
+async fn run_for_each_over_repeat_results() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({"name": "Story A"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(100, "Story A", "")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({"name": "Story B"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(101, "Story B", "")))
+        .mount(&server)
+        .await;
+    for story_id in [100, 101] {
+        Mock::given(method("POST"))
+            .and(path(format!("/api/v3/stories/{story_id}/tasks")))
+            .and(body_partial_json(
+                serde_json::json!({"description": "Review"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(task_json(
+                story_id * 10,
+                story_id,
+                "Review",
+                false,
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: create
+    entity: story
+    alias: stories
+    repeat:
+      - { name: "Story A" }
+      - { name: "Story B" }
+  - action: create
+    entity: task
+    for_each:
+      items: $ref(stories)
+    fields:
+      story_id: $item(id)
+      description: "Review"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed["summary"]["total"], 4);
+    assert_eq!(parsed["summary"]["succeeded"], 4);
+}
+
+#[tokio::test]
+async fn run_when_skips_operations() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(epic_json(1, "Docs", None)))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(100, "Setup", "")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/100/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(story_comment_json(
+            1,
+            100,
+            "Still open",
+            "00000000-0000-0000-0000-000000000001",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+vars:
+  include_docs: false
+operations:
+  - action: create
+    entity: epic
+    alias: docs
+    when: $var(include_docs)
+    fields:
+      name: "Docs"
+  - action: create
+    entity: story
+    alias: setup
+    fields:
+      name: "Setup"
+  - action: comment
+    entity: story
+    id: $ref(setup)
+    when: "not $ref(setup.completed)"
+    fields:
+      text: "Still open"
+  - action: comment
+    entity: story
+    id: $ref(setup)
+    when: "exists($ref(docs))"
+    fields:
+      text: "See the docs epic"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let statuses: Vec<&str> = parsed["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| op["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, vec!["skipped", "success", "success", "skipped"]);
+    assert_eq!(parsed["summary"]["total"], 4);
+    assert_eq!(parsed["summary"]["succeeded"], 2);
+    assert_eq!(parsed["summary"]["skipped"], 2);
+}
+
+#[tokio::test]
+async fn run_when_reports_skips_in_progress() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(100, "A", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+vars:
+  env: staging
+operations:
+  - action: create
+    entity: story
+    when: "$var(env) == 'production'"
+    repeat:
+      - { name: "Deploy" }
+      - { name: "Announce" }
+  - action: create
+    entity: story
+    fields:
+      name: "A"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("[1/3] Skipped create story (when: $var(env) == 'production')"));
+    assert!(output.contains("[2/3] Skipped create story"));
+    assert!(output.contains("Executed 1/3 operations successfully (2 skipped)."));
+}
+
+#[tokio::test]
+async fn run_skips_operations_that_use_a_skipped_result() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({"name": "Fallback"})))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(full_story_json(100, "Fallback", "")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+vars:
+  include_docs: false
+operations:
+  - action: create
+    entity: epic
+    alias: docs
+    when: $var(include_docs)
+    fields:
+      name: "Docs"
+  - action: create
+    entity: story
+    alias: guide
+    fields:
+      name: "Guide"
+      epic_id: $ref(docs)
+  - action: comment
+    entity: story
+    id: $ref(guide)
+    fields:
+      text: "Review the guide"
+  - action: create
+    entity: story
+    when: "not exists($ref(docs))"
+    fields:
+      name: "Fallback"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let operations = parsed["operations"].as_array().unwrap();
+    let statuses: Vec<&str> = operations
+        .iter()
+        .map(|op| op["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, vec!["skipped", "skipped", "skipped", "success"]);
+    assert_eq!(operations[0]["reason"], "when: $var(include_docs)");
+    assert_eq!(operations[1]["reason"], "'docs' was skipped");
+    assert_eq!(operations[2]["reason"], "'guide' was skipped");
+    assert!(operations[3].get("reason").is_none());
+    assert_eq!(parsed["summary"]["skipped"], 3);
+}
+
+#[tokio::test]
+async fn run_include_resolves_namespaced_refs() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(
+            serde_json::json!({"name": "Set up CI for billing"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(
+            300,
+            "Set up CI for billing",
+            "",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/300/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(story_comment_json(
+            1,
+            300,
+            "Pipeline ready",
+            "00000000-0000-0000-0000-000000000001",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    std::fs::write(
+        tmp.path().join("ci.shortcut.yml"),
+        r#"
+version: 1
+vars:
+  service: web
+operations:
+  - action: create
+    entity: story
+    alias: pipeline
+    fields:
+      name: "Set up CI for $var(service)"
+"#,
+    )
+    .unwrap();
+    let yaml = r#"
+version: 1
+vars:
+  service: billing
+include:
+  - file: ci.shortcut.yml
+    vars:
+      service: $var(service)
+operations:
+  - action: comment
+    entity: story
+    id: $ref(ci.pipeline)
+    fields:
+      text: "Pipeline ready"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed["summary"]["succeeded"], 2);
+}
+
+#[tokio::test]
+async fn run_coerces_typed_var_overrides() {
+    let out = make_output();
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(
+            serde_json::json!({"name": "Launch", "estimate": 5}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(1, "Launch", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+vars:
+  points:
+    type: int
+    required: true
+operations:
+  - action: create
+    entity: story
+    fields:
+      name: "Launch"
+      estimate: $var(points)
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args_with_vars(&file, vec![("points".to_string(), "5".to_string())]);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+}
+
+#[tokio::test]
+async fn run_on_error_rollback_undoes_completed_operations() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_epic_json(55, "Epic", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // The story is read before it is updated so the update can be reverted
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/200"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "Old", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/200"))
+        .and(body_partial_json(serde_json::json!({"name": "New"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "New", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/labels"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("name is taken"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Rollback: restore the story, then delete the epic
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/200"))
+        .and(body_partial_json(serde_json::json!({"name": "Old"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "Old", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/epics/55"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+on_error: rollback
+operations:
+  - action: create
+    entity: epic
+    fields:
+      name: "Epic"
+  - action: update
+    entity: story
+    id: 200
+    fields:
+      name: "New"
+  - action: create
+    entity: label
+    fields:
+      name: "Label"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let rollback = parsed["rollback"].as_array().unwrap();
+    assert_eq!(rollback.len(), 2);
+    assert_eq!(rollback[0]["index"], 1);
+    assert_eq!(rollback[0]["status"], "rolled_back");
+    assert_eq!(rollback[0]["detail"], "restored name");
+    assert_eq!(rollback[1]["index"], 0);
+    assert_eq!(rollback[1]["id"], 55);
+    assert_eq!(rollback[1]["status"], "rolled_back");
+}
+
+#[tokio::test]
+async fn run_on_error_rollback_clears_fields_that_were_null() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    // The story has no estimate before the update
+    Mock::given(method("GET"))
+        .and(path("/api/v3/stories/200"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "Story", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/200"))
+        .and(body_partial_json(serde_json::json!({"estimate": 5})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "Story", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/labels"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("name is taken"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Rollback must send the null explicitly to clear the estimate again
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/stories/200"))
+        .and(body_partial_json(serde_json::json!({"estimate": null})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(full_story_json(200, "Story", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+on_error: rollback
+operations:
+  - action: update
+    entity: story
+    id: 200
+    fields:
+      estimate: 5
+  - action: create
+    entity: label
+    fields:
+      name: "Label"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let rollback = parsed["rollback"].as_array().unwrap();
+    assert_eq!(rollback.len(), 1);
+    assert_eq!(rollback[0]["status"], "rolled_back");
+    assert_eq!(rollback[0]["detail"], "restored estimate");
+}
+
+#[tokio::test]
+async fn run_on_error_rollback_reports_what_was_not_rolled_back() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/stories/200"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/labels"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("name is taken"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Only the failing operation asks for a rollback
+    let yaml = r#"
+version: 1
+operations:
+  - action: delete
+    entity: story
+    id: 200
+  - action: create
+    entity: label
+    on_error: rollback
+    fields:
+      name: "Label"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("Rolled back 0/1 completed operations."),
+        "{output}"
+    );
+    assert!(output.contains("Not rolled back:"), "{output}");
+    assert!(
+        output.contains(
+            "operation 1 (delete story 200): not_supported — deleted entities cannot be restored"
+        ),
+        "{output}"
+    );
+}
+
+#[tokio::test]
+async fn run_on_error_stop_does_not_roll_back() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/epics"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_epic_json(55, "Epic", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/labels"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("name is taken"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/epics/55"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // The epic opts into rollback, but the failing label does not
+    let yaml = r#"
+version: 1
+operations:
+  - action: create
+    entity: epic
+    on_error: rollback
+    fields:
+      name: "Epic"
+  - action: create
+    entity: label
+    fields:
+      name: "Label"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(parsed.get("rollback").is_none());
+}
+
+#[tokio::test]
+async fn run_concurrent_repeat_reports_in_order() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    // The first entry answers last
+    for (id, name, delay) in [(1, "A", 600), (2, "B", 300), (3, "C", 0)] {
+        Mock::given(method("POST"))
+            .and(path("/api/v3/labels"))
+            .and(body_partial_json(serde_json::json!({"name": name})))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(label_json(id, name))
+                    .set_delay(std::time::Duration::from_millis(delay)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: create
+    entity: label
+    alias: labels
+    repeat:
+      - name: "A"
+      - name: "B"
+      - name: "C"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args_with_concurrency(&file, 3);
+    let started = std::time::Instant::now();
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(850),
+        "entries did not run concurrently: {:?}",
+        started.elapsed()
+    );
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let a = output.find("[1/3] Created label 1 - A").unwrap();
+    let b = output.find("[2/3] Created label 2 - B").unwrap();
+    let c = output.find("[3/3] Created label 3 - C").unwrap();
+    assert!(a < b && b < c, "{output}");
+}
+
+#[tokio::test]
+async fn run_concurrent_waits_for_refs() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Json, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    mount_default_workflow(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/epics"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(full_epic_json(55, "Epic", ""))
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories"))
+        .and(body_partial_json(serde_json::json!({"epic_id": 55})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(full_story_json(200, "Story", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/labels"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(label_json(7, "Label")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: create
+    entity: epic
+    alias: epic
+    fields:
+      name: "Epic"
+  - action: create
+    entity: story
+    fields:
+      name: "Story"
+      epic_id: $ref(epic)
+  - action: create
+    entity: label
+    fields:
+      name: "Label"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args_with_concurrency(&file, 4);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let entities: Vec<&str> = parsed["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| op["entity"].as_str().unwrap())
+        .collect();
+    assert_eq!(entities, ["epic", "story", "label"]);
+    assert_eq!(parsed["summary"]["succeeded"], 3);
+}
+
+#[tokio::test]
+async fn run_concurrent_for_each_does_not_wait_for_unrelated_operations() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+    let delay = std::time::Duration::from_millis(400);
+
+    for (id, name) in [(1, "Before"), (2, "After")] {
+        Mock::given(method("POST"))
+            .and(path("/api/v3/labels"))
+            .and(body_partial_json(serde_json::json!({"name": name})))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(label_json(id, name))
+                    .set_delay(delay),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/stories"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "data": [search_story_result_json(7, "Flaky login", "bug")],
+                    "next": null,
+                    "total": 1,
+                }))
+                .set_delay(delay),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/stories/7/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(story_comment_json(
+            1,
+            7,
+            "Still blocked?",
+            "00000000-0000-0000-0000-000000000001",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: create
+    entity: label
+    fields:
+      name: "Before"
+  - action: comment
+    entity: story
+    for_each:
+      query: "is:blocked"
+    fields:
+      text: "Still blocked?"
+  - action: create
+    entity: label
+    fields:
+      name: "After"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = run_args_with_concurrency(&file, 4);
+    let started = std::time::Instant::now();
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_ok(), "Expected ok, got: {result:?}");
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(1000),
+        "the for_each held up the other operations: {:?}",
+        started.elapsed()
+    );
+
+    // Numbered in template order, with the items counted from the for_each on
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let before = output.find("[1/2] Created label 1 - Before").unwrap();
+    let items = output.find("comment story for each of 1").unwrap();
+    let comment = output.find("[2/3] ").unwrap();
+    let after = output.find("[3/3] Created label 2 - After").unwrap();
+    assert!(
+        before < items && items < comment && comment < after,
+        "{output}"
+    );
+}
@@ -598,3 +598,94 @@ operations:
         "Expected key validation error: {output}"
     );
 }
+
+// ── Validation: for_each rejected ──
+
+#[tokio::test]
+async fn sync_rejects_for_each() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let yaml = r#"
+version: 1
+operations:
+  - action: comment
+    entity: story
+    for_each:
+      query: "is:blocked"
+    fields:
+      text: "Ping"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = sync_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("sync does not support 'for_each'"),
+        "Expected for_each validation error: {output}"
+    );
+}
+
+#[tokio::test]
+async fn sync_rejects_when() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let yaml = r#"
+version: 1
+vars:
+  include_docs: false
+operations:
+  - action: create
+    entity: epic
+    alias: docs
+    when: $var(include_docs)
+    fields:
+      name: "Docs"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = sync_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("sync does not support 'when'"),
+        "Expected when validation error: {output}"
+    );
+}
+
+#[tokio::test]
+async fn sync_rejects_rollback() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let server = MockServer::start().await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    let yaml = r#"
+version: 1
+on_error: rollback
+operations:
+  - action: create
+    entity: epic
+    alias: docs
+    fields:
+      name: "Docs"
+"#;
+    let file = write_template(&tmp, yaml);
+    let client = api::client_with_token("test-token", &server.uri()).unwrap();
+    let args = sync_args(&file);
+    let result = template::run(&args, &client, tmp.path().to_path_buf(), &out).await;
+    assert!(result.is_err());
+
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("sync does not support 'on_error: rollback'"),
+        "Expected rollback validation error: {output}"
+    );
+}
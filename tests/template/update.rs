@@ -35,6 +35,7 @@ async fn update_template_name() {
             estimate: None,
             labels: vec![],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
@@ -72,6 +73,7 @@ async fn update_template_story_contents() {
             estimate: Some(3),
             labels: vec![],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
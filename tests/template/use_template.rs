@@ -45,6 +45,7 @@ async fn use_template_minimal() {
             estimate: None,
             labels: vec![],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
@@ -92,6 +93,7 @@ async fn use_template_with_overrides() {
             estimate: Some(5),
             labels: vec!["urgent".to_string()],
             iteration_id: None,
+            group: None,
             custom_fields: vec![],
         })),
     };
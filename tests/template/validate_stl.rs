@@ -1,5 +1,6 @@
 use crate::support::make_output;
 use shortcut_cli::commands::template;
+use shortcut_cli::output::{ColorMode, OutputConfig, OutputMode};
 
 fn write_template(dir: &tempfile::TempDir, yaml: &str) -> String {
     let path = dir.path().join("test.shortcut.yml");
@@ -181,3 +182,106 @@ operations:
         template::validate_stl::run(&template::validate_stl::ValidateArgs { file }, &out).await;
     assert!(result.is_ok(), "Expected ok, got: {result:?}");
 }
+
+#[tokio::test]
+async fn validate_prints_location_and_snippet() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let tmp = tempfile::tempdir().unwrap();
+
+    let yaml = r#"version: 1
+operations:
+  - action: create
+    entity: epic
+    alias: launch
+    fields:
+      name: "Launch"
+  - action: create
+    entity: story
+    fields:
+      name: "Checklist"
+      epic_id: $ref(lanch)
+      colour: red
+"#;
+    let file = write_template(&tmp, yaml);
+    let result = template::validate_stl::run(
+        &template::validate_stl::ValidateArgs { file: file.clone() },
+        &out,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "2 validation error(s) found"
+    );
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        format!(
+            "Validation errors:
+  - {file}:12:16: operation 2: $ref(lanch) references undefined alias 'lanch'
+       |
+    12 |       epic_id: $ref(lanch)
+       |                ^
+  - {file}:13:7: operation 2: unknown field 'colour' for story entity
+       |
+    13 |       colour: red
+       |       ^
+"
+        )
+    );
+}
+
+#[tokio::test]
+async fn validate_prints_location_in_json_template() {
+    let (out, buf) = OutputConfig::with_buffer(OutputMode::Human, ColorMode::Never);
+    let tmp = tempfile::tempdir().unwrap();
+
+    let json = r#"{
+  "version": 1,
+  "operations": [
+    {
+      "action": "create",
+      "entity": "epic",
+      "alias": "launch",
+      "fields": { "name": "Launch" }
+    },
+    {
+      "action": "create",
+      "entity": "story",
+      "fields": {
+        "name": "Checklist",
+        "epic_id": "$ref(lanch)",
+        "colour": "red"
+      }
+    }
+  ]
+}
+"#;
+    let path = tmp.path().join("test.shortcut.json");
+    std::fs::write(&path, json).unwrap();
+    let file = path.to_str().unwrap().to_string();
+    let result = template::validate_stl::run(
+        &template::validate_stl::ValidateArgs { file: file.clone() },
+        &out,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "2 validation error(s) found"
+    );
+    let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        output,
+        format!(
+            r#"Validation errors:
+  - {file}:15:21: operation 2: $ref(lanch) references undefined alias 'lanch'
+       |
+    15 |         "epic_id": "$ref(lanch)",
+       |                     ^
+  - {file}:16:9: operation 2: unknown field 'colour' for story entity
+       |
+    16 |         "colour": "red"
+       |         ^
+"#
+        )
+    );
+}